@@ -741,8 +741,20 @@ where
 			self.transaction_pool.status()
 		);
 
-		// Get the current Validators public keys
-		let validator = EthereumSigner::from(keys[0]).into_account();
+		// A single runtime api instance keeps its storage overlay between calls, so the
+		// conversion rates cached by the runtime are reused for every transaction of a sender.
+		let fee_compatibility_api = self.client.runtime_api();
+
+		// Get the account of the current Validator. Rotated keys keep the account of the
+		// validator they were rotated from, which only the runtime knows about.
+		let validator = match fee_compatibility_api
+			.api_version::<dyn CompatibleFeeApi<Block, AccountId>>(self.parent_hash)
+		{
+			Ok(Some(version)) if version >= 2 => fee_compatibility_api
+				.validator_account(self.parent_hash, keys[0])
+				.unwrap_or_else(|_| EthereumSigner::from(keys[0]).into_account()),
+			_ => EthereumSigner::from(keys[0]).into_account(),
+		};
 
 		let end_reason =
 			loop {
@@ -769,9 +781,7 @@ where
 
 				// Check if the transaction is compatible with the current fee
 				// and the current validator
-				let is_compatible = self
-					.client
-					.runtime_api()
+				let is_compatible = fee_compatibility_api
 					.is_compatible_fee(
 						self.parent_hash,
 						pending_tx.data().clone(),
@@ -68,7 +68,10 @@ pub trait RuntimeApiCollection<
 	+ EthCompatRuntimeApiCollection<Block>
 	+ sp_consensus_aura::AuraApi<Block, stbl_core_primitives::aura::Public>
 	+ sp_consensus_grandpa::GrandpaApi<Block>
-	+ frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Nonce>
+	+ stbl_primitives_aura_equivocation_api::AuraEquivocationApi<
+		Block,
+		stbl_core_primitives::aura::Public,
+	> + frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Nonce>
 	+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
 	+ stbl_primitives_fee_compatible_api::CompatibleFeeApi<Block, AccountId>
 	+ stbl_primitives_zero_gas_transactions_api::ZeroGasTransactionApi<Block>
@@ -87,7 +90,10 @@ where
 		+ EthCompatRuntimeApiCollection<Block>
 		+ sp_consensus_aura::AuraApi<Block, stbl_core_primitives::aura::Public>
 		+ sp_consensus_grandpa::GrandpaApi<Block>
-		+ frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Nonce>
+		+ stbl_primitives_aura_equivocation_api::AuraEquivocationApi<
+			Block,
+			stbl_core_primitives::aura::Public,
+		> + frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Nonce>
 		+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
 		+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
 		+ stbl_primitives_fee_compatible_api::CompatibleFeeApi<Block, AccountId>
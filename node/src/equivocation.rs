@@ -0,0 +1,124 @@
+// Copyright © 2022 STABILITY SOLUTIONS, INC. (“STABILITY”)
+// This file is part of the Stability Global Trust Network client
+// software and accompanying documentation (the “Software”).
+
+// You can download and use the Software for free under the terms of
+// the Stability Open License Agreement as published by Stability on
+// Github at https://github.com/stabilityprotocol/stability/blob/master/LICENSE.
+
+// THE SOFTWARE IS PROVIDED “AS IS” WITHOUT WARRANTY OF ANY KIND.
+// STABILITY EXPRESSLY DISCLAIMS ALL WARRANTIES, EXPRESS OR IMPLIED,
+// INCLUDING MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE, AND
+// NON-INFRINGEMENT. IN NO EVENT SHALL OWNER BE LIABLE FOR ANY
+// INDIRECT, INCIDENTAL, SPECIAL OR CONSEQUENTIAL DAMAGES ARISING
+// OUT OF USE OF THE SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF
+// SUCH DAMAGES.
+
+// Please see the Stability Open License Agreement for more
+// information.
+
+//! Detection and reporting of Aura equivocations.
+//!
+//! Every imported header is indexed by its Aura slot. When two different
+//! headers are seen for the same slot, the author of that slot sealed both of
+//! them and an equivocation report is submitted to the runtime, which removes
+//! and jails the offending validator.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use futures::StreamExt;
+use sc_client_api::BlockchainEvents;
+use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_consensus_aura::{digests::CompatibleDigestItem, AuraApi, Slot};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT},
+	DigestItem,
+};
+use stbl_core_primitives::aura::{Public as AuraId, Signature as AuraSignature};
+use stbl_primitives_aura_equivocation_api::{AuraEquivocationApi, EquivocationProof};
+
+/// Number of past slots for which imported headers are remembered.
+const SLOTS_TO_KEEP: u64 = 1024;
+
+const LOG_TARGET: &str = "aura-equivocation";
+
+/// Watches block imports and reports the Aura equivocations found.
+pub async fn run_aura_equivocation_reporter<B, C>(
+	client: Arc<C>,
+	offchain_tx_pool_factory: OffchainTransactionPoolFactory<B>,
+) where
+	B: BlockT,
+	C: BlockchainEvents<B> + HeaderBackend<B> + ProvideRuntimeApi<B> + Send + Sync + 'static,
+	C::Api: AuraApi<B, AuraId> + AuraEquivocationApi<B, AuraId>,
+{
+	let mut seen_headers = BTreeMap::<Slot, B::Header>::new();
+	let mut import_notifications = client.import_notification_stream();
+
+	while let Some(notification) = import_notifications.next().await {
+		let header = notification.header;
+		let Some(slot) = aura_slot::<B>(&header) else {
+			continue;
+		};
+
+		seen_headers.retain(|seen_slot, _| u64::from(*seen_slot) + SLOTS_TO_KEEP > u64::from(slot));
+
+		let first_header = match seen_headers.get(&slot) {
+			Some(first_header) if first_header.hash() != header.hash() => first_header.clone(),
+			Some(_) => continue,
+			None => {
+				seen_headers.insert(slot, header);
+				continue;
+			}
+		};
+
+		let offender = match client.runtime_api().authorities(*header.parent_hash()) {
+			Ok(authorities) if !authorities.is_empty() => {
+				authorities[(*slot % authorities.len() as u64) as usize].clone()
+			}
+			_ => continue,
+		};
+
+		log::warn!(
+			target: LOG_TARGET,
+			"Aura equivocation by {:?} at slot {}: {:?} and {:?}",
+			offender,
+			*slot,
+			first_header.hash(),
+			header.hash(),
+		);
+
+		let equivocation_proof = EquivocationProof {
+			offender,
+			slot,
+			first_header,
+			second_header: header,
+		};
+
+		let best_hash = client.info().best_hash;
+		let mut runtime_api = client.runtime_api();
+		runtime_api
+			.register_extension(offchain_tx_pool_factory.offchain_transaction_pool(best_hash));
+
+		match runtime_api
+			.submit_report_equivocation_unsigned_extrinsic(best_hash, equivocation_proof)
+		{
+			Ok(Some(())) => log::info!(target: LOG_TARGET, "Submitted Aura equivocation report"),
+			Ok(None) => log::warn!(target: LOG_TARGET, "Aura equivocation report was rejected"),
+			Err(e) => log::error!(
+				target: LOG_TARGET,
+				"Failed to submit Aura equivocation report: {:?}",
+				e
+			),
+		}
+	}
+}
+
+fn aura_slot<B: BlockT>(header: &B::Header) -> Option<Slot> {
+	header
+		.digest()
+		.logs()
+		.iter()
+		.find_map(<DigestItem as CompatibleDigestItem<AuraSignature>>::as_aura_pre_digest)
+}
@@ -0,0 +1,68 @@
+// Copyright © 2022 STABILITY SOLUTIONS, INC. (“STABILITY”)
+// This file is part of the Stability Global Trust Network client
+// software and accompanying documentation (the “Software”).
+
+// You can download and use the Software for free under the terms of
+// the Stability Open License Agreement as published by Stability on
+// Github at https://github.com/stabilityprotocol/stability/blob/master/LICENSE.
+
+// THE SOFTWARE IS PROVIDED “AS IS” WITHOUT WARRANTY OF ANY KIND.
+// STABILITY EXPRESSLY DISCLAIMS ALL WARRANTIES, EXPRESS OR IMPLIED,
+// INCLUDING MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE, AND
+// NON-INFRINGEMENT. IN NO EVENT SHALL OWNER BE LIABLE FOR ANY
+// INDIRECT, INCIDENTAL, SPECIAL OR CONSEQUENTIAL DAMAGES ARISING
+// OUT OF USE OF THE SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF
+// SUCH DAMAGES.
+
+// Please see the Stability Open License Agreement for more
+// information.
+
+//! Collection of the GRANDPA precommits reported to the runtime.
+//!
+//! The precommits of the latest justification seen by the local voter are
+//! reported through the finality votes inherent in the blocks authored by this
+//! node, so that the runtime can tell which validators take part in finality.
+//! The runtime notes each justification once, and no inherent is created for
+//! a justification it has already noted.
+
+use std::sync::{Arc, Mutex};
+
+use futures::StreamExt;
+use sc_consensus_grandpa::GrandpaJustificationStream;
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+use stbl_primitives_finality_votes::{FinalityVotes, InherentDataProvider};
+
+/// Precommits of the latest justification.
+pub type SharedFinalityVotes<B> =
+	Arc<Mutex<Option<FinalityVotes<<B as BlockT>::Hash, NumberFor<B>>>>>;
+
+/// Keeps the precommits of the latest justification.
+pub async fn run_finality_votes_collector<B: BlockT>(
+	justification_stream: GrandpaJustificationStream<B>,
+	finality_votes: SharedFinalityVotes<B>,
+) {
+	let mut justifications = justification_stream.subscribe(100_000);
+
+	while let Some(justification) = justifications.next().await {
+		let justification = justification.justification;
+
+		*finality_votes.lock().expect("finality votes lock poisoned") = Some(FinalityVotes {
+			round: justification.round,
+			precommits: justification.commit.precommits,
+		});
+	}
+}
+
+/// Inherent data provider reporting the latest precommits, if any. They are
+/// not consumed, as the providers are also created for slots this node
+/// doesn't author.
+pub fn inherent_data_provider<B: BlockT>(
+	finality_votes: &SharedFinalityVotes<B>,
+) -> InherentDataProvider<B::Hash, NumberFor<B>> {
+	InherentDataProvider(
+		finality_votes
+			.lock()
+			.expect("finality votes lock poisoned")
+			.clone(),
+	)
+}
@@ -33,7 +33,9 @@ mod chain_spec;
 mod cli;
 mod client;
 mod command;
+mod equivocation;
 mod eth;
+mod finality_votes;
 mod rpc;
 mod service;
 mod stability;
@@ -595,6 +595,16 @@ where
 			return Ok(task_manager);
 		}
 
+		// Report the validators sealing more than one block for the same slot.
+		task_manager.spawn_handle().spawn(
+			"aura-equivocation-reporter",
+			None,
+			crate::equivocation::run_aura_equivocation_reporter(
+				client.clone(),
+				OffchainTransactionPoolFactory::new(transaction_pool.clone()),
+			),
+		);
+
 		let proposer_factory = stbl_cli_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
 			client.clone(),
@@ -606,16 +616,32 @@ where
 			telemetry.as_ref().map(|x| x.handle()),
 		);
 
+		// Keep the precommits of the latest justification to report them in
+		// the authored blocks.
+		let finality_votes = crate::finality_votes::SharedFinalityVotes::<B>::default();
+		task_manager.spawn_handle().spawn(
+			"finality-votes-collector",
+			None,
+			crate::finality_votes::run_finality_votes_collector(
+				grandpa_link.justification_stream(),
+				finality_votes.clone(),
+			),
+		);
+
 		let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
 		let target_gas_price = eth_config.target_gas_price;
-		let create_inherent_data_providers = move |_, ()| async move {
-			let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
-			let slot = sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
-				*timestamp,
-				slot_duration,
-			);
-			let dynamic_fee = fp_dynamic_fee::InherentDataProvider(U256::from(target_gas_price));
-			Ok((slot, timestamp, dynamic_fee))
+		let create_inherent_data_providers = move |_, ()| {
+			let finality_votes = crate::finality_votes::inherent_data_provider(&finality_votes);
+			async move {
+				let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+				let slot = sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
+					*timestamp,
+					slot_duration,
+				);
+				let dynamic_fee =
+					fp_dynamic_fee::InherentDataProvider(U256::from(target_gas_price));
+				Ok((slot, timestamp, dynamic_fee, finality_votes))
+			}
 		};
 
 		let aura = sc_consensus_aura::start_aura::<
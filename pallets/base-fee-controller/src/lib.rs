@@ -0,0 +1,183 @@
+// Copyright © 2022 STABILITY SOLUTIONS, INC. (“STABILITY”)
+// This file is part of the Stability Global Trust Network client
+// software and accompanying documentation (the “Software”).
+
+// You can download and use the Software for free under the terms of
+// the Stability Open License Agreement as published by Stability on
+// Github at https://github.com/stabilityprotocol/stability/blob/master/LICENSE.
+
+// THE SOFTWARE IS PROVIDED “AS IS” WITHOUT WARRANTY OF ANY KIND.
+// STABILITY EXPRESSLY DISCLAIMS ALL WARRANTIES, EXPRESS OR IMPLIED,
+// INCLUDING MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE, AND
+// NON-INFRINGEMENT. IN NO EVENT SHALL OWNER BE LIABLE FOR ANY
+// INDIRECT, INCIDENTAL, SPECIAL OR CONSEQUENTIAL DAMAGES ARISING
+// OUT OF USE OF THE SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF
+// SUCH DAMAGES.
+
+// Please see the Stability Open License Agreement for more
+// information.
+
+//! # Base Fee Controller Pallet
+//!
+//! Lets governance update the base fee per gas and the elasticity of the base
+//! fee pallet without a runtime upgrade. Every update must stay within the
+//! bounds set in the configuration and cannot move a value further than the
+//! maximum change allowed per call.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub use pallet::*;
+
+use frame_support::traits::EnsureOrigin;
+use pallet_base_fee::{BaseFeePerGas, Elasticity};
+use sp_core::U256;
+use sp_runtime::Permill;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// The pallet has to be declared after the base fee pallet in the runtime,
+	/// so that the base fee is kept within its bounds after the base fee pallet
+	/// adjusts it at the end of the block.
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_base_fee::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Origin allowed to update the base fee per gas and the elasticity.
+		type ControlOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Lowest base fee per gas that can be set.
+		type MinBaseFeePerGas: Get<U256>;
+		/// Highest base fee per gas that can be set.
+		type MaxBaseFeePerGas: Get<U256>;
+		/// Largest change of the base fee per gas in a single update, relative to
+		/// the current base fee per gas. A base fee per gas out of its bounds is
+		/// measured from the closest bound instead.
+		type MaxBaseFeePerGasChange: Get<Permill>;
+		/// Highest elasticity that can be set.
+		type MaxElasticity: Get<Permill>;
+		/// Largest change of the elasticity in a single update.
+		type MaxElasticityChange: Get<Permill>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The base fee per gas was updated by governance.
+		BaseFeePerGasSet { old: U256, new: U256 },
+		/// The elasticity was updated by governance.
+		ElasticitySet { old: Permill, new: Permill },
+		/// The base fee per gas left its bounds after an elastic adjustment and
+		/// was brought back to the closest bound.
+		BaseFeePerGasClamped { old: U256, new: U256 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The base fee per gas is out of the allowed bounds.
+		BaseFeePerGasOutOfBounds,
+		/// The base fee per gas changes more than allowed in a single update.
+		BaseFeePerGasChangeTooLarge,
+		/// The elasticity is above the allowed maximum.
+		ElasticityOutOfBounds,
+		/// The elasticity changes more than allowed in a single update.
+		ElasticityChangeTooLarge,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_: BlockNumberFor<T>) -> Weight {
+			if Elasticity::<T>::get() == Permill::zero() {
+				return T::DbWeight::get().reads(1);
+			}
+
+			T::DbWeight::get().reads_writes(2, 1)
+		}
+
+		fn on_finalize(_: BlockNumberFor<T>) {
+			// Without elasticity the base fee only changes through this pallet.
+			if Elasticity::<T>::get() == Permill::zero() {
+				return;
+			}
+
+			let old = BaseFeePerGas::<T>::get();
+			let new = old.clamp(T::MinBaseFeePerGas::get(), T::MaxBaseFeePerGas::get());
+			if new != old {
+				BaseFeePerGas::<T>::put(new);
+				Self::deposit_event(Event::BaseFeePerGasClamped { old, new });
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn set_base_fee_per_gas(origin: OriginFor<T>, fee: U256) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				fee >= T::MinBaseFeePerGas::get() && fee <= T::MaxBaseFeePerGas::get(),
+				Error::<T>::BaseFeePerGasOutOfBounds
+			);
+
+			let old = BaseFeePerGas::<T>::get();
+			// A base fee per gas left out of its bounds, e.g. at zero, can be moved
+			// back from the closest bound.
+			let current = old.clamp(T::MinBaseFeePerGas::get(), T::MaxBaseFeePerGas::get());
+			let change = if fee > current {
+				fee - current
+			} else {
+				current - fee
+			};
+			let max_change = current
+				.saturating_mul(U256::from(T::MaxBaseFeePerGasChange::get().deconstruct()))
+				/ U256::from(Permill::one().deconstruct());
+			ensure!(
+				change <= max_change,
+				Error::<T>::BaseFeePerGasChangeTooLarge
+			);
+
+			BaseFeePerGas::<T>::put(fee);
+			Self::deposit_event(Event::BaseFeePerGasSet { old, new: fee });
+			Ok(())
+		}
+
+		// Also covers the base fee per gas kept within its bounds at the end of
+		// the block, which `on_initialize` doesn't account for without elasticity.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2))]
+		pub fn set_elasticity(origin: OriginFor<T>, elasticity: Permill) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				elasticity <= T::MaxElasticity::get(),
+				Error::<T>::ElasticityOutOfBounds
+			);
+
+			let old = Elasticity::<T>::get();
+			let change = Permill::from_parts(elasticity.deconstruct().abs_diff(old.deconstruct()));
+			ensure!(
+				change <= T::MaxElasticityChange::get(),
+				Error::<T>::ElasticityChangeTooLarge
+			);
+
+			Elasticity::<T>::put(elasticity);
+			Self::deposit_event(Event::ElasticitySet {
+				old,
+				new: elasticity,
+			});
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,126 @@
+// Copyright © 2022 STABILITY SOLUTIONS, INC. (“STABILITY”)
+// This file is part of the Stability Global Trust Network client
+// software and accompanying documentation (the “Software”).
+
+// You can download and use the Software for free under the terms of
+// the Stability Open License Agreement as published by Stability on
+// Github at https://github.com/stabilityprotocol/stability/blob/master/LICENSE.
+
+// THE SOFTWARE IS PROVIDED “AS IS” WITHOUT WARRANTY OF ANY KIND.
+// STABILITY EXPRESSLY DISCLAIMS ALL WARRANTIES, EXPRESS OR IMPLIED,
+// INCLUDING MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE, AND
+// NON-INFRINGEMENT. IN NO EVENT SHALL OWNER BE LIABLE FOR ANY
+// INDIRECT, INCIDENTAL, SPECIAL OR CONSEQUENTIAL DAMAGES ARISING
+// OUT OF USE OF THE SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF
+// SUCH DAMAGES.
+
+// Please see the Stability Open License Agreement for more
+// information.
+
+#![cfg(test)]
+
+use super::*;
+use crate as base_fee_controller;
+
+use frame_support::traits::{ConstU32, ConstU64};
+use frame_support::{ord_parameter_types, parameter_types};
+use sp_core::H256;
+use sp_io;
+use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = frame_support::weights::constants::RocksDbWeight;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+	type RuntimeTask = ();
+	type Nonce = u64;
+	type Block = Block;
+	type SingleBlockMigrations = ();
+	type MultiBlockMigrator = ();
+	type PreInherents = ();
+	type PostInherents = ();
+	type PostTransactions = ();
+}
+
+parameter_types! {
+	pub DefaultBaseFeePerGas: U256 = U256::from(1_000_000_000u128);
+	pub DefaultElasticity: Permill = Permill::zero();
+	pub MinBaseFeePerGas: U256 = U256::from(100_000_000u128);
+	pub MaxBaseFeePerGas: U256 = U256::from(100_000_000_000u128);
+	pub MaxBaseFeePerGasChange: Permill = Permill::from_percent(50);
+	pub MaxElasticity: Permill = Permill::from_percent(50);
+	pub MaxElasticityChange: Permill = Permill::from_percent(25);
+}
+
+pub struct BaseFeeThreshold;
+impl pallet_base_fee::BaseFeeThreshold for BaseFeeThreshold {
+	fn lower() -> Permill {
+		Permill::zero()
+	}
+	fn ideal() -> Permill {
+		Permill::from_parts(500_000)
+	}
+	fn upper() -> Permill {
+		Permill::from_parts(1_000_000)
+	}
+}
+
+impl pallet_base_fee::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Threshold = BaseFeeThreshold;
+	type DefaultBaseFeePerGas = DefaultBaseFeePerGas;
+	type DefaultElasticity = DefaultElasticity;
+}
+
+ord_parameter_types! {
+	pub const AllowedAccountId: u64 = 1;
+	pub const NotAllowedAccountId: u64 = 2;
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ControlOrigin = frame_system::EnsureSignedBy<AllowedAccountId, u64>;
+	type MinBaseFeePerGas = MinBaseFeePerGas;
+	type MaxBaseFeePerGas = MaxBaseFeePerGas;
+	type MaxBaseFeePerGasChange = MaxBaseFeePerGasChange;
+	type MaxElasticity = MaxElasticity;
+	type MaxElasticityChange = MaxElasticityChange;
+}
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		BaseFee: pallet_base_fee,
+		BaseFeeController: base_fee_controller,
+	}
+);
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<Test>::default()
+		.build_storage()
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,206 @@
+// Copyright © 2022 STABILITY SOLUTIONS, INC. (“STABILITY”)
+// This file is part of the Stability Global Trust Network client
+// software and accompanying documentation (the “Software”).
+
+// You can download and use the Software for free under the terms of
+// the Stability Open License Agreement as published by Stability on
+// Github at https://github.com/stabilityprotocol/stability/blob/master/LICENSE.
+
+// THE SOFTWARE IS PROVIDED “AS IS” WITHOUT WARRANTY OF ANY KIND.
+// STABILITY EXPRESSLY DISCLAIMS ALL WARRANTIES, EXPRESS OR IMPLIED,
+// INCLUDING MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE, AND
+// NON-INFRINGEMENT. IN NO EVENT SHALL OWNER BE LIABLE FOR ANY
+// INDIRECT, INCIDENTAL, SPECIAL OR CONSEQUENTIAL DAMAGES ARISING
+// OUT OF USE OF THE SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF
+// SUCH DAMAGES.
+
+// Please see the Stability Open License Agreement for more
+// information.
+
+use super::*;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Get, Hooks},
+};
+use mock::{
+	new_test_ext, AllowedAccountId, BaseFeeController, NotAllowedAccountId, RuntimeEvent,
+	RuntimeOrigin, System, Test,
+};
+use sp_runtime::DispatchError;
+
+fn allowed() -> RuntimeOrigin {
+	RuntimeOrigin::signed(AllowedAccountId::get())
+}
+
+#[test]
+fn set_base_fee_per_gas_within_bounds() {
+	new_test_ext().execute_with(|| {
+		let fee = U256::from(1_500_000_000u128);
+		assert_ok!(BaseFeeController::set_base_fee_per_gas(allowed(), fee));
+		assert_eq!(BaseFeePerGas::<Test>::get(), fee);
+		System::assert_last_event(RuntimeEvent::BaseFeeController(Event::BaseFeePerGasSet {
+			old: U256::from(1_000_000_000u128),
+			new: fee,
+		}));
+
+		let fee = U256::from(750_000_000u128);
+		assert_ok!(BaseFeeController::set_base_fee_per_gas(allowed(), fee));
+		assert_eq!(BaseFeePerGas::<Test>::get(), fee);
+	});
+}
+
+#[test]
+fn set_base_fee_per_gas_from_not_allowed_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			BaseFeeController::set_base_fee_per_gas(
+				RuntimeOrigin::signed(NotAllowedAccountId::get()),
+				U256::from(1_100_000_000u128)
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_base_fee_per_gas_out_of_bounds() {
+	new_test_ext().execute_with(|| {
+		BaseFeePerGas::<Test>::put(U256::from(150_000_000u128));
+		assert_noop!(
+			BaseFeeController::set_base_fee_per_gas(allowed(), U256::from(99_999_999u128)),
+			Error::<Test>::BaseFeePerGasOutOfBounds
+		);
+
+		BaseFeePerGas::<Test>::put(U256::from(90_000_000_000u128));
+		assert_noop!(
+			BaseFeeController::set_base_fee_per_gas(allowed(), U256::from(100_000_000_001u128)),
+			Error::<Test>::BaseFeePerGasOutOfBounds
+		);
+	});
+}
+
+#[test]
+fn set_base_fee_per_gas_change_too_large() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			BaseFeeController::set_base_fee_per_gas(allowed(), U256::from(1_500_000_001u128)),
+			Error::<Test>::BaseFeePerGasChangeTooLarge
+		);
+		assert_noop!(
+			BaseFeeController::set_base_fee_per_gas(allowed(), U256::from(499_999_999u128)),
+			Error::<Test>::BaseFeePerGasChangeTooLarge
+		);
+	});
+}
+
+#[test]
+fn set_elasticity_within_bounds() {
+	new_test_ext().execute_with(|| {
+		let elasticity = Permill::from_percent(25);
+		assert_ok!(BaseFeeController::set_elasticity(allowed(), elasticity));
+		assert_eq!(Elasticity::<Test>::get(), elasticity);
+		System::assert_last_event(RuntimeEvent::BaseFeeController(Event::ElasticitySet {
+			old: Permill::zero(),
+			new: elasticity,
+		}));
+
+		assert_ok!(BaseFeeController::set_elasticity(
+			allowed(),
+			Permill::from_percent(50)
+		));
+		assert_ok!(BaseFeeController::set_elasticity(
+			allowed(),
+			Permill::from_percent(25)
+		));
+		assert_eq!(Elasticity::<Test>::get(), Permill::from_percent(25));
+	});
+}
+
+#[test]
+fn fail_to_set_elasticity() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			BaseFeeController::set_elasticity(
+				RuntimeOrigin::signed(NotAllowedAccountId::get()),
+				Permill::from_percent(10)
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			BaseFeeController::set_elasticity(allowed(), Permill::from_percent(26)),
+			Error::<Test>::ElasticityChangeTooLarge
+		);
+
+		Elasticity::<Test>::put(Permill::from_percent(40));
+		assert_noop!(
+			BaseFeeController::set_elasticity(allowed(), Permill::from_percent(51)),
+			Error::<Test>::ElasticityOutOfBounds
+		);
+	});
+}
+
+#[test]
+fn elastic_base_fee_is_kept_within_bounds() {
+	new_test_ext().execute_with(|| {
+		let above = U256::from(200_000_000_000u128);
+		BaseFeePerGas::<Test>::put(above);
+		BaseFeeController::on_finalize(1);
+		assert_eq!(BaseFeePerGas::<Test>::get(), above);
+
+		Elasticity::<Test>::put(Permill::from_percent(10));
+		BaseFeeController::on_finalize(1);
+		assert_eq!(
+			BaseFeePerGas::<Test>::get(),
+			U256::from(100_000_000_000u128)
+		);
+		System::assert_last_event(RuntimeEvent::BaseFeeController(
+			Event::BaseFeePerGasClamped {
+				old: above,
+				new: U256::from(100_000_000_000u128),
+			},
+		));
+
+		BaseFeePerGas::<Test>::put(U256::from(1u128));
+		BaseFeeController::on_finalize(1);
+		assert_eq!(BaseFeePerGas::<Test>::get(), U256::from(100_000_000u128));
+	});
+}
+
+#[test]
+fn set_base_fee_per_gas_out_of_bounds_moves_from_closest_bound() {
+	new_test_ext().execute_with(|| {
+		BaseFeePerGas::<Test>::put(U256::zero());
+		assert_ok!(BaseFeeController::set_base_fee_per_gas(
+			allowed(),
+			U256::from(150_000_000u128)
+		));
+		assert_eq!(BaseFeePerGas::<Test>::get(), U256::from(150_000_000u128));
+
+		BaseFeePerGas::<Test>::put(U256::zero());
+		assert_noop!(
+			BaseFeeController::set_base_fee_per_gas(allowed(), U256::from(150_000_001u128)),
+			Error::<Test>::BaseFeePerGasChangeTooLarge
+		);
+
+		BaseFeePerGas::<Test>::put(U256::from(200_000_000_000u128));
+		assert_ok!(BaseFeeController::set_base_fee_per_gas(
+			allowed(),
+			U256::from(50_000_000_000u128)
+		));
+		assert_eq!(BaseFeePerGas::<Test>::get(), U256::from(50_000_000_000u128));
+	});
+}
+
+#[test]
+fn on_initialize_charges_the_clamp_with_elasticity_only() {
+	new_test_ext().execute_with(|| {
+		let db_weight = <Test as frame_system::Config>::DbWeight::get();
+		assert_eq!(BaseFeeController::on_initialize(1), db_weight.reads(1));
+
+		Elasticity::<Test>::put(Permill::from_percent(10));
+		assert_eq!(
+			BaseFeeController::on_initialize(1),
+			db_weight.reads_writes(2, 1)
+		);
+	});
+}
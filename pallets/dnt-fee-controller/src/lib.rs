@@ -21,6 +21,9 @@
 
 pub use pallet::*;
 
+/// Fee shares are expressed in basis points of the fee.
+pub const BASIS_POINTS: u32 = 10_000;
+
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -31,12 +34,13 @@ pub mod pallet {
 
 	use super::*;
 
-	use frame_support::pallet_prelude::*;
+	use frame_support::{pallet_prelude::*, traits::Contains};
 	use pallet_erc20_manager::ERC20Manager;
 	use pallet_user_fee_selector::UserFeeTokenController;
 	use pallet_validator_fee_selector::ValidatorFeeTokenController;
 	use runner::OnChargeDecentralizedNativeTokenFee;
 	use sp_core::{H160, U256};
+	use sp_std::{vec, vec::Vec};
 
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
@@ -52,17 +56,43 @@ pub mod pallet {
 		FeeVaultOverflow,
 		InvalidConversionRate,
 		InvalidPercentage,
+		InvalidShare,
 		ArithmeticError,
+		ConversionRateUnavailable,
+		ERC20TransferFailed,
+		/// The shares of the fee add up to more than the fee.
+		FeeSplitUnderflow,
 	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn fee_vault_precompile_address)]
 	pub type FeeVaultPrecompileAddressStorage<T: Config> = StorageValue<_, H160, OptionQuery>;
 
+	/// Validator share of the fees in whole percent. Superseded by `ValidatorShareStorage`,
+	/// kept rounded down for the existing readers.
 	#[pallet::storage]
 	#[pallet::getter(fn validator_percentage)]
 	pub type ValidatorPercentageStorage<T: Config> = StorageValue<_, U256, OptionQuery>;
 
+	/// Validator share of the fees in basis points.
+	#[pallet::storage]
+	#[pallet::getter(fn validator_share)]
+	pub type ValidatorShareStorage<T: Config> = StorageValue<_, U256, OptionQuery>;
+
+	/// Treasury share of the fees in basis points.
+	#[pallet::storage]
+	#[pallet::getter(fn treasury_share)]
+	pub type TreasuryShareStorage<T: Config> = StorageValue<_, U256, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn treasury)]
+	pub type TreasuryStorage<T: Config> = StorageValue<_, H160, OptionQuery>;
+
+	/// Dapp share of the fees in basis points, overriding the default split for a dapp.
+	#[pallet::storage]
+	#[pallet::getter(fn dapp_share_override)]
+	pub type DappShareStorage<T: Config> = StorageMap<_, Blake2_128Concat, H160, U256, OptionQuery>;
+
 	#[pallet::config]
 	pub trait Config:
 		frame_system::Config + pallet_evm::Config + pallet_fee_rewards_vault::Config
@@ -76,6 +106,8 @@ pub mod pallet {
 	pub struct GenesisConfig<T> {
 		pub fee_vault_precompile_address: H160,
 		pub validator_percentage: U256,
+		pub treasury: Option<H160>,
+		pub treasury_share: U256,
 		#[serde(skip)]
 		pub _config: PhantomData<T>,
 	}
@@ -88,6 +120,8 @@ pub mod pallet {
 				)
 				.unwrap(),
 				validator_percentage: 50.into(),
+				treasury: None,
+				treasury_share: 0.into(),
 				_config: Default::default(),
 			}
 		}
@@ -98,6 +132,11 @@ pub mod pallet {
 		fn build(&self) {
 			FeeVaultPrecompileAddressStorage::<T>::put(self.fee_vault_precompile_address);
 			ValidatorPercentageStorage::<T>::put(self.validator_percentage);
+			ValidatorShareStorage::<T>::put(self.validator_percentage.saturating_mul(100.into()));
+			if let Some(treasury) = self.treasury {
+				TreasuryStorage::<T>::put(treasury);
+			}
+			TreasuryShareStorage::<T>::put(self.treasury_share);
 		}
 	}
 
@@ -112,8 +151,13 @@ pub mod pallet {
 			sender: H160,
 			validator: H160,
 			token: H160,
-		) -> (U256, U256) {
+		) -> Result<(U256, U256), Self::Error> {
 			T::ValidatorTokenController::conversion_rate(sender, validator, token)
+				.map_err(|_| Error::<T>::ConversionRateUnavailable)
+		}
+
+		fn get_transaction_conversion_rate_gas(sender: H160, validator: H160, token: H160) -> u64 {
+			T::ValidatorTokenController::conversion_rate_gas(sender, validator, token)
 		}
 
 		fn get_fee_vault() -> H160 {
@@ -143,10 +187,8 @@ pub mod pallet {
 				_ => return Err(Error::ArithmeticError),
 			};
 
-			T::ERC20Manager::withdraw_amount(token, from, mapped_amount)
-				.map_err(|_| Error::<T>::ERC20WithdrawFailed)?;
-			T::ERC20Manager::deposit_amount(token, fee_vault, mapped_amount)
-				.map_err(|_| Error::<T>::ERC20DepositFailed)?;
+			T::ERC20Manager::transfer(token, from, fee_vault, mapped_amount)
+				.map_err(|_| Error::<T>::ERC20TransferFailed)?;
 
 			Ok(())
 		}
@@ -179,10 +221,8 @@ pub mod pallet {
 			};
 
 			let fee_vault = FeeVaultPrecompileAddressStorage::<T>::get().unwrap();
-			T::ERC20Manager::withdraw_amount(token, fee_vault, mapped_amount)
-				.map_err(|_| Error::<T>::ERC20WithdrawFailed)?;
-			T::ERC20Manager::deposit_amount(token, from, mapped_amount)
-				.map_err(|_| Error::<T>::ERC20DepositFailed)?;
+			T::ERC20Manager::refund(token, fee_vault, from, mapped_amount)
+				.map_err(|_| Error::<T>::ERC20TransferFailed)?;
 
 			Ok(())
 		}
@@ -193,9 +233,10 @@ pub mod pallet {
 			actual_amount: U256,
 			validator: H160,
 			to: Option<H160>,
-		) -> Result<(U256, U256), Self::Error> {
+			consumers: &[(H160, u64)],
+		) -> Result<(U256, U256, U256), Self::Error> {
 			if actual_amount.is_zero() {
-				return Ok((0.into(), 0.into()));
+				return Ok((0.into(), 0.into(), 0.into()));
 			}
 
 			if conversion_rate.1 == U256::zero() {
@@ -211,38 +252,50 @@ pub mod pallet {
 				_ => return Err(Error::ArithmeticError),
 			};
 
-			let validator_share = match to {
-				Some(_) => ValidatorPercentageStorage::<T>::get().unwrap(),
-				_ => 100.into(),
-			};
+			let treasury = TreasuryStorage::<T>::get();
 
-			let validator_fee = fee_in_user_token
-				.saturating_mul(validator_share.into())
-				.div_mod(U256::from(100))
-				.0;
+			let treasury_fee = match treasury {
+				Some(_) => Self::share_of(fee_in_user_token, Self::get_treasury_share()),
+				_ => U256::zero(),
+			};
 
-			let dapp_fee = match fee_in_user_token.checked_sub(validator_fee) {
-				Some(v) => v,
-				_ => return Err(Error::<T>::FeeVaultOverflow),
+			// Without a dapp, e.g. for contract creations, its share goes to the validator.
+			let dapp_rewards = match to {
+				Some(to) => Self::dapp_rewards(fee_in_user_token, treasury_fee, to, consumers)?,
+				None => Vec::new(),
 			};
+			let dapp_fee = dapp_rewards
+				.iter()
+				.fold(U256::zero(), |total, (_, reward)| {
+					total.saturating_add(*reward)
+				});
+			let validator_fee = fee_in_user_token
+				.checked_sub(treasury_fee)
+				.and_then(|v| v.checked_sub(dapp_fee))
+				.ok_or(Error::<T>::FeeSplitUnderflow)?;
 
-			pallet_fee_rewards_vault::Pallet::<T>::add_claimable_reward(
+			pallet_fee_rewards_vault::Pallet::<T>::add_validator_reward(
 				validator,
 				token,
 				validator_fee,
 			)
 			.map_err(|_| Error::<T>::FeeVaultOverflow)?;
 
-			if to.is_some() {
+			for (dapp, reward) in dapp_rewards {
+				pallet_fee_rewards_vault::Pallet::<T>::add_claimable_reward(dapp, token, reward)
+					.map_err(|_| Error::<T>::FeeVaultOverflow)?;
+			}
+
+			if let Some(treasury) = treasury {
 				pallet_fee_rewards_vault::Pallet::<T>::add_claimable_reward(
-					to.unwrap(),
+					treasury,
 					token,
-					dapp_fee,
+					treasury_fee,
 				)
 				.map_err(|_| Error::<T>::FeeVaultOverflow)?;
 			}
 
-			Ok((validator_fee, dapp_fee))
+			Ok((validator_fee, dapp_fee, treasury_fee))
 		}
 	}
 
@@ -251,12 +304,142 @@ pub mod pallet {
 			if percentage > 100.into() {
 				return Err(Error::<T>::InvalidPercentage);
 			}
-			ValidatorPercentageStorage::<T>::put(percentage);
-			Ok(())
+			Self::set_validator_share(percentage.saturating_mul(100.into()))
+				.map_err(|_| Error::<T>::InvalidPercentage)
 		}
 
 		pub fn get_validator_percentage() -> U256 {
 			ValidatorPercentageStorage::<T>::get().unwrap()
 		}
+
+		pub fn set_validator_share(share: U256) -> Result<(), Error<T>> {
+			if share.saturating_add(Self::get_treasury_share()) > BASIS_POINTS.into() {
+				return Err(Error::<T>::InvalidShare);
+			}
+			ValidatorShareStorage::<T>::put(share);
+			ValidatorPercentageStorage::<T>::put(share / 100);
+			Ok(())
+		}
+
+		/// Validator share in basis points. Falls back to the percentage set before shares
+		/// were kept in basis points.
+		pub fn get_validator_share() -> U256 {
+			ValidatorShareStorage::<T>::get()
+				.unwrap_or_else(|| Self::get_validator_percentage().saturating_mul(100.into()))
+		}
+
+		pub fn set_treasury_share(share: U256) -> Result<(), Error<T>> {
+			if share.saturating_add(Self::get_validator_share()) > BASIS_POINTS.into() {
+				return Err(Error::<T>::InvalidShare);
+			}
+			TreasuryShareStorage::<T>::put(share);
+			Ok(())
+		}
+
+		pub fn get_treasury_share() -> U256 {
+			TreasuryShareStorage::<T>::get()
+		}
+
+		pub fn set_treasury(treasury: Option<H160>) {
+			TreasuryStorage::<T>::set(treasury);
+		}
+
+		pub fn get_treasury() -> Option<H160> {
+			TreasuryStorage::<T>::get()
+		}
+
+		/// Overrides the dapp share, in basis points, for `dapp`. `None` restores the default
+		/// split.
+		pub fn set_dapp_share(dapp: H160, share: Option<U256>) -> Result<(), Error<T>> {
+			if let Some(share) = share {
+				if share.saturating_add(Self::get_treasury_share()) > BASIS_POINTS.into() {
+					return Err(Error::<T>::InvalidShare);
+				}
+			}
+			DappShareStorage::<T>::set(dapp, share);
+			Ok(())
+		}
+
+		/// Share of the fees, in basis points, that `dapp` receives: its override if any,
+		/// otherwise what the validator and the treasury leave.
+		pub fn get_dapp_share(dapp: H160) -> U256 {
+			let treasury_share = match TreasuryStorage::<T>::get() {
+				Some(_) => Self::get_treasury_share(),
+				_ => U256::zero(),
+			};
+			let available = U256::from(BASIS_POINTS).saturating_sub(treasury_share);
+
+			match DappShareStorage::<T>::get(dapp) {
+				Some(share) => share.min(available),
+				_ => available.saturating_sub(Self::get_validator_share()),
+			}
+		}
+
+		/// Rewards of the dapps out of `fee`, split across `consumers` by gas. A consumer with an
+		/// overridden share gets that share of its part of the fee first, out of its part of the
+		/// dapp and validator shares, and the validator the remainder. Consumers that could not
+		/// claim their rewards from the vault get nothing, leaving their part to the validator.
+		fn dapp_rewards(
+			fee: U256,
+			treasury_fee: U256,
+			to: H160,
+			consumers: &[(H160, u64)],
+		) -> Result<Vec<(H160, U256)>, Error<T>> {
+			let validator_fee = Self::share_of(fee, Self::get_validator_share());
+			let dapp_fee = fee
+				.checked_sub(validator_fee)
+				.and_then(|v| v.checked_sub(treasury_fee))
+				.ok_or(Error::<T>::FeeSplitUnderflow)?;
+
+			let mut rewards = Self::split_by_gas(dapp_fee, to, consumers);
+			let validator_parts = Self::split_by_gas(validator_fee, to, consumers);
+			let fee_parts = Self::split_by_gas(fee, to, consumers);
+
+			for (((dapp, reward), (_, validator_part)), (_, fee_part)) in
+				rewards.iter_mut().zip(validator_parts).zip(fee_parts)
+			{
+				if let Some(dapp_share) = DappShareStorage::<T>::get(*dapp) {
+					*reward = Self::share_of(fee_part, dapp_share)
+						.min(reward.saturating_add(validator_part));
+				}
+			}
+
+			rewards.retain(|(dapp, _)| {
+				<T as pallet_fee_rewards_vault::Config>::PayoutFilter::contains(dapp)
+			});
+
+			Ok(rewards)
+		}
+
+		/// Splits `amount` across `consumers` in proportion to the gas each one consumed. The
+		/// rounding remainder goes to the first consumer, and everything to `to` if none
+		/// consumed gas.
+		fn split_by_gas(amount: U256, to: H160, consumers: &[(H160, u64)]) -> Vec<(H160, U256)> {
+			let total_gas = consumers.iter().fold(U256::zero(), |total, (_, gas)| {
+				total.saturating_add((*gas).into())
+			});
+
+			if total_gas.is_zero() {
+				return vec![(to, amount)];
+			}
+
+			let mut split: Vec<(H160, U256)> = consumers
+				.iter()
+				.map(|(dapp, gas)| (*dapp, amount.saturating_mul((*gas).into()) / total_gas))
+				.collect();
+
+			let distributed = split.iter().fold(U256::zero(), |total, (_, reward)| {
+				total.saturating_add(*reward)
+			});
+			split[0].1 = split[0]
+				.1
+				.saturating_add(amount.saturating_sub(distributed));
+
+			split
+		}
+
+		fn share_of(amount: U256, share: U256) -> U256 {
+			amount.saturating_mul(share).div_mod(BASIS_POINTS.into()).0
+		}
 	}
 }
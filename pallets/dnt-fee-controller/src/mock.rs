@@ -49,6 +49,8 @@ parameter_types! {
 	pub MeaninglessTokenAddress : H160 = H160::from_str("0x22D598E0a9a1b474CdC7c6fBeA0B4F83E12046a9").unwrap();
 	pub MeaninglessAddress : H160 = H160::from_low_u64_be(1);
 	pub MeaninglessAddress2 : H160 = H160::from_low_u64_be(2);
+	pub MeaninglessTreasury : H160 = H160::from_low_u64_be(3);
+	pub MeaninglessNotWhitelisted : H160 = H160::from_low_u64_be(4);
 	pub MeaninglessConversionRate : (U256, U256) = (1.into(), 1.into());
 }
 
@@ -148,7 +150,14 @@ impl pallet_evm::Config for Test {
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
 }
 
-impl pallet_fee_rewards_vault::Config for Test {}
+impl pallet_fee_rewards_vault::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ERC20Manager = MockERC20Manager;
+	type VaultAddress = FeeVaultAddress;
+	type PayoutFilter = MockPayoutFilter;
+	type PayoutPeriod = frame_support::traits::ConstU64<100>;
+	type MaxPayoutsPerBlock = frame_support::traits::ConstU32<10>;
+}
 
 frame_support::construct_runtime!(
 	pub enum Test {
@@ -195,8 +204,16 @@ impl pallet_validator_fee_selector::ValidatorFeeTokenController for MockValidato
 		Ok(())
 	}
 
-	fn conversion_rate(_s: H160, _validator: H160, _token: H160) -> (U256, U256) {
-		MeaninglessConversionRate::get()
+	fn conversion_rate(
+		_s: H160,
+		_validator: H160,
+		_token: H160,
+	) -> Result<(U256, U256), Self::Error> {
+		Ok(MeaninglessConversionRate::get())
+	}
+
+	fn conversion_rate_gas(_sender: H160, _validator: H160, _token: H160) -> u64 {
+		Default::default()
 	}
 
 	fn conversion_rate_controller(_validator: H160) -> H160 {
@@ -213,6 +230,38 @@ impl pallet_validator_fee_selector::ValidatorFeeTokenController for MockValidato
 	fn update_default_controller(_controller: H160) -> Result<(), Self::Error> {
 		Ok(())
 	}
+
+	fn conversion_rate_gas_limit(_validator: H160) -> u64 {
+		Default::default()
+	}
+
+	fn update_conversion_rate_gas_limit(
+		_validator: H160,
+		_gas_limit: u64,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+
+	fn conversion_rate_fallback(
+		_validator: H160,
+	) -> Option<pallet_validator_fee_selector::FallbackConversionRate> {
+		None
+	}
+
+	fn update_conversion_rate_fallback(
+		_validator: H160,
+		_fallback: Option<pallet_validator_fee_selector::FallbackConversionRate>,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+}
+
+/// Every holder can claim its rewards but `MeaninglessNotWhitelisted`.
+pub struct MockPayoutFilter;
+impl Contains<H160> for MockPayoutFilter {
+	fn contains(holder: &H160) -> bool {
+		*holder != MeaninglessNotWhitelisted::get()
+	}
 }
 
 pub struct MockPrefix;
@@ -291,6 +340,8 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	crate::GenesisConfig::<Test> {
 		fee_vault_precompile_address: FeeVaultAddress::get(),
 		validator_percentage: 50.into(),
+		treasury: None,
+		treasury_share: 0.into(),
 		_config: Default::default(),
 	}
 	.assimilate_storage(&mut t)
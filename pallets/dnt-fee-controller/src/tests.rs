@@ -25,7 +25,10 @@ use crate::mock::{
 };
 
 use super::*;
-use mock::{new_test_ext, MeaninglessAddress, MeaninglessAddress2, Test};
+use mock::{
+	new_test_ext, MeaninglessAddress, MeaninglessAddress2, MeaninglessNotWhitelisted,
+	MeaninglessTreasury, Test,
+};
 use runner::OnChargeDecentralizedNativeTokenFee;
 
 #[test]
@@ -116,7 +119,7 @@ fn transaction_fee_cr_returns_validator_cr() {
 			MeaninglessAddress::get(),
 			MeaninglessTokenAddress::get()
 		),
-		MeaninglessConversionRate::get()
+		Ok(MeaninglessConversionRate::get())
 	);
 }
 
@@ -132,6 +135,7 @@ fn pay_fees_calls_vault_pallet() {
 			meaningless_amount,
 			MeaninglessAddress::get(),
 			Some(MeaninglessAddress2::get()),
+			&[],
 		);
 
 		assert!(result.is_ok());
@@ -166,11 +170,12 @@ fn pay_fees_calls_to_none_address() {
 			meaningless_amount,
 			MeaninglessAddress::get(),
 			None,
+			&[],
 		);
 
 		assert!(result.is_ok());
 
-		let (returned_validator_amount, returned_dapp_amount) = result.unwrap();
+		let (returned_validator_amount, returned_dapp_amount, _) = result.unwrap();
 
 		assert_eq!(returned_validator_amount, validator_amount);
 
@@ -210,11 +215,12 @@ fn pay_fees_calls_with_updated_percentages() {
 			meaningless_amount,
 			MeaninglessAddress::get(),
 			Some(MeaninglessAddress2::get()),
+			&[],
 		);
 
 		assert!(result.is_ok());
 
-		let (returned_validator_amount, returned_dapp_amount) = result.unwrap();
+		let (returned_validator_amount, returned_dapp_amount, _) = result.unwrap();
 
 		assert_eq!(returned_validator_amount, validator_amount);
 		assert_eq!(returned_dapp_amount, meaningless_amount - validator_amount);
@@ -281,3 +287,307 @@ fn validator_percentage_updates() {
 		assert_eq!(DNTFeeController::validator_percentage().unwrap(), 10.into());
 	});
 }
+
+#[test]
+fn pay_fees_splits_in_basis_points_with_treasury() {
+	new_test_ext().execute_with(|| {
+		DNTFeeController::set_treasury(Some(MeaninglessTreasury::get()));
+		assert!(DNTFeeController::set_validator_share(4_550.into()).is_ok());
+		assert!(DNTFeeController::set_treasury_share(1_025.into()).is_ok());
+
+		let result = <Pallet<Test> as OnChargeDecentralizedNativeTokenFee>::pay_fees(
+			MeaninglessTokenAddress::get(),
+			(1.into(), 1.into()),
+			10_000.into(),
+			MeaninglessAddress::get(),
+			Some(MeaninglessAddress2::get()),
+			&[],
+		);
+
+		assert_eq!(result.unwrap(), (4_550.into(), 4_425.into(), 1_025.into()));
+
+		assert_eq!(
+			pallet_fee_rewards_vault::Pallet::<Test>::claimable_reward(
+				MeaninglessTreasury::get(),
+				MeaninglessTokenAddress::get(),
+			),
+			1_025.into(),
+		);
+	})
+}
+
+#[test]
+fn pay_fees_without_dapp_keeps_treasury_share() {
+	new_test_ext().execute_with(|| {
+		DNTFeeController::set_treasury(Some(MeaninglessTreasury::get()));
+		assert!(DNTFeeController::set_treasury_share(1_000.into()).is_ok());
+
+		let result = <Pallet<Test> as OnChargeDecentralizedNativeTokenFee>::pay_fees(
+			MeaninglessTokenAddress::get(),
+			(1.into(), 1.into()),
+			10_000.into(),
+			MeaninglessAddress::get(),
+			None,
+			&[],
+		);
+
+		assert_eq!(result.unwrap(), (9_000.into(), 0.into(), 1_000.into()));
+	})
+}
+
+#[test]
+fn treasury_share_is_ignored_without_treasury() {
+	new_test_ext().execute_with(|| {
+		assert!(DNTFeeController::set_treasury_share(1_000.into()).is_ok());
+
+		let result = <Pallet<Test> as OnChargeDecentralizedNativeTokenFee>::pay_fees(
+			MeaninglessTokenAddress::get(),
+			(1.into(), 1.into()),
+			10_000.into(),
+			MeaninglessAddress::get(),
+			Some(MeaninglessAddress2::get()),
+			&[],
+		);
+
+		assert_eq!(result.unwrap(), (5_000.into(), 5_000.into(), 0.into()));
+	})
+}
+
+#[test]
+fn fail_shares_above_basis_points() {
+	new_test_ext().execute_with(|| {
+		assert!(matches!(
+			DNTFeeController::set_treasury_share(5_001.into()),
+			Err(Error::<Test>::InvalidShare)
+		));
+
+		assert!(DNTFeeController::set_treasury_share(2_000.into()).is_ok());
+
+		assert!(matches!(
+			DNTFeeController::set_validator_share(8_001.into()),
+			Err(Error::<Test>::InvalidShare)
+		));
+		assert!(matches!(
+			DNTFeeController::set_validator_percentage(81.into()),
+			Err(Error::<Test>::InvalidPercentage)
+		));
+	})
+}
+
+#[test]
+fn validator_share_follows_percentage() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(DNTFeeController::get_validator_share(), 5_000.into());
+
+		assert!(DNTFeeController::set_validator_share(1_234.into()).is_ok());
+
+		assert_eq!(DNTFeeController::get_validator_share(), 1_234.into());
+		assert_eq!(DNTFeeController::validator_percentage().unwrap(), 12.into());
+	});
+}
+
+#[test]
+fn pay_fees_uses_dapp_share_override() {
+	new_test_ext().execute_with(|| {
+		DNTFeeController::set_treasury(Some(MeaninglessTreasury::get()));
+		assert!(DNTFeeController::set_treasury_share(1_000.into()).is_ok());
+		assert!(
+			DNTFeeController::set_dapp_share(MeaninglessAddress2::get(), Some(7_000.into()))
+				.is_ok()
+		);
+
+		let result = <Pallet<Test> as OnChargeDecentralizedNativeTokenFee>::pay_fees(
+			MeaninglessTokenAddress::get(),
+			(1.into(), 1.into()),
+			10_000.into(),
+			MeaninglessAddress::get(),
+			Some(MeaninglessAddress2::get()),
+			&[],
+		);
+
+		assert_eq!(result.unwrap(), (2_000.into(), 7_000.into(), 1_000.into()));
+		assert_eq!(
+			DNTFeeController::get_dapp_share(MeaninglessAddress2::get()),
+			7_000.into()
+		);
+
+		assert!(DNTFeeController::set_dapp_share(MeaninglessAddress2::get(), None).is_ok());
+
+		let result = <Pallet<Test> as OnChargeDecentralizedNativeTokenFee>::pay_fees(
+			MeaninglessTokenAddress::get(),
+			(1.into(), 1.into()),
+			10_000.into(),
+			MeaninglessAddress::get(),
+			Some(MeaninglessAddress2::get()),
+			&[],
+		);
+
+		assert_eq!(result.unwrap(), (5_000.into(), 4_000.into(), 1_000.into()));
+		assert_eq!(
+			DNTFeeController::get_dapp_share(MeaninglessAddress2::get()),
+			4_000.into()
+		);
+	})
+}
+
+#[test]
+fn dapp_share_override_is_capped_by_treasury_share() {
+	new_test_ext().execute_with(|| {
+		assert!(matches!(
+			DNTFeeController::set_dapp_share(MeaninglessAddress2::get(), Some(10_001.into())),
+			Err(Error::<Test>::InvalidShare)
+		));
+
+		assert!(
+			DNTFeeController::set_dapp_share(MeaninglessAddress2::get(), Some(9_500.into()))
+				.is_ok()
+		);
+		DNTFeeController::set_treasury(Some(MeaninglessTreasury::get()));
+		assert!(DNTFeeController::set_validator_share(0.into()).is_ok());
+		assert!(DNTFeeController::set_treasury_share(1_000.into()).is_ok());
+
+		let result = <Pallet<Test> as OnChargeDecentralizedNativeTokenFee>::pay_fees(
+			MeaninglessTokenAddress::get(),
+			(1.into(), 1.into()),
+			10_000.into(),
+			MeaninglessAddress::get(),
+			Some(MeaninglessAddress2::get()),
+			&[],
+		);
+
+		assert_eq!(result.unwrap(), (0.into(), 9_000.into(), 1_000.into()));
+	})
+}
+
+#[test]
+fn pay_fees_splits_dapp_share_by_consumed_gas() {
+	new_test_ext().execute_with(|| {
+		let router = MeaninglessAddress2::get();
+		let dapp = MeaninglessTreasury::get();
+
+		let result = <Pallet<Test> as OnChargeDecentralizedNativeTokenFee>::pay_fees(
+			MeaninglessTokenAddress::get(),
+			(1.into(), 1.into()),
+			10_001.into(),
+			MeaninglessAddress::get(),
+			Some(router),
+			&[(dapp, 30_000), (router, 10_000)],
+		);
+
+		assert_eq!(result.unwrap(), (5_000.into(), 5_001.into(), 0.into()));
+
+		assert_eq!(
+			pallet_fee_rewards_vault::Pallet::<Test>::claimable_reward(
+				dapp,
+				MeaninglessTokenAddress::get(),
+			),
+			3_751.into(),
+		);
+		assert_eq!(
+			pallet_fee_rewards_vault::Pallet::<Test>::claimable_reward(
+				router,
+				MeaninglessTokenAddress::get(),
+			),
+			1_250.into(),
+		);
+	})
+}
+
+#[test]
+fn dapp_share_override_applies_to_its_own_gas_only() {
+	new_test_ext().execute_with(|| {
+		let router = MeaninglessAddress2::get();
+		let dapp = MeaninglessTreasury::get();
+		assert!(DNTFeeController::set_dapp_share(router, Some(7_000.into())).is_ok());
+
+		let result = <Pallet<Test> as OnChargeDecentralizedNativeTokenFee>::pay_fees(
+			MeaninglessTokenAddress::get(),
+			(1.into(), 1.into()),
+			10_000.into(),
+			MeaninglessAddress::get(),
+			Some(router),
+			&[(dapp, 30_000), (router, 10_000)],
+		);
+
+		// The router gets its share of the fee of its own gas, and the dapp it called the
+		// default split.
+		assert_eq!(result.unwrap(), (4_500.into(), 5_500.into(), 0.into()));
+
+		assert_eq!(
+			pallet_fee_rewards_vault::Pallet::<Test>::claimable_reward(
+				dapp,
+				MeaninglessTokenAddress::get(),
+			),
+			3_750.into(),
+		);
+		assert_eq!(
+			pallet_fee_rewards_vault::Pallet::<Test>::claimable_reward(
+				router,
+				MeaninglessTokenAddress::get(),
+			),
+			1_750.into(),
+		);
+	})
+}
+
+#[test]
+fn consumers_that_cannot_claim_leave_their_share_to_the_validator() {
+	new_test_ext().execute_with(|| {
+		let dapp = MeaninglessAddress2::get();
+		let not_whitelisted = MeaninglessNotWhitelisted::get();
+
+		let result = <Pallet<Test> as OnChargeDecentralizedNativeTokenFee>::pay_fees(
+			MeaninglessTokenAddress::get(),
+			(1.into(), 1.into()),
+			10_000.into(),
+			MeaninglessAddress::get(),
+			Some(dapp),
+			&[(dapp, 30_000), (not_whitelisted, 10_000)],
+		);
+
+		assert_eq!(result.unwrap(), (6_250.into(), 3_750.into(), 0.into()));
+
+		assert_eq!(
+			pallet_fee_rewards_vault::Pallet::<Test>::claimable_reward(
+				MeaninglessAddress::get(),
+				MeaninglessTokenAddress::get(),
+			),
+			6_250.into(),
+		);
+		assert_eq!(
+			pallet_fee_rewards_vault::Pallet::<Test>::claimable_reward(
+				dapp,
+				MeaninglessTokenAddress::get(),
+			),
+			3_750.into(),
+		);
+		assert_eq!(
+			pallet_fee_rewards_vault::Pallet::<Test>::claimable_reward(
+				not_whitelisted,
+				MeaninglessTokenAddress::get(),
+			),
+			0.into(),
+		);
+	})
+}
+
+#[test]
+fn fail_pay_fees_when_shares_exceed_the_fee() {
+	new_test_ext().execute_with(|| {
+		DNTFeeController::set_treasury(Some(MeaninglessTreasury::get()));
+		assert!(DNTFeeController::set_treasury_share(2_000.into()).is_ok());
+		// Shares stored before the setters capped them
+		ValidatorShareStorage::<Test>::put(sp_core::U256::from(9_000));
+
+		let result = <Pallet<Test> as OnChargeDecentralizedNativeTokenFee>::pay_fees(
+			MeaninglessTokenAddress::get(),
+			(1.into(), 1.into()),
+			10_000.into(),
+			MeaninglessAddress::get(),
+			Some(MeaninglessAddress2::get()),
+			&[],
+		);
+
+		assert!(matches!(result, Err(Error::<Test>::FeeSplitUnderflow)));
+	})
+}
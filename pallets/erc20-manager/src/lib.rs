@@ -21,26 +21,86 @@
 
 pub use pallet::*;
 
+use frame_support::weights::Weight;
+use pallet_evm::Log;
 use sp_core::{H160, U256};
+use sp_std::vec::Vec;
 
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
 
+/// Topic of the ERC-20 `Transfer(address,address,uint256)` event.
+pub const TRANSFER_TOPIC: [u8; 32] = [
+	0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37, 0x8d, 0xaa,
+	0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a, 0x4d, 0xf5, 0x23, 0xb3, 0xef,
+];
+
 pub trait ERC20Manager {
 	type Error;
 	fn balance_of(token: H160, payer: H160) -> U256;
 	fn withdraw_amount(token: H160, payer: H160, amount: U256) -> Result<U256, Self::Error>;
 	fn deposit_amount(token: H160, payee: H160, amount: U256) -> Result<U256, Self::Error>;
+
+	fn transfer(token: H160, from: H160, to: H160, amount: U256) -> Result<U256, Self::Error> {
+		Self::withdraw_amount(token, from, amount)?;
+		Self::deposit_amount(token, to, amount)
+	}
+
+	/// Transfers back part of an amount moved by `transfer`, which already consulted the
+	/// compliance hook of the token, so that a refund does not fail on a change made since.
+	fn refund(token: H160, from: H160, to: H160, amount: U256) -> Result<U256, Self::Error> {
+		Self::transfer(token, from, to, amount)
+	}
+
+	/// Weight of a `transfer` of `token` besides its balance accesses, spent calling the
+	/// compliance hook of the token and depositing its `Transfer` log.
+	fn transfer_weight(_token: H160) -> Weight {
+		Weight::zero()
+	}
+}
+
+/// `Transfer` logs of the balance changes made while in use, collected to be added to the
+/// receipt of the Ethereum transaction making them. Outside of it, the logs are deposited as
+/// events right away, in the extrinsic or hook making the changes.
+#[derive(Default)]
+pub struct TransferLogs {
+	logs: Vec<Log>,
+}
+
+environmental::environmental!(transfer_logs: TransferLogs);
+
+impl TransferLogs {
+	/// Runs `f` collecting the logs of the balance changes it makes.
+	pub fn using<R>(&mut self, f: impl FnOnce() -> R) -> R {
+		transfer_logs::using(self, f)
+	}
+
+	/// Takes the logs collected so far.
+	pub fn take(&mut self) -> Vec<Log> {
+		sp_std::mem::take(&mut self.logs)
+	}
+
+	/// Collects `log` if logs are being collected, and gives it back otherwise.
+	pub fn collect(log: Log) -> Option<Log> {
+		let mut log = Some(log);
+		transfer_logs::with(|transfer_logs| transfer_logs.logs.extend(log.take()));
+		log
+	}
 }
 
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 
-	use pallet_supported_tokens_manager::SupportedTokensManager;
+	use frame_support::pallet_prelude::*;
+	use pallet_evm::GasWeightMapping;
+	use pallet_supported_tokens_manager::{
+		BalanceLayout, SupportedTokensManager, TOKEN_VIEW_GAS_LIMIT,
+	};
 	use sp_core::{H160, H256, U256};
+	use sp_std::vec;
 	use stbl_tools::some_or_err;
 
 	#[pallet::pallet]
@@ -52,6 +112,7 @@ pub mod pallet {
 		UnderflowBalance,
 		OverflowBalance,
 		FailedTokenConfiguration,
+		NonCompliantTransfer,
 	}
 
 	#[pallet::config]
@@ -63,10 +124,10 @@ pub mod pallet {
 		type Error = Error<T>;
 
 		fn balance_of(token: H160, user: H160) -> U256 {
-			let slot = Self::get_address_balance_storage_slot(token, user);
+			let (slot, mask) = Self::get_address_balance_storage_location(token, user);
 
-			let balance = pallet_evm::AccountStorages::<T>::get(token, slot);
-			U256::from_big_endian(balance.as_bytes())
+			let word = pallet_evm::AccountStorages::<T>::get(token, slot);
+			Self::decode_balance(word, mask)
 		}
 
 		fn withdraw_amount(token: H160, payer: H160, amount: U256) -> Result<U256, Error<T>> {
@@ -74,56 +135,176 @@ pub mod pallet {
 				return Ok(U256::from(0));
 			};
 
-			let slot = Self::get_address_balance_storage_slot(token, payer);
+			Self::ensure_compliant(token, &[payer])?;
+			Self::sub_balance(token, payer, amount)?;
+			Self::record_transfer_log(token, payer, H160::zero(), amount);
 
-			pallet_evm::AccountStorages::<T>::try_mutate(&token, &slot, |stored_value| {
-				let current_balance = U256::from_big_endian(stored_value.as_bytes());
-				let new_balance = some_or_err!(current_balance.checked_sub(amount), || {
-					Error::<T>::UnderflowBalance
-				});
-
-				let mut new_balance_bytes: [u8; 32] = [0; 32];
-				new_balance.to_big_endian(&mut new_balance_bytes);
+			Ok(amount)
+		}
 
-				*stored_value = H256::from(new_balance_bytes);
+		fn deposit_amount(token: H160, payee: H160, amount: U256) -> Result<U256, Self::Error> {
+			if amount.is_zero() {
+				return Ok(U256::from(0));
+			};
 
-				Ok(amount)
-			})?;
+			Self::ensure_compliant(token, &[payee])?;
+			Self::add_balance(token, payee, amount)?;
+			Self::record_transfer_log(token, H160::zero(), payee, amount);
 
 			Ok(amount)
 		}
 
-		fn deposit_amount(token: H160, payee: H160, amount: U256) -> Result<U256, Self::Error> {
+		fn transfer(token: H160, from: H160, to: H160, amount: U256) -> Result<U256, Self::Error> {
 			if amount.is_zero() {
 				return Ok(U256::from(0));
 			};
 
-			let slot = Self::get_address_balance_storage_slot(token, payee);
+			Self::ensure_compliant(token, &[from, to])?;
+			Self::move_balance(token, from, to, amount)
+		}
+
+		fn refund(token: H160, from: H160, to: H160, amount: U256) -> Result<U256, Self::Error> {
+			if amount.is_zero() {
+				return Ok(U256::from(0));
+			};
+
+			Self::move_balance(token, from, to, amount)
+		}
+
+		fn transfer_weight(token: H160) -> Weight {
+			let views = T::SupportedTokensManager::compliance_views(token, 2);
+			T::GasWeightMapping::gas_to_weight(
+				TOKEN_VIEW_GAS_LIMIT.saturating_mul(views.into()),
+				true,
+			)
+			.saturating_add(T::DbWeight::get().writes(1))
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		// As the token code is bypassed, its blacklist and pause switch are checked through the
+		// compliance hook of the token before any balance is moved.
+		fn ensure_compliant(token: H160, accounts: &[H160]) -> Result<(), Error<T>> {
+			match T::SupportedTokensManager::is_compliant(token, accounts) {
+				true => Ok(()),
+				false => Err(Error::<T>::NonCompliantTransfer),
+			}
+		}
+
+		fn move_balance(token: H160, from: H160, to: H160, amount: U256) -> Result<U256, Error<T>> {
+			Self::sub_balance(token, from, amount)?;
+			Self::add_balance(token, to, amount)?;
+			Self::record_transfer_log(token, from, to, amount);
+
+			Ok(amount)
+		}
+
+		fn sub_balance(token: H160, payer: H160, amount: U256) -> Result<(), Error<T>> {
+			let (slot, mask) = Self::get_address_balance_storage_location(token, payer);
 
 			pallet_evm::AccountStorages::<T>::try_mutate(&token, &slot, |stored_value| {
-				let current_balance = U256::from_big_endian(stored_value.as_bytes());
+				let current_balance = Self::decode_balance(*stored_value, mask);
+				let new_balance = some_or_err!(current_balance.checked_sub(amount), || {
+					Error::<T>::UnderflowBalance
+				});
+
+				*stored_value = some_or_err!(
+					Self::encode_balance(*stored_value, mask, new_balance),
+					|| Error::<T>::OverflowBalance
+				);
+
+				Ok(())
+			})
+		}
+
+		fn add_balance(token: H160, payee: H160, amount: U256) -> Result<(), Error<T>> {
+			let (slot, mask) = Self::get_address_balance_storage_location(token, payee);
+
+			pallet_evm::AccountStorages::<T>::try_mutate(&token, &slot, |stored_value| {
+				let current_balance = Self::decode_balance(*stored_value, mask);
 				let new_balance = some_or_err!(current_balance.checked_add(amount), || {
 					Error::<T>::OverflowBalance
 				});
 
-				let mut new_balance_bytes: [u8; 32] = [0; 32];
-				new_balance.to_big_endian(&mut new_balance_bytes);
-
-				*stored_value = H256::from(new_balance_bytes);
+				*stored_value = some_or_err!(
+					Self::encode_balance(*stored_value, mask, new_balance),
+					|| Error::<T>::OverflowBalance
+				);
 
 				Ok(())
-			})?;
+			})
+		}
 
-			Ok(amount)
+		// Withdrawals and deposits that are not part of a transfer are logged as a burn or a
+		// mint, so the balances rebuilt from the logs match the storage.
+		fn record_transfer_log(token: H160, from: H160, to: H160, amount: U256) {
+			let mut data = [0u8; 32];
+			amount.to_big_endian(&mut data);
+
+			let log = Log {
+				address: token,
+				topics: vec![TRANSFER_TOPIC.into(), from.into(), to.into()],
+				data: data.to_vec(),
+			};
+
+			if let Some(log) = TransferLogs::collect(log) {
+				Self::deposit_transfer_log_event(log);
+			}
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		fn get_address_balance_storage_slot(token: H160, address: H160) -> H256 {
-			let balance_slot = T::SupportedTokensManager::get_token_balance_slot(token)
-				.unwrap_or(H256::from_low_u64_be(0));
+		// Deposits `log` as an EVM log event, for the balance changes without a receipt.
+		fn deposit_transfer_log_event(log: Log) {
+			let event =
+				<<T as pallet_evm::Config>::RuntimeEvent as From<pallet_evm::Event<T>>>::from(
+					pallet_evm::Event::<T>::Log { log },
+				);
+			let event = <<T as pallet_evm::Config>::RuntimeEvent as Into<
+				<T as frame_system::Config>::RuntimeEvent,
+			>>::into(event);
+			<frame_system::Pallet<T>>::deposit_event(event);
+		}
+
+		fn get_address_balance_storage_location(token: H160, address: H160) -> (H256, U256) {
+			let layout = T::SupportedTokensManager::get_token_balance_layout(token)
+				.unwrap_or(BalanceLayout::mapping(H256::from_low_u64_be(0)));
+
+			Self::balance_storage_location(&layout, address)
+		}
+
+		/// Slot of the word holding the balance of `address` in `layout`, and the bits of the
+		/// word the balance takes.
+		pub fn balance_storage_location(layout: &BalanceLayout, address: H160) -> (H256, U256) {
+			let mut slot = layout.slot;
+			for key in layout.outer_keys.iter() {
+				slot = stbl_tools::eth::get_storage_address_for_key(*key, slot);
+			}
+			slot = stbl_tools::eth::get_storage_address_for_mapping(address, slot);
+			for key in layout.inner_keys.iter() {
+				slot = stbl_tools::eth::get_storage_address_for_key(*key, slot);
+			}
+
+			let slot = U256::from_big_endian(slot.as_bytes())
+				.overflowing_add(layout.offset.into())
+				.0;
+
+			(stbl_tools::misc::u256_to_h256(slot), layout.mask)
+		}
+
+		/// The balance in the `mask` bits of `word`.
+		pub fn decode_balance(word: H256, mask: U256) -> U256 {
+			(U256::from_big_endian(word.as_bytes()) & mask) >> mask.trailing_zeros()
+		}
+
+		/// Writes `balance` in the `mask` bits of `word`, keeping the other bits. `None` if the
+		/// balance does not fit.
+		pub fn encode_balance(word: H256, mask: U256, balance: U256) -> Option<H256> {
+			let shift = mask.trailing_zeros();
+			if balance > mask >> shift {
+				return None;
+			}
 
-			stbl_tools::eth::get_storage_address_for_mapping(address, balance_slot)
+			let word = (U256::from_big_endian(word.as_bytes()) & !mask) | (balance << shift);
+			Some(stbl_tools::misc::u256_to_h256(word))
 		}
 	}
 }
@@ -22,10 +22,12 @@
 use super::*;
 use crate as map_svm_evm;
 
+use core::cell::RefCell;
 use frame_support::pallet_prelude::Weight;
 use frame_support::parameter_types;
 use frame_support::traits::{Contains, Everything};
 use hex::FromHex;
+use pallet_supported_tokens_manager::{BalanceLayout, ComplianceHook};
 use sp_core::{H256, U256};
 use sp_runtime::BuildStorage;
 use sp_runtime::{
@@ -155,6 +157,11 @@ parameter_types! {
 	pub MeaninglessAddress:H160 = H160::from_str("0xaf537bd156c7E548D0BF2CD43168dABF7aF2feb5").expect("invalid address");
 	pub MeaninglessTokenAddress:H160 = H160::from_str("0xdAC17F958D2ee523a2206206994597C13D831ec7").expect("invalid address");
 }
+thread_local! {
+	pub static BALANCE_LAYOUT: RefCell<Option<BalanceLayout>> = RefCell::new(None);
+	pub static BLACKLISTED: RefCell<Vec<H160>> = RefCell::new(Vec::new());
+}
+
 pub struct MockSupportedTokensManager;
 
 impl pallet_supported_tokens_manager::SupportedTokensManager for MockSupportedTokensManager {
@@ -170,14 +177,42 @@ impl pallet_supported_tokens_manager::SupportedTokensManager for MockSupportedTo
 		Ok(())
 	}
 
+	fn add_supported_token_with_layout(
+		_token: H160,
+		_layout: BalanceLayout,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+
 	fn remove_supported_token(_token: H160) -> Result<(), Self::Error> {
 		Ok(())
 	}
 
+	fn set_token_compliance_hook(
+		_token: H160,
+		_hook: Option<ComplianceHook>,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+
+	fn is_compliant(_token: H160, accounts: &[H160]) -> bool {
+		BLACKLISTED.with(|blacklisted| {
+			!accounts
+				.iter()
+				.any(|account| blacklisted.borrow().contains(account))
+		})
+	}
+
 	fn get_token_balance_slot(_token: H160) -> Option<H256> {
 		Some(ZeroSlot::get())
 	}
 
+	fn get_token_balance_layout(_token: H160) -> Option<BalanceLayout> {
+		BALANCE_LAYOUT
+			.with(|layout| layout.borrow().clone())
+			.or(Some(BalanceLayout::mapping(ZeroSlot::get())))
+	}
+
 	type Error = ();
 
 	fn get_default_token() -> H160 {
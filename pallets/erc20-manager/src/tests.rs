@@ -17,9 +17,14 @@
 // Please see the Stability Open License Agreement for more
 // information.
 
-use crate::mock::{new_test_ext, ERC20SlotZero, MeaninglessAddress, Test};
-use pallet_evm::Runner;
-use sp_core::{H160, U256};
+use crate::mock::{
+	new_test_ext, ERC20SlotZero, MeaninglessAddress, RuntimeEvent, System, Test, BALANCE_LAYOUT,
+	BLACKLISTED,
+};
+use crate::{ERC20Manager, TransferLogs, TRANSFER_TOPIC};
+use pallet_evm::{Log, Runner};
+use pallet_supported_tokens_manager::BalanceLayout;
+use sp_core::{H160, H256, U256};
 
 type TestRunner = <Test as pallet_evm::Config>::Runner;
 
@@ -139,3 +144,245 @@ fn fail_when_overflow() {
 		);
 	});
 }
+
+fn transfer_log(from: H160, to: H160, amount: U256) -> Log {
+	Log {
+		address: ERC20SlotZero::get(),
+		topics: vec![TRANSFER_TOPIC.into(), from.into(), to.into()],
+		data: stbl_tools::misc::u256_to_h256(amount).as_bytes().to_vec(),
+	}
+}
+
+#[test]
+fn transfer_records_a_transfer_log() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let recipient = H160::from_low_u64_be(1);
+		let mut transfer_logs = TransferLogs::default();
+
+		transfer_logs.using(|| {
+			crate::Pallet::<Test>::deposit_amount(
+				ERC20SlotZero::get(),
+				MeaninglessAddress::get(),
+				100.into(),
+			)
+			.unwrap();
+			crate::Pallet::<Test>::transfer(
+				ERC20SlotZero::get(),
+				MeaninglessAddress::get(),
+				recipient,
+				40.into(),
+			)
+			.unwrap();
+		});
+
+		assert_eq!(
+			get_balance_of(ERC20SlotZero::get(), MeaninglessAddress::get()),
+			60.into()
+		);
+		assert_eq!(get_balance_of(ERC20SlotZero::get(), recipient), 40.into());
+		assert_eq!(
+			transfer_logs.take(),
+			vec![
+				transfer_log(H160::zero(), MeaninglessAddress::get(), 100.into()),
+				transfer_log(MeaninglessAddress::get(), recipient, 40.into()),
+			]
+		);
+		assert_eq!(transfer_logs.take(), vec![]);
+		// Collected logs are not deposited as events.
+		assert_eq!(System::events(), vec![]);
+	});
+}
+
+#[test]
+fn failed_transfer_records_no_log() {
+	new_test_ext().execute_with(|| {
+		let mut transfer_logs = TransferLogs::default();
+
+		transfer_logs.using(|| {
+			assert!(crate::Pallet::<Test>::withdraw_amount(
+				ERC20SlotZero::get(),
+				MeaninglessAddress::get(),
+				1000.into(),
+			)
+			.is_err());
+			assert!(crate::Pallet::<Test>::transfer(
+				ERC20SlotZero::get(),
+				MeaninglessAddress::get(),
+				H160::from_low_u64_be(1),
+				1000.into(),
+			)
+			.is_err());
+		});
+
+		assert_eq!(transfer_logs.take(), vec![]);
+	});
+}
+
+#[test]
+fn non_compliant_accounts_cannot_send_or_receive() {
+	new_test_ext().execute_with(|| {
+		let recipient = H160::from_low_u64_be(1);
+
+		crate::Pallet::<Test>::deposit_amount(
+			ERC20SlotZero::get(),
+			MeaninglessAddress::get(),
+			100.into(),
+		)
+		.unwrap();
+		BLACKLISTED.with(|blacklisted| blacklisted.borrow_mut().push(recipient));
+		let mut transfer_logs = TransferLogs::default();
+		transfer_logs.using(|| {
+			assert!(matches!(
+				crate::Pallet::<Test>::transfer(
+					ERC20SlotZero::get(),
+					MeaninglessAddress::get(),
+					recipient,
+					40.into(),
+				),
+				Err(crate::Error::NonCompliantTransfer)
+			));
+			assert!(matches!(
+				crate::Pallet::<Test>::deposit_amount(ERC20SlotZero::get(), recipient, 40.into()),
+				Err(crate::Error::NonCompliantTransfer)
+			));
+
+			BLACKLISTED
+				.with(|blacklisted| *blacklisted.borrow_mut() = vec![MeaninglessAddress::get()]);
+			assert!(matches!(
+				crate::Pallet::<Test>::withdraw_amount(
+					ERC20SlotZero::get(),
+					MeaninglessAddress::get(),
+					40.into(),
+				),
+				Err(crate::Error::NonCompliantTransfer)
+			));
+		});
+
+		assert_eq!(
+			get_balance_of(ERC20SlotZero::get(), MeaninglessAddress::get()),
+			100.into()
+		);
+		assert_eq!(get_balance_of(ERC20SlotZero::get(), recipient), 0.into());
+		assert_eq!(transfer_logs.take(), vec![]);
+	});
+}
+
+#[test]
+fn refund_skips_compliance_hook() {
+	new_test_ext().execute_with(|| {
+		crate::Pallet::<Test>::deposit_amount(
+			ERC20SlotZero::get(),
+			MeaninglessAddress::get(),
+			100.into(),
+		)
+		.unwrap();
+		BLACKLISTED.with(|blacklisted| *blacklisted.borrow_mut() = vec![MeaninglessAddress::get()]);
+
+		let recipient = H160::from_low_u64_be(1);
+		assert_eq!(
+			crate::Pallet::<Test>::refund(
+				ERC20SlotZero::get(),
+				MeaninglessAddress::get(),
+				recipient,
+				40.into(),
+			)
+			.ok(),
+			Some(40.into())
+		);
+
+		assert_eq!(
+			get_balance_of(ERC20SlotZero::get(), MeaninglessAddress::get()),
+			60.into()
+		);
+		assert_eq!(get_balance_of(ERC20SlotZero::get(), recipient), 40.into());
+	});
+}
+
+#[test]
+fn transfer_logs_not_collected_are_deposited_as_events() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		crate::Pallet::<Test>::deposit_amount(
+			ERC20SlotZero::get(),
+			MeaninglessAddress::get(),
+			100.into(),
+		)
+		.unwrap();
+
+		System::assert_last_event(RuntimeEvent::Evm(pallet_evm::Event::Log {
+			log: transfer_log(H160::zero(), MeaninglessAddress::get(), 100.into()),
+		}));
+	});
+}
+
+#[test]
+fn packed_balance_in_nested_mapping() {
+	new_test_ext().execute_with(|| {
+		let token = ERC20SlotZero::get();
+		let holder = MeaninglessAddress::get();
+		let partition = H256::from_low_u64_be(7);
+		let flags = U256::one() << 255;
+
+		// mapping(bytes32 => mapping(address => struct { uint256 a; uint128 balance; bool flag; }))
+		BALANCE_LAYOUT.with(|layout| {
+			*layout.borrow_mut() = Some(BalanceLayout {
+				slot: H256::from_low_u64_be(3),
+				outer_keys: vec![partition],
+				inner_keys: vec![],
+				offset: 1,
+				mask: U256::from(u128::MAX),
+			})
+		});
+
+		let entry = stbl_tools::eth::get_storage_address_for_mapping(
+			holder,
+			stbl_tools::eth::get_storage_address_for_key(partition, H256::from_low_u64_be(3)),
+		);
+		let slot =
+			stbl_tools::misc::u256_to_h256(U256::from_big_endian(entry.as_bytes()) + U256::one());
+		pallet_evm::AccountStorages::<Test>::insert(
+			token,
+			slot,
+			stbl_tools::misc::u256_to_h256(flags),
+		);
+
+		crate::Pallet::<Test>::deposit_amount(token, holder, 100.into()).unwrap();
+		crate::Pallet::<Test>::withdraw_amount(token, holder, 40.into()).unwrap();
+
+		assert_eq!(crate::Pallet::<Test>::balance_of(token, holder), 60.into());
+		assert_eq!(
+			pallet_evm::AccountStorages::<Test>::get(token, slot),
+			stbl_tools::misc::u256_to_h256(flags + U256::from(60))
+		);
+
+		// The balance cannot overflow into the flags.
+		assert!(crate::Pallet::<Test>::deposit_amount(token, holder, u128::MAX.into()).is_err());
+	});
+}
+
+#[test]
+fn balance_in_upper_bits() {
+	new_test_ext().execute_with(|| {
+		let token = ERC20SlotZero::get();
+		let holder = MeaninglessAddress::get();
+
+		BALANCE_LAYOUT.with(|layout| {
+			*layout.borrow_mut() = Some(BalanceLayout {
+				mask: U256::MAX << 160,
+				..BalanceLayout::mapping(H256::from_low_u64_be(5))
+			})
+		});
+
+		crate::Pallet::<Test>::deposit_amount(token, holder, 1.into()).unwrap();
+
+		let slot =
+			stbl_tools::eth::get_storage_address_for_mapping(holder, H256::from_low_u64_be(5));
+		assert_eq!(
+			pallet_evm::AccountStorages::<Test>::get(token, slot),
+			stbl_tools::misc::u256_to_h256(U256::one() << 160)
+		);
+		assert_eq!(crate::Pallet::<Test>::balance_of(token, holder), 1.into());
+	});
+}
@@ -25,23 +25,51 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod migrations;
+
 pub use pallet::*;
 
+use pallet_erc20_manager::ERC20Manager;
 use sp_core::{H160, U256};
+use sp_std::vec::Vec;
 
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use frame_support::pallet_prelude::*;
+	use frame_support::{pallet_prelude::*, traits::Contains};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Zero;
 
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config {}
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		type ERC20Manager: ERC20Manager;
+		/// Address holding the tokens of the claimable rewards.
+		type VaultAddress: Get<H160>;
+		/// Holders whose rewards can be paid out automatically, besides not being frozen.
+		type PayoutFilter: Contains<H160>;
+		/// Blocks between the start of two automatic payout rounds.
+		#[pallet::constant]
+		type PayoutPeriod: Get<BlockNumberFor<Self>>;
+		/// Holders paid out per block while a payout round is in progress.
+		#[pallet::constant]
+		type MaxPayoutsPerBlock: Get<u32>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A reward was paid out automatically. [holder, recipient, token, amount]
+		RewardPaidOut(H160, H160, H160, U256),
+		/// An automatic payout failed and the reward stays claimable. [holder, token, amount]
+		RewardPayoutFailed(H160, H160, U256),
+	}
 
 	// double map
 	#[pallet::storage]
@@ -54,6 +82,58 @@ pub mod pallet {
 	#[pallet::getter(fn whitelist)]
 	pub(super) type Whitelist<T: Config> = StorageMap<_, Twox64Concat, H160, bool, ValueQuery>;
 
+	// addresses that have accrued validator rewards, which they can claim after leaving the set
+	#[pallet::storage]
+	#[pallet::getter(fn accrued_validator_rewards)]
+	pub(super) type AccruedValidatorRewards<T: Config> =
+		StorageMap<_, Twox64Concat, H160, bool, ValueQuery>;
+
+	// address each holder has its rewards sent to
+	#[pallet::storage]
+	#[pallet::getter(fn payout_address)]
+	pub(super) type PayoutAddress<T: Config> = StorageMap<_, Twox64Concat, H160, H160, OptionQuery>;
+
+	// address each holder allows to claim its rewards on its behalf
+	#[pallet::storage]
+	#[pallet::getter(fn claim_delegate)]
+	pub(super) type ClaimDelegate<T: Config> = StorageMap<_, Twox64Concat, H160, H160, OptionQuery>;
+
+	// holders opted in to automatic payouts, with the minimum reward per token paid out
+	#[pallet::storage]
+	#[pallet::getter(fn auto_payout)]
+	pub(super) type AutoPayout<T: Config> = StorageMap<_, Twox64Concat, H160, U256, OptionQuery>;
+
+	// whether a payout round is in progress
+	#[pallet::storage]
+	pub(super) type PayoutInProgress<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	// last holder paid out in the round in progress
+	#[pallet::storage]
+	pub(super) type PayoutCursor<T: Config> = StorageValue<_, H160, OptionQuery>;
+
+	// addresses whose rewards governance has frozen
+	#[pallet::storage]
+	#[pallet::getter(fn frozen)]
+	pub(super) type Frozen<T: Config> = StorageMap<_, Twox64Concat, H160, bool, ValueQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			let mut weight = T::DbWeight::get().reads(1);
+
+			if !PayoutInProgress::<T>::get() {
+				let period = T::PayoutPeriod::get();
+				if period.is_zero() || !(n % period).is_zero() {
+					return weight;
+				}
+				PayoutInProgress::<T>::put(true);
+				weight = weight.saturating_add(T::DbWeight::get().writes(1));
+			}
+
+			weight.saturating_add(Self::process_payouts())
+		}
+	}
+
 	impl<T: Config> Pallet<T> {
 		pub fn is_whitelisted(address: H160) -> bool {
 			Self::whitelist(address)
@@ -63,10 +143,55 @@ pub mod pallet {
 			Whitelist::<T>::insert(address, is_whitelisted);
 		}
 
+		pub fn has_accrued_validator_rewards(address: H160) -> bool {
+			Self::accrued_validator_rewards(address)
+		}
+
+		pub fn get_auto_payout(address: H160) -> Option<U256> {
+			Self::auto_payout(address)
+		}
+
+		/// Opts `address` in to automatic payouts of the rewards of at least `minimum`, or out
+		/// with `None`.
+		pub fn set_auto_payout(address: H160, minimum: Option<U256>) {
+			AutoPayout::<T>::set(address, minimum);
+		}
+
+		pub fn is_frozen(address: H160) -> bool {
+			Self::frozen(address)
+		}
+
+		pub fn set_frozen(address: H160, is_frozen: bool) {
+			Frozen::<T>::insert(address, is_frozen);
+		}
+
+		pub fn get_payout_address(address: H160) -> Option<H160> {
+			Self::payout_address(address)
+		}
+
+		pub fn set_payout_address(address: H160, payout_address: Option<H160>) {
+			PayoutAddress::<T>::set(address, payout_address);
+		}
+
+		pub fn get_claim_delegate(address: H160) -> Option<H160> {
+			Self::claim_delegate(address)
+		}
+
+		pub fn set_claim_delegate(address: H160, delegate: Option<H160>) {
+			ClaimDelegate::<T>::set(address, delegate);
+		}
+
 		pub fn get_claimable_reward(address: H160, token: H160) -> U256 {
 			Self::claimable_reward(address, token)
 		}
 
+		/// Tokens with a non-zero claimable reward for `address`, with their amounts.
+		pub fn get_claimable_rewards(address: H160) -> Vec<(H160, U256)> {
+			ClaimableReward::<T>::iter_prefix(address)
+				.filter(|(_, amount)| !amount.is_zero())
+				.collect()
+		}
+
 		pub fn add_claimable_reward(
 			address: H160,
 			token: H160,
@@ -85,6 +210,21 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Adds a reward earned as a validator, recording `address` so it keeps its claim rights
+		/// once it leaves the validator set.
+		pub fn add_validator_reward(
+			address: H160,
+			token: H160,
+			amount: U256,
+		) -> Result<(), &'static str> {
+			Self::add_claimable_reward(address, token, amount)?;
+
+			if !amount.is_zero() && !Self::accrued_validator_rewards(address) {
+				AccruedValidatorRewards::<T>::insert(address, true);
+			}
+			Ok(())
+		}
+
 		pub fn sub_claimable_reward(
 			address: H160,
 			token: H160,
@@ -102,5 +242,77 @@ pub mod pallet {
 			ClaimableReward::<T>::insert(address, token, new_amount);
 			Ok(())
 		}
+
+		// Pays out the next holders of the round in progress, resuming after the cursor, and
+		// ends the round once every holder is paid out.
+		fn process_payouts() -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut weight = db_weight.reads(1);
+
+			let mut holders = match PayoutCursor::<T>::get() {
+				Some(last) => AutoPayout::<T>::iter_from(AutoPayout::<T>::hashed_key_for(last)),
+				None => AutoPayout::<T>::iter(),
+			};
+
+			let mut last = None;
+			for _ in 0..T::MaxPayoutsPerBlock::get() {
+				match holders.next() {
+					Some((holder, minimum)) => {
+						weight = weight
+							.saturating_add(db_weight.reads(1))
+							.saturating_add(Self::payout(holder, minimum));
+						last = Some(holder);
+					}
+					None => {
+						PayoutInProgress::<T>::kill();
+						PayoutCursor::<T>::kill();
+						return weight.saturating_add(db_weight.writes(2));
+					}
+				}
+			}
+
+			PayoutCursor::<T>::set(last);
+			weight.saturating_add(db_weight.writes(1))
+		}
+
+		// Transfers the rewards of `holder` of at least `minimum` to its payout address.
+		fn payout(holder: H160, minimum: U256) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut weight = db_weight.reads(2);
+
+			if Self::is_frozen(holder) || !T::PayoutFilter::contains(&holder) {
+				return weight;
+			}
+
+			let recipient = Self::payout_address(holder).unwrap_or(holder);
+			weight = weight.saturating_add(db_weight.reads(1));
+
+			for (token, amount) in Self::get_claimable_rewards(holder) {
+				weight = weight.saturating_add(db_weight.reads(1));
+
+				if amount < minimum {
+					continue;
+				}
+
+				let result = frame_support::storage::with_storage_layer(|| -> DispatchResult {
+					Self::sub_claimable_reward(holder, token, amount)?;
+					T::ERC20Manager::transfer(token, T::VaultAddress::get(), recipient, amount)
+						.map_err(|_| DispatchError::Other("Payout transfer failed"))?;
+					Ok(())
+				});
+				weight = weight
+					.saturating_add(db_weight.reads_writes(2, 3))
+					.saturating_add(T::ERC20Manager::transfer_weight(token));
+
+				match result {
+					Ok(()) => {
+						Self::deposit_event(Event::RewardPaidOut(holder, recipient, token, amount))
+					}
+					Err(_) => Self::deposit_event(Event::RewardPayoutFailed(holder, token, amount)),
+				}
+			}
+
+			weight
+		}
 	}
 }
@@ -0,0 +1,73 @@
+// Copyright © 2022 STABILITY SOLUTIONS, INC. (“STABILITY”)
+// This file is part of the Stability Global Trust Network client
+// software and accompanying documentation (the “Software”).
+
+// You can download and use the Software for free under the terms of
+// the Stability Open License Agreement as published by Stability on
+// Github at https://github.com/stabilityprotocol/stability/blob/master/LICENSE.
+
+// THE SOFTWARE IS PROVIDED “AS IS” WITHOUT WARRANTY OF ANY KIND.
+// STABILITY EXPRESSLY DISCLAIMS ALL WARRANTIES, EXPRESS OR IMPLIED,
+// INCLUDING MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE, AND
+// NON-INFRINGEMENT. IN NO EVENT SHALL OWNER BE LIABLE FOR ANY
+// INDIRECT, INCIDENTAL, SPECIAL OR CONSEQUENTIAL DAMAGES ARISING
+// OUT OF USE OF THE SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF
+// SUCH DAMAGES.
+
+// Please see the Stability Open License Agreement for more
+// information.
+
+//! Storage migrations of the Fee Rewards Vault pallet.
+
+use super::*;
+use frame_support::{
+	migrations::VersionedMigration,
+	traits::{Contains, Get, UncheckedOnRuntimeUpgrade},
+	weights::Weight,
+};
+use sp_std::{collections::btree_set::BTreeSet, marker::PhantomData};
+
+pub mod v2 {
+	use super::*;
+
+	/// Records the holders of claimable rewards that are `Validators` in
+	/// `AccruedValidatorRewards`, as the rewards they accrued before it existed were earned as
+	/// validators.
+	pub struct InnerMigrateToV2<T, Validators>(PhantomData<(T, Validators)>);
+
+	impl<T: Config, Validators: Contains<H160>> UncheckedOnRuntimeUpgrade
+		for InnerMigrateToV2<T, Validators>
+	{
+		fn on_runtime_upgrade() -> Weight {
+			let mut reads = 0u64;
+			let mut writes = 0u64;
+
+			let mut holders = BTreeSet::new();
+			for (holder, _, amount) in ClaimableReward::<T>::iter() {
+				reads += 1;
+				if !amount.is_zero() {
+					holders.insert(holder);
+				}
+			}
+
+			for holder in holders {
+				reads += 1;
+				if Validators::contains(&holder) {
+					AccruedValidatorRewards::<T>::insert(holder, true);
+					writes += 1;
+				}
+			}
+
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+	}
+
+	/// Migrates the pallet to the storage version 2, which added `AccruedValidatorRewards`.
+	pub type MigrateToV2<T, Validators> = VersionedMigration<
+		1,
+		2,
+		InnerMigrateToV2<T, Validators>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
@@ -21,9 +21,10 @@
 
 use crate as pallet_fee_rewards_vault;
 
-use frame_support::ord_parameter_types;
+use core::cell::RefCell;
 use frame_support::traits::{ConstU32, ConstU64, Contains};
-use sp_core::H256;
+use frame_support::{ord_parameter_types, parameter_types};
+use sp_core::{H160, H256, U256};
 use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
 use sp_runtime::BuildStorage;
 
@@ -73,7 +74,57 @@ ord_parameter_types! {
 	pub const NotAllowedAccountId: u64 = 2;
 }
 
-impl pallet_fee_rewards_vault::Config for Test {}
+parameter_types! {
+	pub VaultAddress: H160 = H160::from_low_u64_be(0x807);
+	pub ExcludedHolder: H160 = H160::from_low_u64_be(0xdead);
+}
+
+thread_local! {
+	pub static ERC20_TRANSFERS: RefCell<Vec<(H160, H160, H160, U256)>> = RefCell::new(Vec::new());
+	pub static ERC20_FAILS: RefCell<bool> = RefCell::new(false);
+}
+
+/// Records the transfers out of the vault as (token, from, to, amount).
+pub struct MockERC20Manager;
+impl pallet_erc20_manager::ERC20Manager for MockERC20Manager {
+	type Error = ();
+
+	fn balance_of(_token: H160, _payer: H160) -> U256 {
+		Default::default()
+	}
+
+	fn withdraw_amount(_token: H160, _payer: H160, amount: U256) -> Result<U256, Self::Error> {
+		Ok(amount)
+	}
+
+	fn deposit_amount(token: H160, payee: H160, amount: U256) -> Result<U256, Self::Error> {
+		if ERC20_FAILS.with(|fails| *fails.borrow()) {
+			return Err(());
+		}
+		ERC20_TRANSFERS.with(|transfers| {
+			transfers
+				.borrow_mut()
+				.push((token, VaultAddress::get(), payee, amount))
+		});
+		Ok(amount)
+	}
+}
+
+pub struct PayoutFilter;
+impl Contains<H160> for PayoutFilter {
+	fn contains(holder: &H160) -> bool {
+		*holder != ExcludedHolder::get()
+	}
+}
+
+impl pallet_fee_rewards_vault::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ERC20Manager = MockERC20Manager;
+	type VaultAddress = VaultAddress;
+	type PayoutFilter = PayoutFilter;
+	type PayoutPeriod = ConstU64<10>;
+	type MaxPayoutsPerBlock = ConstU32<2>;
+}
 
 frame_support::construct_runtime!(
 	pub enum Test {
@@ -86,5 +137,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	let t = frame_system::GenesisConfig::<Test>::default()
 		.build_storage()
 		.unwrap();
-	t.into()
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
@@ -20,9 +20,15 @@
 //! Tests for the Validator Set pallet.
 
 use super::*;
-use crate::mock::{new_test_ext, FeeRewardsVault};
+use crate::mock::{
+	new_test_ext, ExcludedHolder, FeeRewardsVault, PayoutFilter, RuntimeEvent, System, Test,
+	VaultAddress, ERC20_FAILS, ERC20_TRANSFERS,
+};
 use core::str::FromStr;
-use frame_support::{assert_noop, parameter_types};
+use frame_support::{
+	assert_noop, parameter_types,
+	traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+};
 
 parameter_types! {
 	pub DappAddress:H160 = H160::from_str("0x5F4bf370dA7e059FAf4eE007233f48D5131F1983").expect("invalid address");
@@ -128,3 +134,316 @@ fn sub_claimable_reward_with_insufficient_balance_should_fail() {
 		);
 	});
 }
+
+#[test]
+fn add_validator_reward_should_record_the_validator() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			FeeRewardsVault::has_accrued_validator_rewards(DappAddress::get()),
+			false
+		);
+
+		FeeRewardsVault::add_validator_reward(
+			DappAddress::get(),
+			TokenAddress::get(),
+			U256::zero(),
+		)
+		.unwrap();
+		assert_eq!(
+			FeeRewardsVault::has_accrued_validator_rewards(DappAddress::get()),
+			false
+		);
+
+		let amount = U256::from(100);
+		FeeRewardsVault::add_validator_reward(DappAddress::get(), TokenAddress::get(), amount)
+			.unwrap();
+		assert_eq!(
+			FeeRewardsVault::has_accrued_validator_rewards(DappAddress::get()),
+			true
+		);
+		assert_eq!(
+			FeeRewardsVault::get_claimable_reward(DappAddress::get(), TokenAddress::get()),
+			amount
+		);
+
+		FeeRewardsVault::add_claimable_reward(DappAddress2::get(), TokenAddress::get(), amount)
+			.unwrap();
+		assert_eq!(
+			FeeRewardsVault::has_accrued_validator_rewards(DappAddress2::get()),
+			false
+		);
+	});
+}
+
+#[test]
+fn migration_to_v2_should_record_the_validators_holding_rewards() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<FeeRewardsVault>();
+
+		let amount = U256::from(100);
+		FeeRewardsVault::add_claimable_reward(DappAddress::get(), TokenAddress::get(), amount)
+			.unwrap();
+		FeeRewardsVault::add_claimable_reward(ExcludedHolder::get(), TokenAddress::get(), amount)
+			.unwrap();
+		FeeRewardsVault::add_claimable_reward(DappAddress2::get(), TokenAddress::get(), amount)
+			.unwrap();
+		FeeRewardsVault::sub_claimable_reward(DappAddress2::get(), TokenAddress::get(), amount)
+			.unwrap();
+
+		// Every holder is a validator but `ExcludedHolder`.
+		migrations::v2::MigrateToV2::<Test, PayoutFilter>::on_runtime_upgrade();
+
+		assert_eq!(
+			FeeRewardsVault::has_accrued_validator_rewards(DappAddress::get()),
+			true
+		);
+		assert_eq!(
+			FeeRewardsVault::has_accrued_validator_rewards(ExcludedHolder::get()),
+			false
+		);
+		assert_eq!(
+			FeeRewardsVault::has_accrued_validator_rewards(DappAddress2::get()),
+			false
+		);
+		assert_eq!(
+			FeeRewardsVault::on_chain_storage_version(),
+			StorageVersion::new(2)
+		);
+	});
+}
+
+#[test]
+fn set_frozen_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(FeeRewardsVault::is_frozen(DappAddress::get()), false);
+		FeeRewardsVault::set_frozen(DappAddress::get(), true);
+		assert_eq!(FeeRewardsVault::is_frozen(DappAddress::get()), true);
+		assert_eq!(FeeRewardsVault::is_frozen(DappAddress2::get()), false);
+		FeeRewardsVault::set_frozen(DappAddress::get(), false);
+		assert_eq!(FeeRewardsVault::is_frozen(DappAddress::get()), false);
+	});
+}
+
+#[test]
+fn get_claimable_rewards_should_list_non_zero_rewards() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			FeeRewardsVault::get_claimable_rewards(DappAddress::get()),
+			vec![]
+		);
+
+		FeeRewardsVault::add_claimable_reward(
+			DappAddress::get(),
+			TokenAddress::get(),
+			U256::from(100),
+		)
+		.unwrap();
+		FeeRewardsVault::add_claimable_reward(
+			DappAddress::get(),
+			TokenAddress2::get(),
+			U256::from(50),
+		)
+		.unwrap();
+		FeeRewardsVault::add_claimable_reward(
+			DappAddress2::get(),
+			TokenAddress::get(),
+			U256::from(10),
+		)
+		.unwrap();
+		FeeRewardsVault::sub_claimable_reward(
+			DappAddress::get(),
+			TokenAddress2::get(),
+			U256::from(50),
+		)
+		.unwrap();
+
+		assert_eq!(
+			FeeRewardsVault::get_claimable_rewards(DappAddress::get()),
+			vec![(TokenAddress::get(), U256::from(100))]
+		);
+		assert_eq!(
+			FeeRewardsVault::get_claimable_rewards(DappAddress2::get()),
+			vec![(TokenAddress::get(), U256::from(10))]
+		);
+	});
+}
+
+#[test]
+fn set_payout_address_and_claim_delegate_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			FeeRewardsVault::get_payout_address(DappAddress::get()),
+			None
+		);
+		assert_eq!(
+			FeeRewardsVault::get_claim_delegate(DappAddress::get()),
+			None
+		);
+
+		FeeRewardsVault::set_payout_address(DappAddress::get(), Some(DappAddress2::get()));
+		FeeRewardsVault::set_claim_delegate(DappAddress::get(), Some(TokenAddress::get()));
+		assert_eq!(
+			FeeRewardsVault::get_payout_address(DappAddress::get()),
+			Some(DappAddress2::get())
+		);
+		assert_eq!(
+			FeeRewardsVault::get_claim_delegate(DappAddress::get()),
+			Some(TokenAddress::get())
+		);
+
+		FeeRewardsVault::set_payout_address(DappAddress::get(), None);
+		FeeRewardsVault::set_claim_delegate(DappAddress::get(), None);
+		assert_eq!(
+			FeeRewardsVault::get_payout_address(DappAddress::get()),
+			None
+		);
+		assert_eq!(
+			FeeRewardsVault::get_claim_delegate(DappAddress::get()),
+			None
+		);
+	});
+}
+
+fn erc20_transfers() -> Vec<(H160, H160, H160, U256)> {
+	ERC20_TRANSFERS.with(|transfers| transfers.borrow().clone())
+}
+
+#[test]
+fn auto_payout_should_pay_rewards_above_minimum_to_payout_address() {
+	new_test_ext().execute_with(|| {
+		let payout_address = H160::from_low_u64_be(0xbeef);
+
+		FeeRewardsVault::set_auto_payout(DappAddress::get(), Some(U256::from(50)));
+		FeeRewardsVault::set_payout_address(DappAddress::get(), Some(payout_address));
+		FeeRewardsVault::add_claimable_reward(
+			DappAddress::get(),
+			TokenAddress::get(),
+			U256::from(100),
+		)
+		.unwrap();
+		FeeRewardsVault::add_claimable_reward(
+			DappAddress::get(),
+			TokenAddress2::get(),
+			U256::from(10),
+		)
+		.unwrap();
+		FeeRewardsVault::add_claimable_reward(
+			DappAddress2::get(),
+			TokenAddress::get(),
+			U256::from(100),
+		)
+		.unwrap();
+
+		FeeRewardsVault::on_initialize(9);
+		assert_eq!(erc20_transfers(), vec![]);
+
+		FeeRewardsVault::on_initialize(10);
+
+		assert_eq!(
+			erc20_transfers(),
+			vec![(
+				TokenAddress::get(),
+				VaultAddress::get(),
+				payout_address,
+				U256::from(100)
+			)]
+		);
+		System::assert_has_event(RuntimeEvent::FeeRewardsVault(crate::Event::RewardPaidOut(
+			DappAddress::get(),
+			payout_address,
+			TokenAddress::get(),
+			U256::from(100),
+		)));
+		assert_eq!(
+			FeeRewardsVault::get_claimable_rewards(DappAddress::get()),
+			vec![(TokenAddress2::get(), U256::from(10))]
+		);
+		assert_eq!(
+			FeeRewardsVault::get_claimable_reward(DappAddress2::get(), TokenAddress::get()),
+			U256::from(100)
+		);
+	});
+}
+
+#[test]
+fn auto_payout_should_resume_from_cursor() {
+	new_test_ext().execute_with(|| {
+		let holders: Vec<H160> = (1..=3).map(H160::from_low_u64_be).collect();
+		for holder in &holders {
+			FeeRewardsVault::set_auto_payout(*holder, Some(U256::zero()));
+			FeeRewardsVault::add_claimable_reward(*holder, TokenAddress::get(), U256::from(1))
+				.unwrap();
+		}
+
+		FeeRewardsVault::on_initialize(10);
+		assert_eq!(erc20_transfers().len(), 2);
+
+		FeeRewardsVault::on_initialize(11);
+		assert_eq!(erc20_transfers().len(), 3);
+
+		let mut paid: Vec<H160> = erc20_transfers()
+			.into_iter()
+			.map(|(_, _, to, _)| to)
+			.collect();
+		paid.sort();
+		assert_eq!(paid, holders);
+
+		FeeRewardsVault::add_claimable_reward(holders[0], TokenAddress::get(), U256::from(1))
+			.unwrap();
+		FeeRewardsVault::on_initialize(12);
+		assert_eq!(erc20_transfers().len(), 3);
+	});
+}
+
+#[test]
+fn auto_payout_should_skip_frozen_and_filtered_holders() {
+	new_test_ext().execute_with(|| {
+		for holder in [DappAddress::get(), ExcludedHolder::get()] {
+			FeeRewardsVault::set_auto_payout(holder, Some(U256::zero()));
+			FeeRewardsVault::add_claimable_reward(holder, TokenAddress::get(), U256::from(100))
+				.unwrap();
+		}
+		FeeRewardsVault::set_frozen(DappAddress::get(), true);
+
+		FeeRewardsVault::on_initialize(10);
+
+		assert_eq!(erc20_transfers(), vec![]);
+		assert_eq!(
+			FeeRewardsVault::get_claimable_reward(DappAddress::get(), TokenAddress::get()),
+			U256::from(100)
+		);
+		assert_eq!(
+			FeeRewardsVault::get_claimable_reward(ExcludedHolder::get(), TokenAddress::get()),
+			U256::from(100)
+		);
+	});
+}
+
+#[test]
+fn auto_payout_failure_should_keep_the_reward_claimable() {
+	new_test_ext().execute_with(|| {
+		ERC20_FAILS.with(|fails| *fails.borrow_mut() = true);
+
+		FeeRewardsVault::set_auto_payout(DappAddress::get(), Some(U256::zero()));
+		FeeRewardsVault::add_claimable_reward(
+			DappAddress::get(),
+			TokenAddress::get(),
+			U256::from(100),
+		)
+		.unwrap();
+
+		FeeRewardsVault::on_initialize(10);
+
+		assert_eq!(
+			FeeRewardsVault::get_claimable_reward(DappAddress::get(), TokenAddress::get()),
+			U256::from(100)
+		);
+		System::assert_has_event(RuntimeEvent::FeeRewardsVault(
+			crate::Event::RewardPayoutFailed(
+				DappAddress::get(),
+				TokenAddress::get(),
+				U256::from(100),
+			),
+		));
+	});
+}
@@ -93,7 +93,10 @@ pub mod pallet {
 
 					let (_, gas_price) = Self::get_transaction_gas_info(&transaction);
 
-					let (transaction_fee_token, _) = Self::get_fee_token_info(&from);
+					let (transaction_fee_token, _) =
+						Self::get_fee_token_info(&from).map_err(|_| {
+							TransactionValidityError::Invalid(InvalidTransaction::Payment)
+						})?;
 
 					let calculated_max_gas = match gas_price.checked_mul(transaction_data.gas_limit)
 					{
@@ -164,7 +167,8 @@ pub mod pallet {
 
 			let (gas_limit, gas_price) = Self::get_transaction_gas_info(&transaction);
 
-			let (transaction_fee_token, conversion_rate) = Self::get_fee_token_info(&from);
+			let (transaction_fee_token, conversion_rate) = Self::get_fee_token_info(&from)
+				.map_err(|_| DispatchError::Other("Conversion rate unavailable"))?;
 
 			let calculated_gas_usage = match gas_limit.checked_mul(gas_price.into()) {
 				Some(a) => a,
@@ -301,7 +305,7 @@ pub mod pallet {
 			Ok(())
 		}
 
-		fn get_fee_token_info(from: &H160) -> (H160, (U256, U256)) {
+		fn get_fee_token_info(from: &H160) -> Result<(H160, (U256, U256)), ()> {
 			let transaction_fee_token =
 				T::DNTFeeController::get_transaction_fee_token(from.clone());
 			let validator = <pallet_evm::Pallet<T>>::find_author();
@@ -309,9 +313,10 @@ pub mod pallet {
 				from.clone(),
 				validator,
 				transaction_fee_token,
-			);
+			)
+			.map_err(|_| ())?;
 
-			(transaction_fee_token, validator_conversion_rate)
+			Ok((transaction_fee_token, validator_conversion_rate))
 		}
 
 		fn get_transaction_gas_info(transaction: &pallet_ethereum::Transaction) -> (U256, U256) {
@@ -372,10 +377,7 @@ pub mod pallet {
 				.saturating_mul(conversion_rate.0)
 				.div_mod(conversion_rate.1)
 				.0;
-			T::ERC20Manager::withdraw_amount(token.clone(), payer.clone(), actual_amount)
-				.map_err(|_| {})?;
-
-			T::ERC20Manager::deposit_amount(token.clone(), payee.clone(), actual_amount)
+			T::ERC20Manager::transfer(token.clone(), payer.clone(), payee.clone(), actual_amount)
 				.map_err(|_| {})?;
 
 			Ok(())
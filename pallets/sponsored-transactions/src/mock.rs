@@ -144,8 +144,12 @@ impl runner::OnChargeDecentralizedNativeTokenFee for MockDNTFeeController {
 		_user: H160,
 		_validator: H160,
 		_token: H160,
-	) -> (U256, U256) {
-		(1.into(), 1.into())
+	) -> Result<(U256, U256), Self::Error> {
+		Ok((1.into(), 1.into()))
+	}
+
+	fn get_transaction_conversion_rate_gas(_user: H160, _validator: H160, _token: H160) -> u64 {
+		Default::default()
 	}
 
 	fn get_fee_vault() -> H160 {
@@ -177,8 +181,9 @@ impl runner::OnChargeDecentralizedNativeTokenFee for MockDNTFeeController {
 		_actual_amount: U256,
 		_validator: H160,
 		_to: Option<H160>,
-	) -> Result<(U256, U256), Self::Error> {
-		Ok((Default::default(), Default::default()))
+		_consumers: &[(H160, u64)],
+	) -> Result<(U256, U256, U256), Self::Error> {
+		Ok((Default::default(), Default::default(), Default::default()))
 	}
 }
 
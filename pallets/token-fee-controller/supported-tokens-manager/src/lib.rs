@@ -20,10 +20,136 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use pallet::*;
-use sp_core::{H160, H256};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::{RuntimeDebug, H160, H256, U256};
+use sp_std::collections::btree_map::BTreeMap;
 use sp_std::vec;
 use sp_std::vec::Vec;
 
+/// Maximum number of mapping keys besides the holder in a balance layout.
+pub const MAX_BALANCE_LAYOUT_KEYS: usize = 4;
+
+/// Where a token keeps the balance of a holder in its contract storage.
+///
+/// The balance of `holder` is found by looking up, starting at `slot`, every key of
+/// `outer_keys`, then `holder`, then every key of `inner_keys`, as Solidity does for nested
+/// mappings. The balance is in the `mask` bits of the word `offset` slots after that entry.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct BalanceLayout {
+	/// Slot of the outermost mapping. For ERC-7201 namespaced storage, the root of the
+	/// namespace plus the position of the mapping in the namespace struct.
+	pub slot: H256,
+	/// Keys looked up before the holder, outermost first.
+	pub outer_keys: Vec<H256>,
+	/// Keys looked up after the holder, outermost first.
+	pub inner_keys: Vec<H256>,
+	/// Position of the balance in the struct stored for the holder, in slots.
+	pub offset: u64,
+	/// Contiguous bits of the word the balance is packed in.
+	pub mask: U256,
+}
+
+impl BalanceLayout {
+	/// The layout of a `mapping(address => uint256)` at `slot`.
+	pub fn mapping(slot: H256) -> Self {
+		Self {
+			slot,
+			outer_keys: Vec::new(),
+			inner_keys: Vec::new(),
+			offset: 0,
+			mask: U256::MAX,
+		}
+	}
+
+	/// Whether the mask is a single run of bits and the keys are within the limit.
+	pub fn is_valid(&self) -> bool {
+		if self.mask.is_zero()
+			|| self.outer_keys.len() + self.inner_keys.len() > MAX_BALANCE_LAYOUT_KEYS
+		{
+			return false;
+		}
+
+		let bits = self.mask >> self.mask.trailing_zeros();
+		(bits & bits.overflowing_add(U256::one()).0).is_zero()
+	}
+}
+
+/// View functions of a token telling whether its balances can be moved. Each one returns a
+/// `bool` and is given by its selector.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ComplianceHook {
+	/// `(address)` view true for the accounts that can neither send nor receive the token, like
+	/// `isBlacklisted(address)`.
+	pub blacklist: Option<[u8; 4]>,
+	/// `()` view true while the token cannot be moved at all, like `paused()`.
+	pub pause: Option<[u8; 4]>,
+}
+
+/// Gas limit of each view function call a `TokenViewCaller` makes.
+pub const TOKEN_VIEW_GAS_LIMIT: u64 = 100_000;
+
+/// Calls view functions of tokens.
+pub trait TokenViewCaller {
+	/// Output of calling `token` with `input`, or `None` if the call failed, and the gas the call
+	/// used. Changes made by the call are discarded.
+	fn call_view(token: H160, input: Vec<u8>) -> (Option<Vec<u8>>, u64);
+}
+
+impl TokenViewCaller for () {
+	fn call_view(_token: H160, _input: Vec<u8>) -> (Option<Vec<u8>>, u64) {
+		(None, 0)
+	}
+}
+
+/// Results of the compliance hook views called while the cache is in use, so that the fee
+/// movements of a transaction call each view once.
+#[derive(Default)]
+pub struct ComplianceCache {
+	views: BTreeMap<(H160, Vec<u8>), bool>,
+	used_gas: u64,
+}
+
+environmental::environmental!(compliance_cache: ComplianceCache);
+
+impl ComplianceCache {
+	/// Runs `f` with the cache in use.
+	pub fn using<R>(&mut self, f: impl FnOnce() -> R) -> R {
+		compliance_cache::using(self, f)
+	}
+
+	/// Gas used by the views called while the cache was in use.
+	pub fn used_gas(&self) -> u64 {
+		self.used_gas
+	}
+}
+
+/// Gas limit of each `balanceOf` call a `BalanceLayoutVerifier` makes to probe a token.
+pub const BALANCE_PROBE_GAS_LIMIT: u64 = 100_000;
+
+/// Checks a balance layout against the code of the token before it is accepted.
+///
+/// A verification calls `balanceOf` at most twice, and a discovery at most three times, as it
+/// only verifies the first slot it finds.
+pub trait BalanceLayoutVerifier {
+	/// Slot of the `mapping(address => uint256)` that `balanceOf` of `token` reads, if any. Only
+	/// the lowest slot whose entry for the holder is read is verified.
+	fn discover_balance_slot(token: H160) -> Option<H256>;
+
+	/// Whether a balance written through `layout` is the one `balanceOf` of `token` returns.
+	fn verify_balance_layout(token: H160, layout: &BalanceLayout) -> bool;
+}
+
+impl BalanceLayoutVerifier for () {
+	fn discover_balance_slot(_token: H160) -> Option<H256> {
+		None
+	}
+
+	fn verify_balance_layout(_token: H160, _layout: &BalanceLayout) -> bool {
+		true
+	}
+}
+
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -50,10 +176,16 @@ pub mod pallet {
 		DefaultTokenCannotBeRemoved,
 		AlreadySupportedToken,
 		TokenNotSupported,
+		InvalidBalanceLayout,
+		UnverifiedBalanceLayout,
+		InvalidComplianceHook,
 	}
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config {}
+	pub trait Config: frame_system::Config {
+		type BalanceLayoutVerifier: BalanceLayoutVerifier;
+		type TokenViewCaller: TokenViewCaller;
+	}
 
 	#[pallet::storage]
 	pub type SupportedTokens<T: Config> = StorageValue<_, Vec<H160>, ValueQuery>;
@@ -61,6 +193,16 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type TokenBalanceSlot<T: Config> = StorageMap<_, Blake2_128Concat, H160, H256, OptionQuery>;
 
+	// Layouts of the tokens that do not keep balances in a plain `mapping(address => uint256)`
+	#[pallet::storage]
+	pub type TokenBalanceLayout<T: Config> =
+		StorageMap<_, Blake2_128Concat, H160, BalanceLayout, OptionQuery>;
+
+	// Views consulted before moving the balances of the tokens that have them
+	#[pallet::storage]
+	pub type TokenComplianceHook<T: Config> =
+		StorageMap<_, Blake2_128Concat, H160, ComplianceHook, OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn default_token)]
 	pub type DefaultTokenStorage<T: Config> = StorageValue<_, H160, OptionQuery>;
@@ -76,13 +218,40 @@ pub mod pallet {
 			if Self::is_supported_token(token) {
 				return Err(Error::<T>::AlreadySupportedToken);
 			}
-			let mut tokens = SupportedTokens::<T>::get();
-			tokens.push(token);
-			TokenBalanceSlot::<T>::insert(token, slot);
-			SupportedTokens::<T>::put(tokens);
+			if !T::BalanceLayoutVerifier::verify_balance_layout(
+				token,
+				&BalanceLayout::mapping(slot),
+			) {
+				return Err(Error::<T>::UnverifiedBalanceLayout);
+			}
+			Self::insert_supported_token(token, slot);
+			Ok(())
+		}
+
+		fn add_supported_token_with_layout(
+			token: H160,
+			layout: BalanceLayout,
+		) -> Result<(), Self::Error> {
+			if !layout.is_valid() {
+				return Err(Error::<T>::InvalidBalanceLayout);
+			}
+			if Self::is_supported_token(token) {
+				return Err(Error::<T>::AlreadySupportedToken);
+			}
+			if !T::BalanceLayoutVerifier::verify_balance_layout(token, &layout) {
+				return Err(Error::<T>::UnverifiedBalanceLayout);
+			}
+			Self::insert_supported_token(token, layout.slot);
+			if layout != BalanceLayout::mapping(layout.slot) {
+				TokenBalanceLayout::<T>::insert(token, layout);
+			}
 			Ok(())
 		}
 
+		fn discover_token_balance_slot(token: H160) -> Option<H256> {
+			T::BalanceLayoutVerifier::discover_balance_slot(token)
+		}
+
 		fn is_supported_token(token: H160) -> bool {
 			let tokens = SupportedTokens::<T>::get();
 			tokens.contains(&token)
@@ -98,6 +267,8 @@ pub mod pallet {
 					let mut tokens = SupportedTokens::<T>::get();
 					tokens.retain(|t| token.ne(t));
 					TokenBalanceSlot::<T>::remove(token);
+					TokenBalanceLayout::<T>::remove(token);
+					TokenComplianceHook::<T>::remove(token);
 					SupportedTokens::<T>::put(tokens);
 					Ok(())
 				}
@@ -108,6 +279,57 @@ pub mod pallet {
 			TokenBalanceSlot::<T>::get(token)
 		}
 
+		fn get_token_balance_layout(token: H160) -> Option<BalanceLayout> {
+			TokenBalanceLayout::<T>::get(token)
+				.or_else(|| Self::get_token_balance_slot(token).map(BalanceLayout::mapping))
+		}
+
+		fn get_token_compliance_hook(token: H160) -> Option<ComplianceHook> {
+			TokenComplianceHook::<T>::get(token)
+		}
+
+		fn set_token_compliance_hook(
+			token: H160,
+			hook: Option<ComplianceHook>,
+		) -> Result<(), Self::Error> {
+			if !Self::is_supported_token(token) {
+				return Err(Error::<T>::TokenNotSupported);
+			}
+			match hook {
+				Some(hook) if hook.blacklist.is_none() && hook.pause.is_none() => {
+					Err(Error::<T>::InvalidComplianceHook)
+				}
+				Some(hook) => {
+					TokenComplianceHook::<T>::insert(token, hook);
+					Ok(())
+				}
+				None => {
+					TokenComplianceHook::<T>::remove(token);
+					Ok(())
+				}
+			}
+		}
+
+		fn is_compliant(token: H160, accounts: &[H160]) -> bool {
+			let Some(hook) = TokenComplianceHook::<T>::get(token) else {
+				return true;
+			};
+
+			let paused = hook.pause.map_or(false, |pause| {
+				!Self::view_returns_false(token, pause.to_vec())
+			});
+			if paused {
+				return false;
+			}
+
+			hook.blacklist.map_or(true, |blacklist| {
+				accounts.iter().all(|account| {
+					let input = [&blacklist[..], H256::from(*account).as_bytes()].concat();
+					Self::view_returns_false(token, input)
+				})
+			})
+		}
+
 		fn get_default_token() -> H160 {
 			DefaultTokenStorage::<T>::get().unwrap_or(H160::zero())
 		}
@@ -122,6 +344,39 @@ pub mod pallet {
 		}
 	}
 
+	impl<T: Config> Pallet<T> {
+		// Whether the `bool` view of `token` called with `input` returned false. A failed call
+		// counts as true, so that a broken hook stops the transfers instead of letting them
+		// through.
+		fn view_returns_false(token: H160, input: Vec<u8>) -> bool {
+			let key = (token, input);
+			if let Some(Some(result)) =
+				compliance_cache::with(|cache| cache.views.get(&key).copied())
+			{
+				return result;
+			}
+
+			let (output, used_gas) = T::TokenViewCaller::call_view(token, key.1.clone());
+			let result = output.map_or(false, |output| {
+				output.len() == 32 && output.iter().all(|b| *b == 0)
+			});
+
+			compliance_cache::with(|cache| {
+				cache.used_gas = cache.used_gas.saturating_add(used_gas);
+				cache.views.insert(key, result);
+			});
+
+			result
+		}
+
+		fn insert_supported_token(token: H160, slot: H256) {
+			let mut tokens = SupportedTokens::<T>::get();
+			tokens.push(token);
+			TokenBalanceSlot::<T>::insert(token, slot);
+			SupportedTokens::<T>::put(tokens);
+		}
+	}
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T> {
 		pub initial_default_token: H160,
@@ -160,12 +415,56 @@ pub trait SupportedTokensManager {
 
 	fn add_supported_token(token: H160, slot: H256) -> Result<(), Self::Error>;
 
+	fn add_supported_token_with_layout(
+		token: H160,
+		layout: BalanceLayout,
+	) -> Result<(), Self::Error>;
+
+	/// Finds the slot of the balances mapping of a token that is not supported yet.
+	fn discover_token_balance_slot(_token: H160) -> Option<H256> {
+		None
+	}
+
 	fn is_supported_token(token: H160) -> bool;
 
 	fn remove_supported_token(token: H160) -> Result<(), Self::Error>;
 
+	/// Slot of the balances mapping of the token, or of its outermost mapping if the balances
+	/// are nested in other mappings.
 	fn get_token_balance_slot(token: H160) -> Option<H256>;
 
+	fn get_token_balance_layout(token: H160) -> Option<BalanceLayout> {
+		Self::get_token_balance_slot(token).map(BalanceLayout::mapping)
+	}
+
+	/// Views consulted before moving the balances of the token, if any.
+	fn get_token_compliance_hook(_token: H160) -> Option<ComplianceHook> {
+		None
+	}
+
+	/// Sets the compliance hook of a supported token, or removes it with `None`.
+	fn set_token_compliance_hook(
+		token: H160,
+		hook: Option<ComplianceHook>,
+	) -> Result<(), Self::Error>;
+
+	/// Whether the token is not paused and none of `accounts` is blacklisted by it.
+	fn is_compliant(_token: H160, _accounts: &[H160]) -> bool {
+		true
+	}
+
+	/// Most views `is_compliant` calls for the token and that many accounts.
+	fn compliance_views(token: H160, accounts: u32) -> u32 {
+		match Self::get_token_compliance_hook(token) {
+			Some(ComplianceHook {
+				blacklist: Some(_),
+				pause,
+			}) => accounts.saturating_add(pause.is_some().into()),
+			Some(ComplianceHook { pause, .. }) => pause.is_some().into(),
+			None => 0,
+		}
+	}
+
 	fn get_default_token() -> H160;
 
 	fn set_default_token(token: H160) -> Result<(), Self::Error>;
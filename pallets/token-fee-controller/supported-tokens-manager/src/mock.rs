@@ -21,7 +21,7 @@
 
 use super::*;
 
-use std::str::FromStr;
+use std::{cell::RefCell, str::FromStr};
 
 use frame_support::{construct_runtime, parameter_types, traits::Everything};
 use sp_core::{H160, H256};
@@ -107,9 +107,60 @@ parameter_types! {
 	pub MockDefaultFeeToken: H160 = H160::from_str("0x261FB2d971eFBBFd027A9C9Cebb8548Cf7d0d2d5").expect("invalid address");
 	pub MeaninglessTokenAddress:H160 = H160::from_str("0xdAC17F958D2ee523a2206206994597C13D831ec7").expect("invalid address");
 	pub MockDefaultTokenBalanceSlot:H256 = H256::from_low_u64_be(0);
+	pub MeaninglessTokenBalanceSlot:H256 = H256::from_low_u64_be(2);
+	pub UnverifiableBalanceSlot:H256 = H256::from_low_u64_be(7);
 }
 
-impl crate::Config for Runtime {}
+/// Finds `MeaninglessTokenBalanceSlot` for every token and accepts every layout but the ones
+/// at `UnverifiableBalanceSlot`.
+pub struct MockBalanceLayoutVerifier;
+
+impl BalanceLayoutVerifier for MockBalanceLayoutVerifier {
+	fn discover_balance_slot(_token: H160) -> Option<H256> {
+		Some(MeaninglessTokenBalanceSlot::get())
+	}
+
+	fn verify_balance_layout(_token: H160, layout: &BalanceLayout) -> bool {
+		layout.slot != UnverifiableBalanceSlot::get()
+	}
+}
+
+parameter_types! {
+	pub BlacklistedAccount: H160 = H160::from_low_u64_be(0xb1);
+	pub const PausedSelector: [u8; 4] = [0x5c, 0x97, 0x5a, 0xbb];
+	pub const IsBlacklistedSelector: [u8; 4] = [0xfe, 0x57, 0x5a, 0x87];
+}
+
+thread_local! {
+	pub static PAUSED: RefCell<bool> = RefCell::new(false);
+}
+
+/// Answers `paused()` with `PAUSED` and `isBlacklisted(address)` with whether the account is
+/// `BlacklistedAccount`, using `VIEW_GAS` gas. Every other call fails.
+pub struct MockTokenViewCaller;
+
+pub const VIEW_GAS: u64 = 1_000;
+
+impl TokenViewCaller for MockTokenViewCaller {
+	fn call_view(_token: H160, input: Vec<u8>) -> (Option<Vec<u8>>, u64) {
+		let result = match input.split_at(4) {
+			(selector, []) if selector == PausedSelector::get() => PAUSED.with(|v| *v.borrow()),
+			(selector, account) if selector == IsBlacklistedSelector::get() => {
+				account == H256::from(BlacklistedAccount::get()).as_bytes()
+			}
+			_ => return (None, VIEW_GAS),
+		};
+		(
+			Some(H256::from_low_u64_be(result as u64).as_bytes().to_vec()),
+			VIEW_GAS,
+		)
+	}
+}
+
+impl crate::Config for Runtime {
+	type BalanceLayoutVerifier = MockBalanceLayoutVerifier;
+	type TokenViewCaller = MockTokenViewCaller;
+}
 
 // Configure a mock runtime to test the pallet.
 construct_runtime!(
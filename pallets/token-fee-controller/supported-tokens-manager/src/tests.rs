@@ -18,13 +18,15 @@
 // information.
 
 use crate::mock::{
-	ExtBuilder, MeaninglessTokenAddress, MockDefaultFeeToken, SupportedTokensManager,
+	BlacklistedAccount, ExtBuilder, IsBlacklistedSelector, MeaninglessTokenAddress,
+	MeaninglessTokenBalanceSlot, MockDefaultFeeToken, PausedSelector, SupportedTokensManager,
+	UnverifiableBalanceSlot, PAUSED, VIEW_GAS,
 };
 
 use super::*;
 
 use frame_support::parameter_types;
-use sp_core::H160;
+use sp_core::{H160, U256};
 
 parameter_types! {
 	pub MeaninglessAccount: H160 = H160::from_low_u64_le(1);
@@ -155,3 +157,310 @@ fn fail_to_remove_not_present_token() {
 		);
 	});
 }
+
+#[test]
+fn add_token_with_layout() {
+	ExtBuilder::default().build().execute_with(|| {
+		let layout = BalanceLayout {
+			slot: H256::from_low_u64_be(2),
+			outer_keys: vec![H256::from_low_u64_be(1)],
+			inner_keys: vec![],
+			offset: 1,
+			mask: U256::from(u128::MAX),
+		};
+
+		assert!(
+			<SupportedTokensManager as crate::SupportedTokensManager>::add_supported_token_with_layout(
+				MeaninglessTokenAddress::get(),
+				layout.clone(),
+			)
+			.is_ok()
+		);
+		assert_eq!(
+			<SupportedTokensManager as crate::SupportedTokensManager>::get_token_balance_layout(
+				MeaninglessTokenAddress::get()
+			),
+			Some(layout)
+		);
+		assert_eq!(
+			<SupportedTokensManager as crate::SupportedTokensManager>::get_token_balance_slot(
+				MeaninglessTokenAddress::get()
+			),
+			Some(H256::from_low_u64_be(2))
+		);
+
+		assert!(
+			<SupportedTokensManager as crate::SupportedTokensManager>::remove_supported_token(
+				MeaninglessTokenAddress::get()
+			)
+			.is_ok()
+		);
+		assert_eq!(
+			<SupportedTokensManager as crate::SupportedTokensManager>::get_token_balance_layout(
+				MeaninglessTokenAddress::get()
+			),
+			None
+		);
+	});
+}
+
+#[test]
+fn plain_mapping_layout_for_tokens_added_with_slot() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert!(
+			<SupportedTokensManager as crate::SupportedTokensManager>::add_supported_token(
+				MeaninglessTokenAddress::get(),
+				H256::from_low_u64_be(2),
+			)
+			.is_ok()
+		);
+		assert_eq!(
+			<SupportedTokensManager as crate::SupportedTokensManager>::get_token_balance_layout(
+				MeaninglessTokenAddress::get()
+			),
+			Some(BalanceLayout::mapping(H256::from_low_u64_be(2)))
+		);
+	});
+}
+
+#[test]
+fn fail_to_add_token_with_invalid_layout() {
+	ExtBuilder::default().build().execute_with(|| {
+		let mapping = BalanceLayout::mapping(H256::from_low_u64_be(2));
+		let invalid_layouts = vec![
+			BalanceLayout {
+				mask: U256::zero(),
+				..mapping.clone()
+			},
+			BalanceLayout {
+				mask: U256::from(0b101),
+				..mapping.clone()
+			},
+			BalanceLayout {
+				outer_keys: vec![H256::zero(); MAX_BALANCE_LAYOUT_KEYS],
+				inner_keys: vec![H256::zero()],
+				..mapping.clone()
+			},
+		];
+
+		for layout in invalid_layouts {
+			assert!(
+				<SupportedTokensManager as crate::SupportedTokensManager>::add_supported_token_with_layout(
+					MeaninglessTokenAddress::get(),
+					layout,
+				)
+				.is_err()
+			);
+		}
+		assert!(
+			!<SupportedTokensManager as crate::SupportedTokensManager>::is_supported_token(
+				MeaninglessTokenAddress::get()
+			)
+		);
+	});
+}
+
+#[test]
+fn fail_to_add_token_with_unverified_slot() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert!(matches!(
+			<SupportedTokensManager as crate::SupportedTokensManager>::add_supported_token(
+				MeaninglessTokenAddress::get(),
+				UnverifiableBalanceSlot::get(),
+			),
+			Err(Error::UnverifiedBalanceLayout)
+		));
+		assert!(matches!(
+			<SupportedTokensManager as crate::SupportedTokensManager>::add_supported_token_with_layout(
+				MeaninglessTokenAddress::get(),
+				BalanceLayout {
+					offset: 1,
+					..BalanceLayout::mapping(UnverifiableBalanceSlot::get())
+				},
+			),
+			Err(Error::UnverifiedBalanceLayout)
+		));
+		assert!(
+			!<SupportedTokensManager as crate::SupportedTokensManager>::is_supported_token(
+				MeaninglessTokenAddress::get()
+			)
+		);
+	});
+}
+
+#[test]
+fn discover_token_balance_slot() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			<SupportedTokensManager as crate::SupportedTokensManager>::discover_token_balance_slot(
+				MeaninglessTokenAddress::get()
+			),
+			Some(MeaninglessTokenBalanceSlot::get())
+		);
+	});
+}
+
+#[test]
+fn tokens_without_compliance_hook_are_compliant() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert!(
+			<SupportedTokensManager as crate::SupportedTokensManager>::is_compliant(
+				MockDefaultFeeToken::get(),
+				&[BlacklistedAccount::get()]
+			)
+		);
+	});
+}
+
+#[test]
+fn compliance_hook_checks_blacklist_and_pause() {
+	ExtBuilder::default().build().execute_with(|| {
+		let token = MockDefaultFeeToken::get();
+		let hook = ComplianceHook {
+			blacklist: Some(IsBlacklistedSelector::get()),
+			pause: Some(PausedSelector::get()),
+		};
+		<SupportedTokensManager as crate::SupportedTokensManager>::set_token_compliance_hook(
+			token,
+			Some(hook.clone()),
+		)
+		.expect("set hook failed");
+		assert_eq!(
+			<SupportedTokensManager as crate::SupportedTokensManager>::get_token_compliance_hook(
+				token
+			),
+			Some(hook)
+		);
+
+		let is_compliant = |accounts: &[H160]| {
+			<SupportedTokensManager as crate::SupportedTokensManager>::is_compliant(token, accounts)
+		};
+		assert!(is_compliant(&[MeaninglessAccount::get()]));
+		assert!(!is_compliant(&[
+			MeaninglessAccount::get(),
+			BlacklistedAccount::get()
+		]));
+
+		PAUSED.with(|paused| *paused.borrow_mut() = true);
+		assert!(!is_compliant(&[MeaninglessAccount::get()]));
+
+		<SupportedTokensManager as crate::SupportedTokensManager>::set_token_compliance_hook(
+			token, None,
+		)
+		.expect("remove hook failed");
+		assert!(is_compliant(&[BlacklistedAccount::get()]));
+	});
+}
+
+#[test]
+fn compliance_cache_calls_each_view_once() {
+	ExtBuilder::default().build().execute_with(|| {
+		let token = MockDefaultFeeToken::get();
+		<SupportedTokensManager as crate::SupportedTokensManager>::set_token_compliance_hook(
+			token,
+			Some(ComplianceHook {
+				blacklist: Some(IsBlacklistedSelector::get()),
+				pause: Some(PausedSelector::get()),
+			}),
+		)
+		.expect("set hook failed");
+		assert_eq!(
+			<SupportedTokensManager as crate::SupportedTokensManager>::compliance_views(token, 2),
+			3
+		);
+
+		let is_compliant = || {
+			<SupportedTokensManager as crate::SupportedTokensManager>::is_compliant(
+				token,
+				&[MeaninglessAccount::get()],
+			)
+		};
+
+		let mut cache = ComplianceCache::default();
+		assert!(cache.using(is_compliant));
+		assert_eq!(cache.used_gas(), 2 * VIEW_GAS);
+
+		// Cached results are kept while the cache is in use.
+		PAUSED.with(|paused| *paused.borrow_mut() = true);
+		assert!(cache.using(is_compliant));
+		assert_eq!(cache.used_gas(), 2 * VIEW_GAS);
+		assert!(!is_compliant());
+	});
+}
+
+#[test]
+fn failing_compliance_hook_is_not_compliant() {
+	ExtBuilder::default().build().execute_with(|| {
+		<SupportedTokensManager as crate::SupportedTokensManager>::set_token_compliance_hook(
+			MockDefaultFeeToken::get(),
+			Some(ComplianceHook {
+				blacklist: None,
+				pause: Some([0xde, 0xad, 0xbe, 0xef]),
+			}),
+		)
+		.expect("set hook failed");
+
+		assert!(
+			!<SupportedTokensManager as crate::SupportedTokensManager>::is_compliant(
+				MockDefaultFeeToken::get(),
+				&[MeaninglessAccount::get()]
+			)
+		);
+	});
+}
+
+#[test]
+fn fail_to_set_compliance_hook() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert!(matches!(
+			<SupportedTokensManager as crate::SupportedTokensManager>::set_token_compliance_hook(
+				MeaninglessTokenAddress::get(),
+				Some(ComplianceHook {
+					blacklist: Some(IsBlacklistedSelector::get()),
+					pause: None,
+				}),
+			),
+			Err(Error::TokenNotSupported)
+		));
+		assert!(matches!(
+			<SupportedTokensManager as crate::SupportedTokensManager>::set_token_compliance_hook(
+				MockDefaultFeeToken::get(),
+				Some(ComplianceHook {
+					blacklist: None,
+					pause: None,
+				}),
+			),
+			Err(Error::InvalidComplianceHook)
+		));
+	});
+}
+
+#[test]
+fn removed_token_loses_compliance_hook() {
+	ExtBuilder::default().build().execute_with(|| {
+		let token = MeaninglessTokenAddress::get();
+		<SupportedTokensManager as crate::SupportedTokensManager>::add_supported_token(
+			token,
+			MeaninglessTokenBalanceSlot::get(),
+		)
+		.expect("add token failed");
+		<SupportedTokensManager as crate::SupportedTokensManager>::set_token_compliance_hook(
+			token,
+			Some(ComplianceHook {
+				blacklist: None,
+				pause: Some(PausedSelector::get()),
+			}),
+		)
+		.expect("set hook failed");
+
+		<SupportedTokensManager as crate::SupportedTokensManager>::remove_supported_token(token)
+			.expect("remove token failed");
+
+		assert_eq!(
+			<SupportedTokensManager as crate::SupportedTokensManager>::get_token_compliance_hook(
+				token
+			),
+			None
+		);
+	});
+}
@@ -94,9 +94,7 @@ pub mod pallet {
 
 		fn transfer(from: H160, to: H160, value: U256) -> Result<(), Self::Error> {
 			let token = Self::get_user_fee_token(from);
-			T::ERC20Manager::withdraw_amount(token, from, value)
-				.map_err(|_| Error::<T>::UnsupportedToken)?;
-			T::ERC20Manager::deposit_amount(token, to, value)
+			T::ERC20Manager::transfer(token, from, to, value)
 				.map_err(|_| Error::<T>::UnsupportedToken)?;
 			Ok(())
 		}
@@ -142,6 +142,21 @@ impl pallet_supported_tokens_manager::SupportedTokensManager for MockSupportedTo
 		Ok(())
 	}
 
+	fn add_supported_token_with_layout(
+		_token: H160,
+		_layout: pallet_supported_tokens_manager::BalanceLayout,
+	) -> Result<(), Self::Error> {
+		MockStorageTokens::mutate(|x| x.push(_token));
+		Ok(())
+	}
+
+	fn set_token_compliance_hook(
+		_token: H160,
+		_hook: Option<pallet_supported_tokens_manager::ComplianceHook>,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+
 	fn remove_supported_token(_token: H160) -> Result<(), Self::Error> {
 		MockStorageTokens::mutate(|x| x.retain(|t| t != &_token));
 		Ok(())
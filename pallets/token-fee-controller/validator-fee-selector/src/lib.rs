@@ -19,6 +19,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use frame_support::pallet_prelude::{Decode, Encode, RuntimeDebug, TypeInfo};
 pub use pallet::*;
 use sp_core::{H160, U256};
 use sp_std::vec::Vec;
@@ -35,11 +36,12 @@ pub mod pallet {
 
 	use frame_support::{
 		pallet_prelude::*,
-		storage::types::{StorageDoubleMap, StorageMap, StorageValue},
+		storage::types::{StorageDoubleMap, StorageMap, StorageNMap, StorageValue},
 		Blake2_128Concat,
 	};
+	use frame_system::pallet_prelude::*;
 
-	use pallet_evm::Runner;
+	use pallet_evm::{GasWeightMapping, Runner};
 	use pallet_supported_tokens_manager::SupportedTokensManager;
 	use sp_core::{H160, H256, U256};
 
@@ -51,11 +53,26 @@ pub mod pallet {
 	pub trait Config: frame_system::Config + pallet_evm::Config {
 		type SupportedTokensManager: SupportedTokensManager;
 		type SimulatorRunner: pallet_evm::Runner<Self>;
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Gas available to a conversion rate controller unless its validator sets another limit.
+		#[pallet::constant]
+		type DefaultConversionRateGasLimit: Get<u64>;
+		/// Highest gas limit a validator can set for its conversion rate controller.
+		#[pallet::constant]
+		type MaxConversionRateGasLimit: Get<u64>;
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {}
 
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The conversion rate controller of a validator failed or returned malformed data and
+		/// the fallback of the validator was used. [validator, token, controller]
+		ConversionRateFallbackUsed(H160, H160, H160),
+	}
+
 	#[pallet::storage]
 	#[pallet::getter(fn validator_support_token)]
 	pub type ValidatorSupportFeeToken<T: Config> = StorageDoubleMap<
@@ -86,9 +103,53 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn validator_conversion_rate_gas_limit)]
+	pub type ConversionRateGasLimit<T: Config> =
+		StorageMap<_, Blake2_128Concat, H160, u64, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn validator_conversion_rate_fallback)]
+	pub type ConversionRateFallback<T: Config> =
+		StorageMap<_, Blake2_128Concat, H160, FallbackConversionRate, OptionQuery>;
+
+	/// Conversion rates already computed in the current block, by validator, token and
+	/// sender. `None` if the transactions in the token are rejected. Cleared at the end of every
+	/// block, the removal of each entry being paid by the lookup that wrote it.
+	#[pallet::storage]
+	#[pallet::getter(fn cached_conversion_rate)]
+	pub type ConversionRateCache<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, H160>, // validator
+			NMapKey<Blake2_128Concat, H160>, // token
+			NMapKey<Blake2_128Concat, H160>, // sender
+		),
+		Option<(U256, U256)>,
+		OptionQuery,
+	>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			// The entries cleared in `on_finalize` are paid by the lookups that wrote them.
+			T::DbWeight::get().writes(1)
+		}
+
+		fn on_finalize(_n: BlockNumberFor<T>) {
+			let _ = ConversionRateCache::<T>::clear(u32::MAX, None);
+		}
+	}
+
+	#[derive(RuntimeDebug, PartialEq, Eq)]
 	pub enum ValidatorFeeTokenError {
 		ControllerIsEOA,
+		NotConversionRateController,
+		MalformedConversionRate,
 		NotSupportedToken,
+		GasLimitTooHigh,
+		InvalidFallbackRate,
+		ConversionRateUnavailable,
 	}
 
 	impl<T: Config> ValidatorSupportedTokens for Pallet<T> {
@@ -126,6 +187,80 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		pub fn set_default_controller(controller: H160) {
 			DefaultController::<T>::put(controller);
+			let _ = ConversionRateCache::<T>::clear(u32::MAX, None);
+		}
+
+		fn call_conversion_rate_controller(
+			conversion_rate_controller: H160,
+			sender: H160,
+			validator: H160,
+			token: H160,
+		) -> Option<(U256, U256)> {
+			let args: sp_std::vec::Vec<H256> =
+				sp_std::vec![sender.into(), validator.into(), token.into()];
+
+			T::SimulatorRunner::call(
+				H160::from_low_u64_be(0),
+				conversion_rate_controller,
+				stbl_tools::eth::generate_calldata(
+					"getConversionRate(address,address,address)",
+					&args,
+				),
+				0.into(),
+				Self::conversion_rate_gas_limit(validator),
+				None,
+				None,
+				None,
+				Default::default(),
+				false,
+				false,
+				None,
+				None,
+				&pallet_evm::EvmConfig::shanghai(),
+			)
+			.ok()
+			.filter(|execution_info| execution_info.exit_reason.is_succeed())
+			.filter(|execution_info| execution_info.value.len() >= 64)
+			.map(|execution_info| {
+				(
+					U256::from_big_endian(&execution_info.value[0..32]),
+					U256::from_big_endian(&execution_info.value[32..64]),
+				)
+			})
+			.filter(|(numerator, denominator)| !numerator.is_zero() && !denominator.is_zero())
+		}
+
+		/// Checks that `controller` is a contract implementing `getConversionRate` and that a
+		/// call for the default token returns a well-formed conversion rate.
+		fn ensure_conversion_rate_controller(
+			validator: H160,
+			controller: H160,
+		) -> Result<(), ValidatorFeeTokenError> {
+			let code = pallet_evm::AccountCodes::<T>::get(controller);
+
+			if code.is_empty() {
+				return Err(ValidatorFeeTokenError::ControllerIsEOA);
+			}
+
+			if !stbl_tools::eth::code_implements_function(
+				code.as_slice(),
+				"getConversionRate(address,address,address)",
+			) {
+				return Err(ValidatorFeeTokenError::NotConversionRateController);
+			}
+
+			Self::call_conversion_rate_controller(
+				controller,
+				H160::zero(),
+				validator,
+				T::SupportedTokensManager::get_default_token(),
+			)
+			.map(|_| ())
+			.ok_or(ValidatorFeeTokenError::MalformedConversionRate)
+		}
+
+		fn clear_cached_conversion_rates(validator: H160) {
+			let _ = ConversionRateCache::<T>::clear_prefix(validator, u32::MAX, None);
 		}
 	}
 
@@ -147,6 +282,7 @@ pub mod pallet {
 				Err(ValidatorFeeTokenError::NotSupportedToken)
 			} else {
 				ValidatorSupportFeeToken::<T>::insert(validator, token, support);
+				let _ = ConversionRateCache::<T>::clear_prefix((validator, token), u32::MAX, None);
 
 				Ok(())
 			}
@@ -157,66 +293,107 @@ pub mod pallet {
 				.unwrap_or(DefaultController::<T>::get().unwrap())
 		}
 
-		fn conversion_rate(sender: H160, validator: H160, token: H160) -> (U256, U256) {
-			let conversion_rate_controller = Self::conversion_rate_controller(validator);
+		fn conversion_rate(
+			sender: H160,
+			validator: H160,
+			token: H160,
+		) -> Result<(U256, U256), Self::Error> {
+			let conversion_rate = match ConversionRateCache::<T>::get((validator, token, sender)) {
+				Some(conversion_rate) => conversion_rate,
+				None => {
+					let controller = Self::conversion_rate_controller(validator);
+					let conversion_rate =
+						Self::call_conversion_rate_controller(controller, sender, validator, token)
+							.or_else(|| {
+								Self::deposit_event(Event::ConversionRateFallbackUsed(
+									validator, token, controller,
+								));
+
+								match ConversionRateFallback::<T>::get(validator) {
+									Some(FallbackConversionRate::Rate(numerator, denominator)) => {
+										Some((numerator, denominator))
+									}
+									Some(FallbackConversionRate::Reject) => None,
+									None => Some((U256::from(1), U256::from(1))),
+								}
+							});
+					ConversionRateCache::<T>::insert((validator, token, sender), conversion_rate);
+
+					conversion_rate
+				}
+			};
 
-			let args: sp_std::vec::Vec<H256> =
-				sp_std::vec![sender.into(), validator.into(), token.into()];
+			conversion_rate.ok_or(ValidatorFeeTokenError::ConversionRateUnavailable)
+		}
 
-			T::SimulatorRunner::call(
-				H160::from_low_u64_be(0),
-				conversion_rate_controller,
-				stbl_tools::eth::generate_calldata(
-					"getConversionRate(address,address,address)",
-					&args,
-				),
-				0.into(),
-				3_000_000,
-				None,
-				None,
-				None,
-				Default::default(),
-				false,
-				false,
-				None,
-				None,
-				&pallet_evm::EvmConfig::shanghai(),
+		// A cached rate costs the read of the cache. Otherwise the controller is called, up to
+		// its gas limit, after reading its address, gas limit and fallback, and the entry is
+		// written to the cache and removed from it at the end of the block.
+		fn conversion_rate_gas(sender: H160, validator: H160, token: H160) -> u64 {
+			if ConversionRateCache::<T>::contains_key((validator, token, sender)) {
+				return <T as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+					T::DbWeight::get().reads(1),
+				);
+			}
+
+			<T as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+				T::DbWeight::get().reads_writes(5, 2),
 			)
-			.map(|execution_info| {
-				let value_len = execution_info.value.len();
-				if value_len >= 64 {
-					(
-						U256::from_big_endian(&execution_info.value[0..32]),
-						U256::from_big_endian(&execution_info.value[32..64]),
-					)
-				} else if value_len >= 32 {
-					(
-						U256::from_big_endian(&execution_info.value[0..32]),
-						U256::from(1),
-					)
-				} else {
-					(U256::from(1), U256::from(1))
-				}
-			})
-			.unwrap_or((U256::from(1), U256::from(1)))
+			.saturating_add(Self::conversion_rate_gas_limit(validator))
 		}
 
 		fn update_conversion_rate_controller(
 			validator: H160,
 			conversion_rate_controller: H160,
 		) -> Result<(), Self::Error> {
-			if pallet_evm::AccountCodes::<T>::get(conversion_rate_controller).is_empty() {
-				Err(ValidatorFeeTokenError::ControllerIsEOA)
-			} else {
-				ValidatorConversionRateController::<T>::insert(
-					validator,
-					conversion_rate_controller,
-				);
-				Ok(())
+			Self::ensure_conversion_rate_controller(validator, conversion_rate_controller)?;
+
+			ValidatorConversionRateController::<T>::insert(validator, conversion_rate_controller);
+			Self::clear_cached_conversion_rates(validator);
+			Ok(())
+		}
+
+		fn conversion_rate_gas_limit(validator: H160) -> u64 {
+			ConversionRateGasLimit::<T>::get(validator)
+				.unwrap_or(T::DefaultConversionRateGasLimit::get())
+		}
+
+		fn update_conversion_rate_gas_limit(
+			validator: H160,
+			gas_limit: u64,
+		) -> Result<(), Self::Error> {
+			if gas_limit > T::MaxConversionRateGasLimit::get() {
+				return Err(ValidatorFeeTokenError::GasLimitTooHigh);
 			}
+
+			ConversionRateGasLimit::<T>::insert(validator, gas_limit);
+			Self::clear_cached_conversion_rates(validator);
+			Ok(())
+		}
+
+		fn conversion_rate_fallback(validator: H160) -> Option<FallbackConversionRate> {
+			ConversionRateFallback::<T>::get(validator)
+		}
+
+		fn update_conversion_rate_fallback(
+			validator: H160,
+			fallback: Option<FallbackConversionRate>,
+		) -> Result<(), Self::Error> {
+			match fallback {
+				Some(FallbackConversionRate::Rate(_, denominator)) if denominator.is_zero() => {
+					return Err(ValidatorFeeTokenError::InvalidFallbackRate)
+				}
+				Some(fallback) => ConversionRateFallback::<T>::insert(validator, fallback),
+				None => ConversionRateFallback::<T>::remove(validator),
+			}
+
+			Self::clear_cached_conversion_rates(validator);
+			Ok(())
 		}
 
 		fn update_default_controller(controller: H160) -> Result<(), Self::Error> {
+			Self::ensure_conversion_rate_controller(H160::zero(), controller)?;
+
 			Self::set_default_controller(controller);
 			Ok(())
 		}
@@ -236,7 +413,14 @@ pub trait ValidatorFeeTokenController {
 
 	fn conversion_rate_controller(validator: H160) -> H160;
 
-	fn conversion_rate(sender: H160, validator: H160, token: H160) -> (U256, U256);
+	fn conversion_rate(
+		sender: H160,
+		validator: H160,
+		token: H160,
+	) -> Result<(U256, U256), Self::Error>;
+
+	/// Gas the next `conversion_rate` lookup for `sender`, `validator` and `token` costs.
+	fn conversion_rate_gas(sender: H160, validator: H160, token: H160) -> u64;
 
 	fn update_conversion_rate_controller(
 		validator: H160,
@@ -244,6 +428,28 @@ pub trait ValidatorFeeTokenController {
 	) -> Result<(), Self::Error>;
 
 	fn update_default_controller(controller: H160) -> Result<(), Self::Error>;
+
+	fn conversion_rate_gas_limit(validator: H160) -> u64;
+
+	fn update_conversion_rate_gas_limit(validator: H160, gas_limit: u64)
+		-> Result<(), Self::Error>;
+
+	fn conversion_rate_fallback(validator: H160) -> Option<FallbackConversionRate>;
+
+	fn update_conversion_rate_fallback(
+		validator: H160,
+		fallback: Option<FallbackConversionRate>,
+	) -> Result<(), Self::Error>;
+}
+
+/// What a validator charges when its conversion rate controller fails or returns malformed
+/// data. Without one, the token trades 1:1 with gas.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum FallbackConversionRate {
+	/// Charge the stored conversion rate.
+	Rate(U256, U256),
+	/// Reject the transactions paying fees in the token.
+	Reject,
 }
 
 pub trait ValidatorSupportedTokens {
@@ -28,11 +28,11 @@ use frame_support::{
 	pallet_prelude::{StorageValue, ValueQuery},
 	parameter_types,
 	traits::{Everything, StorageInstance},
-	weights::Weight,
+	weights::{constants::RocksDbWeight, Weight},
 };
 use frame_system::{EnsureSigned, RawOrigin};
 use pallet_evm::{EvmConfig, IdentityAddressMapping};
-use sp_core::{ConstU32, H160, H256};
+use sp_core::{ConstU32, ConstU64, H160, H256};
 use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
 use sp_runtime::BuildStorage;
 use sp_std::vec;
@@ -49,7 +49,7 @@ parameter_types! {
 
 impl frame_system::Config for Runtime {
 	type BaseCallFilter = Everything;
-	type DbWeight = ();
+	type DbWeight = RocksDbWeight;
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
 	type Hash = H256;
@@ -148,6 +148,21 @@ impl pallet_supported_tokens_manager::SupportedTokensManager for MockSupportedTo
 		Ok(())
 	}
 
+	fn add_supported_token_with_layout(
+		_token: H160,
+		_layout: pallet_supported_tokens_manager::BalanceLayout,
+	) -> Result<(), Self::Error> {
+		MockStorageTokens::mutate(|x| x.push(_token));
+		Ok(())
+	}
+
+	fn set_token_compliance_hook(
+		_token: H160,
+		_hook: Option<pallet_supported_tokens_manager::ComplianceHook>,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+
 	fn remove_supported_token(_token: H160) -> Result<(), Self::Error> {
 		MockStorageTokens::mutate(|x| x.retain(|t| t != &_token));
 		Ok(())
@@ -171,6 +186,9 @@ impl pallet_supported_tokens_manager::SupportedTokensManager for MockSupportedTo
 impl crate::Config for Runtime {
 	type SupportedTokensManager = MockSupportedTokensManager;
 	type SimulatorRunner = pallet_evm::runner::stack::Runner<Self>;
+	type RuntimeEvent = RuntimeEvent;
+	type DefaultConversionRateGasLimit = ConstU64<3_000_000>;
+	type MaxConversionRateGasLimit = ConstU64<10_000_000>;
 }
 
 pub struct EnsureAddressLinkedOrTruncated;
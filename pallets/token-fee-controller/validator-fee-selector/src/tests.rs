@@ -17,11 +17,13 @@
 // Please see the Stability Open License Agreement for more
 // information.
 
-use frame_support::parameter_types;
+use frame_support::{parameter_types, traits::{Get, Hooks}};
+use pallet_evm::GasWeightMapping;
 use pallet_supported_tokens_manager::SupportedTokensManager;
-use sp_core::H160;
+use sp_core::{H160, H256, U256};
 
-use crate::mock::{ExtBuilder, MockSupportedTokensManager, ValidatorFeeSelector, MeaninglessTokenAddress, NotSupportedToken, Runtime, };
+use crate::mock::{ExtBuilder, MockSupportedTokensManager, ValidatorFeeSelector, MeaninglessTokenAddress, NotSupportedToken, Runtime, RuntimeEvent, System, };
+use crate::{FallbackConversionRate, ValidatorFeeTokenError};
 
 parameter_types! {
 	pub MeaninglessAccount: H160 = H160::from_low_u64_le(1);
@@ -108,6 +110,8 @@ fn update_default_controller() {
 fn updated_token_conversion_rate() {
     ExtBuilder::default().build().execute_with(|| {
         let conversion_rate_controller : H160 = crate::GenesisConfig::<Runtime>::default().initial_default_conversion_rate_controller;
+        pallet_evm::AccountCodes::<Runtime>::insert(conversion_rate_controller, conversion_rate_controller_code());
+
         assert!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::update_conversion_rate_controller(
             MeaninglessAccount::get(),
             conversion_rate_controller,
@@ -139,4 +143,308 @@ fn not_supported_by_validator_if_not_supported_by_chain() {
             token,
         ), false);
     });
-}
\ No newline at end of file
+}
+
+// Returns (2, 3) for `getConversionRate(address,address,address)` and reverts otherwise.
+fn conversion_rate_controller_code() -> Vec<u8> {
+    vec![
+        0x60, 0x00, 0x35, 0x60, 0xE0, 0x1C, 0x63, 0xFF, 0x47, 0xB5, 0xA6, 0x14, 0x60, 0x14, 0x57,
+        0x60, 0x00, 0x60, 0x00, 0xFD, 0x5B, 0x60, 0x02, 0x60, 0x00, 0x52, 0x60, 0x03, 0x60, 0x20,
+        0x52, 0x60, 0x40, 0x60, 0x00, 0xF3,
+    ]
+}
+
+#[test]
+fn conversion_rate_is_cached_within_a_block() {
+    ExtBuilder::default().build().execute_with(|| {
+        let conversion_rate_controller : H160 = crate::GenesisConfig::<Runtime>::default().initial_default_conversion_rate_controller;
+        let token = MockSupportedTokensManager::get_default_token();
+
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::conversion_rate(
+            MeaninglessAccount::get(),
+            MeaninglessAccount::get(),
+            token,
+        ), Ok((U256::from(1), U256::from(1))));
+
+        pallet_evm::AccountCodes::<Runtime>::insert(conversion_rate_controller, conversion_rate_controller_code());
+
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::conversion_rate(
+            MeaninglessAccount::get(),
+            MeaninglessAccount::get(),
+            token,
+        ), Ok((U256::from(1), U256::from(1))));
+
+        <ValidatorFeeSelector as Hooks<u64>>::on_finalize(1);
+
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::conversion_rate(
+            MeaninglessAccount::get(),
+            MeaninglessAccount::get(),
+            token,
+        ), Ok((U256::from(2), U256::from(3))));
+    });
+}
+
+#[test]
+fn conversion_rate_lookup_pays_for_the_controller_call_until_cached() {
+    ExtBuilder::default().build().execute_with(|| {
+        let token = MockSupportedTokensManager::get_default_token();
+        let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::conversion_rate_gas(
+            MeaninglessAccount::get(),
+            MeaninglessAccount::get(),
+            token,
+        ), <Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(db_weight.reads_writes(5, 2)) + 3_000_000);
+
+        assert!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::conversion_rate(
+            MeaninglessAccount::get(),
+            MeaninglessAccount::get(),
+            token,
+        ).is_ok());
+
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::conversion_rate_gas(
+            MeaninglessAccount::get(),
+            MeaninglessAccount::get(),
+            token,
+        ), <Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(db_weight.reads(1)));
+    });
+}
+
+// Returns (sender, 1), the sender being the first argument.
+fn sender_conversion_rate_controller_code() -> Vec<u8> {
+    vec![
+        0x60, 0x04, 0x35, 0x60, 0x00, 0x52, 0x60, 0x01, 0x60, 0x20, 0x52, 0x60, 0x40, 0x60, 0x00,
+        0xF3,
+    ]
+}
+
+#[test]
+fn conversion_rate_is_cached_per_sender() {
+    ExtBuilder::default().build().execute_with(|| {
+        let conversion_rate_controller : H160 = crate::GenesisConfig::<Runtime>::default().initial_default_conversion_rate_controller;
+        let token = MockSupportedTokensManager::get_default_token();
+
+        pallet_evm::AccountCodes::<Runtime>::insert(conversion_rate_controller, sender_conversion_rate_controller_code());
+
+        for sender in [H160::from_low_u64_be(2), H160::from_low_u64_be(3)] {
+            assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::conversion_rate(
+                sender,
+                MeaninglessAccount::get(),
+                token,
+            ), Ok((U256::from_big_endian(H256::from(sender).as_bytes()), U256::from(1))));
+        }
+    });
+}
+
+#[test]
+fn updating_conversion_rate_controller_invalidates_cached_rates() {
+    ExtBuilder::default().build().execute_with(|| {
+        let new_controller = H160::from_low_u64_be(0x1234);
+        let token = MockSupportedTokensManager::get_default_token();
+
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::conversion_rate(
+            MeaninglessAccount::get(),
+            MeaninglessAccount::get(),
+            token,
+        ), Ok((U256::from(1), U256::from(1))));
+        assert!(ValidatorFeeSelector::cached_conversion_rate((MeaninglessAccount::get(), token, MeaninglessAccount::get())).is_some());
+
+        pallet_evm::AccountCodes::<Runtime>::insert(new_controller, conversion_rate_controller_code());
+        assert!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::update_conversion_rate_controller(
+            MeaninglessAccount::get(),
+            new_controller,
+        ).is_ok());
+
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::conversion_rate(
+            MeaninglessAccount::get(),
+            MeaninglessAccount::get(),
+            token,
+        ), Ok((U256::from(2), U256::from(3))));
+    });
+}
+
+#[test]
+fn updating_fee_token_acceptance_invalidates_cached_rates() {
+    ExtBuilder::default().build().execute_with(|| {
+        let token = MockSupportedTokensManager::get_default_token();
+
+        assert!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::conversion_rate(
+            MeaninglessAccount::get(),
+            MeaninglessAccount::get(),
+            token,
+        ).is_ok());
+        assert!(ValidatorFeeSelector::cached_conversion_rate((MeaninglessAccount::get(), token, MeaninglessAccount::get())).is_some());
+
+        assert!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::update_fee_token_acceptance(
+            MeaninglessAccount::get(),
+            token,
+            false,
+        ).is_ok());
+
+        assert!(ValidatorFeeSelector::cached_conversion_rate((MeaninglessAccount::get(), token, MeaninglessAccount::get())).is_none());
+    });
+}
+
+#[test]
+fn stored_fallback_rate_is_used_when_controller_fails() {
+    ExtBuilder::default().build().execute_with(|| {
+        let conversion_rate_controller : H160 = crate::GenesisConfig::<Runtime>::default().initial_default_conversion_rate_controller;
+        let token = MockSupportedTokensManager::get_default_token();
+
+        assert!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::update_conversion_rate_fallback(
+            MeaninglessAccount::get(),
+            Some(FallbackConversionRate::Rate(U256::from(5), U256::from(7))),
+        ).is_ok());
+
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::conversion_rate(
+            MeaninglessAccount::get(),
+            MeaninglessAccount::get(),
+            token,
+        ), Ok((U256::from(5), U256::from(7))));
+
+        System::assert_last_event(RuntimeEvent::ValidatorFeeSelector(crate::Event::ConversionRateFallbackUsed(
+            MeaninglessAccount::get(),
+            token,
+            conversion_rate_controller,
+        )));
+    });
+}
+
+#[test]
+fn fail_closed_validator_rejects_token_when_controller_fails() {
+    ExtBuilder::default().build().execute_with(|| {
+        let token = MockSupportedTokensManager::get_default_token();
+
+        assert!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::update_conversion_rate_fallback(
+            MeaninglessAccount::get(),
+            Some(FallbackConversionRate::Reject),
+        ).is_ok());
+
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::conversion_rate(
+            MeaninglessAccount::get(),
+            MeaninglessAccount::get(),
+            token,
+        ), Err(ValidatorFeeTokenError::ConversionRateUnavailable));
+    });
+}
+
+#[test]
+fn fail_update_fallback_rate_with_zero_denominator() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::update_conversion_rate_fallback(
+            MeaninglessAccount::get(),
+            Some(FallbackConversionRate::Rate(U256::from(5), U256::zero())),
+        ), Err(ValidatorFeeTokenError::InvalidFallbackRate));
+    });
+}
+
+#[test]
+fn controller_running_out_of_gas_uses_fallback() {
+    ExtBuilder::default().build().execute_with(|| {
+        let conversion_rate_controller : H160 = crate::GenesisConfig::<Runtime>::default().initial_default_conversion_rate_controller;
+        let token = MockSupportedTokensManager::get_default_token();
+
+        pallet_evm::AccountCodes::<Runtime>::insert(conversion_rate_controller, conversion_rate_controller_code());
+
+        assert!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::update_conversion_rate_gas_limit(
+            MeaninglessAccount::get(),
+            1_000,
+        ).is_ok());
+
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::conversion_rate(
+            MeaninglessAccount::get(),
+            MeaninglessAccount::get(),
+            token,
+        ), Ok((U256::from(1), U256::from(1))));
+
+        System::assert_last_event(RuntimeEvent::ValidatorFeeSelector(crate::Event::ConversionRateFallbackUsed(
+            MeaninglessAccount::get(),
+            token,
+            conversion_rate_controller,
+        )));
+    });
+}
+
+#[test]
+fn fail_update_gas_limit_above_max() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::update_conversion_rate_gas_limit(
+            MeaninglessAccount::get(),
+            10_000_001,
+        ), Err(ValidatorFeeTokenError::GasLimitTooHigh));
+
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::conversion_rate_gas_limit(
+            MeaninglessAccount::get(),
+        ), 3_000_000);
+    });
+}
+
+#[test]
+fn fail_update_conversion_rate_controller_without_get_conversion_rate() {
+    ExtBuilder::default().build().execute_with(|| {
+        let new_controller = H160::from_low_u64_be(0x1234);
+        // Returns (2, 3) for any call, but doesn't declare `getConversionRate`.
+        pallet_evm::AccountCodes::<Runtime>::insert(new_controller, vec![
+            0x60, 0x02, 0x60, 0x00, 0x52, 0x60, 0x03, 0x60, 0x20, 0x52, 0x60, 0x40, 0x60, 0x00, 0xF3,
+        ]);
+
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::update_conversion_rate_controller(
+            MeaninglessAccount::get(),
+            new_controller,
+        ), Err(ValidatorFeeTokenError::NotConversionRateController));
+    });
+}
+
+#[test]
+fn fail_update_conversion_rate_controller_with_malformed_return_data() {
+    ExtBuilder::default().build().execute_with(|| {
+        let new_controller = H160::from_low_u64_be(0x1234);
+        // Declares `getConversionRate` but returns no data.
+        pallet_evm::AccountCodes::<Runtime>::insert(new_controller, vec![
+            0x63, 0xFF, 0x47, 0xB5, 0xA6, 0x50, 0x00,
+        ]);
+
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::update_conversion_rate_controller(
+            MeaninglessAccount::get(),
+            new_controller,
+        ), Err(ValidatorFeeTokenError::MalformedConversionRate));
+
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::update_default_controller(
+            new_controller,
+        ), Err(ValidatorFeeTokenError::MalformedConversionRate));
+    });
+}
+
+#[test]
+fn fail_update_conversion_rate_controller_returning_a_single_word() {
+    ExtBuilder::default().build().execute_with(|| {
+        let new_controller = H160::from_low_u64_be(0x1234);
+        // Declares `getConversionRate` but only returns 5.
+        pallet_evm::AccountCodes::<Runtime>::insert(new_controller, vec![
+            0x63, 0xFF, 0x47, 0xB5, 0xA6, 0x50, 0x60, 0x05, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60,
+            0x00, 0xF3,
+        ]);
+
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::update_conversion_rate_controller(
+            MeaninglessAccount::get(),
+            new_controller,
+        ), Err(ValidatorFeeTokenError::MalformedConversionRate));
+    });
+}
+
+#[test]
+fn fail_update_conversion_rate_controller_with_zero_numerator() {
+    ExtBuilder::default().build().execute_with(|| {
+        let new_controller = H160::from_low_u64_be(0x1234);
+        // Declares `getConversionRate` and returns (0, 1).
+        pallet_evm::AccountCodes::<Runtime>::insert(new_controller, vec![
+            0x63, 0xFF, 0x47, 0xB5, 0xA6, 0x50, 0x60, 0x00, 0x60, 0x00, 0x52, 0x60, 0x01, 0x60,
+            0x20, 0x52, 0x60, 0x40, 0x60, 0x00, 0xF3,
+        ]);
+
+        assert_eq!(<ValidatorFeeSelector as crate::ValidatorFeeTokenController>::update_conversion_rate_controller(
+            MeaninglessAccount::get(),
+            new_controller,
+        ), Err(ValidatorFeeTokenError::MalformedConversionRate));
+    });
+}
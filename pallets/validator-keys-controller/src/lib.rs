@@ -38,11 +38,34 @@ mod tests;
 use frame_support::pallet_prelude::*;
 use log;
 pub use pallet::*;
-use sp_runtime::traits::Convert;
+use sp_runtime::{
+	offchain::{
+		storage::{StorageRetrievalError, StorageValueRef},
+		StorageKind,
+	},
+	traits::{Convert, OpaqueKeys},
+};
 use sp_std::prelude::*;
 
 pub const LOG_TARGET: &'static str = "runtime::validator-set";
 
+/// Prefix of the offchain storage records linking an aura key to the
+/// finalization key it has to be published with. The key of a record is the
+/// prefix followed by the encoded aura key, and its value is the encoded
+/// finalization key.
+pub const KEY_PAIRING_PREFIX: &[u8] = b"validator-keys-controller::pairing::";
+
+/// Prefix of the offchain storage records requesting the rotation of the keys
+/// of a validator. The key of a record is the prefix followed by the encoded
+/// current aura key, and its value is ignored.
+pub const KEY_ROTATION_REQUEST_PREFIX: &[u8] = b"validator-keys-controller::rotate::";
+
+/// Prefix of the offchain storage records holding the keys the offchain worker
+/// generated to rotate to. The key of a record is the prefix followed by the
+/// encoded aura key rotated from, and its value is the encoded new aura and
+/// finalization keys.
+pub const KEY_ROTATION_PREFIX: &[u8] = b"validator-keys-controller::rotation::";
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -84,11 +107,26 @@ pub mod pallet {
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
+	/// Validator account owning each aura key it rotated from or to. Keys that
+	/// were never rotated are not stored, as their account derives from the key.
+	#[pallet::storage]
+	#[pallet::getter(fn key_owner)]
+	pub type KeyOwners<T: Config> =
+		StorageMap<_, Twox64Concat, T::AuthorityId, T::AccountId, OptionQuery>;
+
+	/// Validator account owning each finalization key it rotated from or to.
+	#[pallet::storage]
+	#[pallet::getter(fn finalization_key_owner)]
+	pub type FinalizationKeyOwners<T: Config> =
+		StorageMap<_, Twox64Concat, T::FinalizationId, T::AccountId, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// New validator addition initiated. Effective in ~2 sessions.
 		ValidatorKeysPublished(T::ValidatorId, T::Keys),
+		/// Validator rotated its session keys. Effective next session.
+		ValidatorKeysRotated(T::ValidatorId, T::Keys),
 	}
 
 	// Errors inform users that something went wrong.
@@ -102,6 +140,12 @@ pub mod pallet {
 		ValidatorNotApproved,
 		/// Only the validator can add itself back after coming online.
 		BadOrigin,
+		/// The signing key is not the current aura key of the validator.
+		NotCurrentKey,
+		/// The new key is already used by a validator.
+		KeyAlreadyInUse,
+		/// The new keys are the same as the current ones.
+		KeysUnchanged,
 	}
 
 	#[pallet::validate_unsigned]
@@ -129,6 +173,25 @@ pub mod pallet {
 						.propagate(true)
 						.build();
 				}
+				Call::rotate_keys {
+					rotation,
+					signature,
+				} => {
+					if !rotation.current.verify(&rotation.encode(), signature) {
+						log::error!(target: LOG_TARGET, "Failed to verify rotation signature",);
+						return InvalidTransaction::BadProof.into();
+					}
+
+					if Self::ensure_valid_rotation(rotation).is_err() {
+						return InvalidTransaction::Call.into();
+					}
+
+					return ValidTransaction::with_tag_prefix("ValidatorKeysRotation")
+						.priority(u64::MAX)
+						.and_provides(rotation.current.clone())
+						.propagate(true)
+						.build();
+				}
 				_ => InvalidTransaction::Call.into(),
 			}
 		}
@@ -140,6 +203,7 @@ pub mod pallet {
 			// Only send messages if we are a potential validator.
 			if sp_io::offchain::is_validator() {
 				Self::offchain_publish_keys(now);
+				Self::offchain_rotate_keys(now);
 			} else {
 				log::trace!(
 					target: "runtime::validator-keys-controller",
@@ -155,47 +219,256 @@ pub mod pallet {
 			let aura_local_keys = T::AuthorityId::all();
 			let grandpa_local_keys = T::FinalizationId::all();
 
-			aura_local_keys
-				.iter()
-				.zip(grandpa_local_keys.iter())
-				.for_each(|(aura, grandpa)| {
-					let validator_id = T::ValidatorIdOfValidation::convert(aura.clone());
-					let account_id = T::AccountIdOfValidator::convert(aura.clone());
-					if pallet_validator_set::Validators::<T>::get().contains(&account_id)
-						&& pallet_session::NextKeys::<T>::get(validator_id).is_none()
-					{
-						let keys = PublishingKeys {
-							aura: aura.clone(),
-							grandpa: grandpa.clone(),
-							block_number,
-						};
+			aura_local_keys.iter().for_each(|aura| {
+				let validator_id = T::ValidatorIdOfValidation::convert(aura.clone());
+				let account_id = T::AccountIdOfValidator::convert(aura.clone());
+				if pallet_validator_set::Validators::<T>::get().contains(&account_id)
+					&& pallet_session::NextKeys::<T>::get(validator_id).is_none()
+				{
+					let grandpa = match Self::paired_finalization_key(
+						aura,
+						aura_local_keys.len() == 1,
+						&grandpa_local_keys,
+					) {
+						Some(grandpa) => grandpa,
+						None => return,
+					};
+
+					let keys = PublishingKeys {
+						aura: aura.clone(),
+						grandpa,
+						block_number,
+					};
+
+					let signature = aura.sign(&keys.encode());
+
+					if signature.is_none() {
+						log::error!(target: LOG_TARGET, "Failed to sign keys",);
+						return;
+					}
 
-						let signature = aura.sign(&keys.encode());
+					let signature = signature.unwrap();
 
-						if signature.is_none() {
-							log::error!(target: LOG_TARGET, "Failed to sign keys",);
-							return;
+					let call = Call::<T>::publish_keys { keys, signature };
+
+					match SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
+					{
+						Err(_) => {
+							log::error!(target: LOG_TARGET, "Failed to submit transaction",);
+						}
+						_ => {
+							log::info!(
+								target: LOG_TARGET,
+								"🔑 Successfully inserted new validator keys",
+							);
 						}
+					};
+				}
+			});
+		}
+
+		// Rotates the keys of the validators whose current aura key is in the
+		// keystore to the keys generated for them, once the operator requests
+		// the rotation. Other keys found in the keystore are never rotated to.
+		fn offchain_rotate_keys(block_number: BlockNumberFor<T>) {
+			T::AuthorityId::all().iter().for_each(|current| {
+				let validator_id = T::ValidatorIdOfValidation::convert(current.clone());
+				match Self::current_keys(&validator_id) {
+					Some((aura, _)) if aura == *current => (),
+					_ => return,
+				};
+
+				let (aura, grandpa) = match Self::generated_keys(current) {
+					Some(keys) => keys,
+					None => return,
+				};
+				// The operator can link the new aura key to another finalization key.
+				let grandpa = Self::linked_finalization_key(&aura).unwrap_or(grandpa);
+
+				let rotation = KeyRotation {
+					current: current.clone(),
+					aura,
+					grandpa,
+					block_number,
+				};
+
+				let signature = match current.sign(&rotation.encode()) {
+					Some(signature) => signature,
+					None => {
+						log::error!(target: LOG_TARGET, "Failed to sign key rotation",);
+						return;
+					}
+				};
 
-						let signature = signature.unwrap();
-
-						let call = Call::<T>::publish_keys { keys, signature };
-
-						match SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(
-							call.into(),
-						) {
-							Err(_) => {
-								log::error!(target: LOG_TARGET, "Failed to submit transaction",);
-							}
-							_ => {
-								log::info!(
-									target: LOG_TARGET,
-									"🔑 Successfully inserted new validator keys",
-								);
-							}
-						};
+				let call = Call::<T>::rotate_keys {
+					rotation,
+					signature,
+				};
+
+				match SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()) {
+					Err(_) => {
+						log::error!(target: LOG_TARGET, "Failed to submit key rotation",);
+					}
+					_ => {
+						log::info!(target: LOG_TARGET, "🔑 Successfully submitted key rotation",);
 					}
-				});
+				};
+			});
+		}
+
+		// Finalization key to publish along with the given aura key. The link
+		// record stored by the operator is used if any, otherwise the keys are
+		// only paired when there is no other candidate.
+		fn paired_finalization_key(
+			aura: &T::AuthorityId,
+			single_aura_key: bool,
+			candidates: &[T::FinalizationId],
+		) -> Option<T::FinalizationId> {
+			if let Some(linked) = Self::linked_finalization_key(aura) {
+				if candidates.contains(&linked) {
+					return Some(linked);
+				}
+
+				log::warn!(
+					target: LOG_TARGET,
+					"Linked finalization key of {:?} is not available, skipping",
+					aura,
+				);
+				return None;
+			}
+
+			if single_aura_key && candidates.len() == 1 {
+				return candidates.first().cloned();
+			}
+
+			log::warn!(
+				target: LOG_TARGET,
+				"Ambiguous finalization key for {:?}, link it in the offchain storage",
+				aura,
+			);
+			None
+		}
+
+		// Keys generated in the keystore to rotate the aura key `current` to.
+		// They are generated once the operator requests the rotation, and the
+		// new finalization key is linked to the new aura key.
+		fn generated_keys(current: &T::AuthorityId) -> Option<(T::AuthorityId, T::FinalizationId)> {
+			let request = [KEY_ROTATION_REQUEST_PREFIX, &current.encode()].concat();
+			let requested =
+				sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &request).is_some();
+
+			let record = [KEY_ROTATION_PREFIX, &current.encode()].concat();
+			let keys = StorageValueRef::persistent(&record)
+				.mutate(
+					|keys: Result<
+						Option<(T::AuthorityId, T::FinalizationId)>,
+						StorageRetrievalError,
+					>| match keys {
+						Ok(Some(keys)) => Ok(keys),
+						_ if requested => Ok((
+							T::AuthorityId::generate_pair(None),
+							T::FinalizationId::generate_pair(None),
+						)),
+						_ => Err(()),
+					},
+				)
+				.ok()?;
+
+			if requested {
+				StorageValueRef::persistent(&[KEY_PAIRING_PREFIX, &keys.0.encode()].concat())
+					.set(&keys.1);
+				StorageValueRef::persistent(&request).clear();
+			}
+
+			Some(keys)
+		}
+
+		fn linked_finalization_key(aura: &T::AuthorityId) -> Option<T::FinalizationId> {
+			let key = [KEY_PAIRING_PREFIX, &aura.encode()].concat();
+
+			StorageValueRef::persistent(&key)
+				.get::<T::FinalizationId>()
+				.ok()
+				.flatten()
+		}
+
+		// Aura and finalization keys the validator has queued for the next
+		// session.
+		fn current_keys(
+			validator_id: &T::ValidatorId,
+		) -> Option<(T::AuthorityId, T::FinalizationId)> {
+			let keys = pallet_session::NextKeys::<T>::get(validator_id)?;
+
+			Some((
+				keys.get::<T::AuthorityId>(<T::AuthorityId as RuntimeAppPublic>::ID)?,
+				keys.get::<T::FinalizationId>(<T::FinalizationId as RuntimeAppPublic>::ID)?,
+			))
+		}
+
+		fn is_unused_authority_key(key: &T::AuthorityId) -> bool {
+			!KeyOwners::<T>::contains_key(key)
+				&& !pallet_validator_set::ApprovedValidators::<T>::get()
+					.contains(&T::AccountIdOfValidator::convert(key.clone()))
+		}
+
+		fn ensure_valid_rotation(
+			rotation: &KeyRotation<T::AuthorityId, T::FinalizationId, BlockNumberFor<T>>,
+		) -> Result<(T::ValidatorId, T::AccountId, T::FinalizationId), Error<T>> {
+			let account_id = T::AccountIdOfValidator::convert(rotation.current.clone());
+			ensure!(
+				pallet_validator_set::ApprovedValidators::<T>::get().contains(&account_id),
+				Error::<T>::ValidatorNotApproved
+			);
+
+			let validator_id = T::ValidatorIdOfValidation::convert(rotation.current.clone());
+			let (aura, grandpa) = Self::current_keys(&validator_id)
+				.filter(|(aura, _)| *aura == rotation.current)
+				.ok_or(Error::<T>::NotCurrentKey)?;
+
+			ensure!(
+				rotation.aura != aura || rotation.grandpa != grandpa,
+				Error::<T>::KeysUnchanged
+			);
+			ensure!(
+				rotation.aura == aura || Self::is_unused_authority_key(&rotation.aura),
+				Error::<T>::KeyAlreadyInUse
+			);
+			ensure!(
+				rotation.grandpa == grandpa
+					|| !FinalizationKeyOwners::<T>::contains_key(&rotation.grandpa),
+				Error::<T>::KeyAlreadyInUse
+			);
+
+			Ok((validator_id, account_id, grandpa))
+		}
+
+		// Sets the keys of the validator for the next session, as
+		// `pallet_session::set_keys` does. The owners of the keys are kept in
+		// sync, as the key ownership proofs of equivocation reports rely on them.
+		fn set_next_keys(validator_id: &T::ValidatorId, keys: T::Keys) {
+			let old_keys = pallet_session::NextKeys::<T>::get(validator_id);
+
+			for id in T::Keys::key_ids() {
+				if let Some(old) = old_keys.as_ref() {
+					pallet_session::KeyOwner::<T>::remove((*id, old.get_raw(*id).to_vec()));
+				}
+
+				pallet_session::KeyOwner::<T>::insert(
+					(*id, keys.get_raw(*id).to_vec()),
+					validator_id,
+				);
+			}
+
+			pallet_session::NextKeys::<T>::insert(validator_id, keys);
+		}
+
+		fn rotate_keys_weight() -> Weight {
+			let key_ids = T::Keys::key_ids().len() as u64;
+
+			Weight::from_parts(60_000_000, 1602)
+				.saturating_add(T::DbWeight::get().reads(7_u64))
+				.saturating_add(T::DbWeight::get().writes(7_u64))
+				.saturating_add(T::DbWeight::get().writes(2_u64.saturating_mul(key_ids)))
 		}
 
 		fn verify_signature(
@@ -235,6 +508,15 @@ pub mod pallet {
 		pub block_number: BlockNumber,
 	}
 
+	/// New session keys of a validator, signed with its current aura key.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct KeyRotation<AuthorityId, FinalizationId, BlockNumber> {
+		pub current: AuthorityId,
+		pub aura: AuthorityId,
+		pub grandpa: FinalizationId,
+		pub block_number: BlockNumber,
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::call_index(0)]
@@ -258,7 +540,7 @@ pub mod pallet {
 				return Err(Error::<T>::ValidatorNotApproved.into());
 			}
 
-			pallet_session::NextKeys::<T>::insert(validator_id.clone(), session_keys.clone());
+			Self::set_next_keys(&validator_id, session_keys.clone());
 
 			pallet_session::QueuedKeys::<T>::mutate(|x| {
 				x.push((validator_id.clone(), session_keys.clone()));
@@ -273,6 +555,42 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Rotate the session keys of a validator. The rotation is signed with
+		/// its current aura key and the new keys apply at the next session.
+		#[pallet::call_index(1)]
+		#[pallet::weight(Pallet::<T>::rotate_keys_weight())]
+		pub fn rotate_keys(
+			origin: OriginFor<T>,
+			rotation: KeyRotation<T::AuthorityId, T::FinalizationId, BlockNumberFor<T>>,
+			_signature: <T::AuthorityId as RuntimeAppPublic>::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let (validator_id, account_id, grandpa) = Self::ensure_valid_rotation(&rotation)?;
+
+			// Retired keys keep their owner, so that they can't be used again.
+			KeyOwners::<T>::insert(rotation.current.clone(), account_id.clone());
+			KeyOwners::<T>::insert(rotation.aura.clone(), account_id.clone());
+			FinalizationKeyOwners::<T>::insert(grandpa, account_id.clone());
+			FinalizationKeyOwners::<T>::insert(rotation.grandpa.clone(), account_id);
+
+			let session_keys = T::SessionKeysBuilder::new(rotation.aura, rotation.grandpa);
+
+			Self::set_next_keys(&validator_id, session_keys.clone());
+
+			pallet_session::QueuedKeys::<T>::mutate(|x| {
+				x.iter_mut()
+					.filter(|(id, _)| *id == validator_id)
+					.for_each(|(_, keys)| *keys = session_keys.clone());
+			});
+
+			pallet_session::QueuedChanged::<T>::set(true);
+
+			Self::deposit_event(Event::ValidatorKeysRotated(validator_id, session_keys));
+
+			Ok(())
+		}
 	}
 }
 
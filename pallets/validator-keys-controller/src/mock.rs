@@ -85,6 +85,7 @@ frame_support::construct_runtime!(
 		ValidatorSet: pallet_validator_set,
 		ValidatorKeysController: crate,
 		Session: pallet_session,
+		Historical: pallet_session::historical,
 	}
 );
 
@@ -257,11 +258,23 @@ where
 pub struct AccountIdOfValidator;
 impl Convert<UintAuthorityId, u64> for AccountIdOfValidator {
 	fn convert(authority_id: UintAuthorityId) -> u64 {
-		authority_id.0.clone()
+		crate::KeyOwners::<Test>::get(&authority_id).unwrap_or(authority_id.0.clone())
 	}
 }
 parameter_types! {
 	pub const MaxKeys: u32 = 100;
+	pub const MaxMissedFinalityEpochs: u32 = 3;
+	pub const MinFinalityJustifications: u32 = 1;
+	pub const MaxFinalityVotes: u32 = 3;
+	pub const EquivocationReportLongevity: u64 = 100;
+	pub const MaxRejoinAttempts: u32 = 3;
+	pub const RejoinWindow: u64 = 100;
+	pub const JailDuration: u64 = 100;
+	pub const MaxOfflineRemovals: u32 = 3;
+	pub const OfflineRemovalWindow: u64 = 100;
+	pub const MaxMetadataLength: u32 = 128;
+	pub const MaxMaintenanceDuration: u64 = 100;
+	pub const StatsHistoryDepth: u32 = 10;
 }
 impl pallet_validator_set::Config for Test {
 	type AddRemoveOrigin = EnsureRoot<Self::AccountId>;
@@ -277,6 +290,32 @@ impl pallet_validator_set::Config for Test {
 	type MaxKeys = MaxKeys;
 
 	type AccountIdOfValidator = AccountIdOfValidator;
+
+	type SlotAuthorship = ();
+
+	type StatsHistoryDepth = StatsHistoryDepth;
+
+	type MaxMaintenanceDuration = MaxMaintenanceDuration;
+
+	type MaxMetadataLength = MaxMetadataLength;
+
+	type JailDuration = JailDuration;
+
+	type MaxOfflineRemovals = MaxOfflineRemovals;
+	type OfflineRemovalWindow = OfflineRemovalWindow;
+
+	type MaxRejoinAttempts = MaxRejoinAttempts;
+
+	type RejoinWindow = RejoinWindow;
+
+	type FinalityVoters = ();
+
+	type MaxMissedFinalityEpochs = MaxMissedFinalityEpochs;
+
+	type MinFinalityJustifications = MinFinalityJustifications;
+	type MaxFinalityVotes = MaxFinalityVotes;
+
+	type EquivocationReportLongevity = EquivocationReportLongevity;
 }
 
 impl pallet_session::Config for Test {
@@ -284,13 +323,18 @@ impl pallet_session::Config for Test {
 	type ValidatorIdOf = pallet_validator_set::ValidatorOf<Self>;
 	type ShouldEndSession = TestShouldEndSession;
 	type NextSessionRotation = ();
-	type SessionManager = ValidatorSet;
+	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Self, ValidatorSet>;
 	type SessionHandler = TestSessionHandler;
 	type Keys = MockSessionKeys;
 	type WeightInfo = ();
 	type RuntimeEvent = RuntimeEvent;
 }
 
+impl pallet_session::historical::Config for Test {
+	type FullIdentification = u64;
+	type FullIdentificationOf = pallet_validator_set::ValidatorOf<Self>;
+}
+
 pub struct SessionKeysBuilder;
 impl crate::SessionKeysBuilder<UintAuthorityId, UintAuthorityId, MockSessionKeys>
 	for SessionKeysBuilder
@@ -304,7 +348,7 @@ impl crate::SessionKeysBuilder<UintAuthorityId, UintAuthorityId, MockSessionKeys
 pub struct ValidatorIdMapping;
 impl Convert<UintAuthorityId, u64> for ValidatorIdMapping {
 	fn convert(a: UintAuthorityId) -> u64 {
-		return a.0;
+		return AccountIdOfValidator::convert(a);
 	}
 }
 impl crate::Config for Test {
@@ -21,10 +21,16 @@
 
 #![cfg(test)]
 
-use frame_system::RawOrigin;
+use frame_support::traits::KeyOwnerProofSystem;
+use frame_system::{mocking::MockUncheckedExtrinsic, RawOrigin};
+use sp_core::crypto::key_types::DUMMY;
+use sp_core::offchain::{
+	testing::{TestOffchainExt, TestTransactionPoolExt},
+	OffchainDbExt, OffchainWorkerExt, StorageKind, TransactionPoolExt,
+};
 use sp_runtime::testing::{TestSignature, UintAuthorityId};
 
-use crate::mock::{new_test_ext, Test};
+use crate::mock::{new_test_ext, Historical, RuntimeCall, Test};
 
 use super::*;
 
@@ -91,3 +97,283 @@ fn validator_publish_keys() {
 		assert!(pallet_session::NextKeys::<Test>::get(keys.aura.0).is_some())
 	});
 }
+
+#[test]
+fn published_keys_can_be_proven() {
+	new_test_ext().execute_with(|| {
+		// A validator without session keys.
+		pallet_session::NextKeys::<Test>::remove(1);
+		pallet_session::KeyOwner::<Test>::remove((DUMMY, UintAuthorityId(1).encode()));
+
+		let keys: PublishingKeys<UintAuthorityId, UintAuthorityId, u64> = PublishingKeys {
+			aura: UintAuthorityId(1),
+			grandpa: UintAuthorityId(1),
+			block_number: 1u64.into(),
+		};
+
+		let signed_message = keys
+			.clone()
+			.using_encoded(|bytes| TestSignature(1, bytes.to_vec()));
+
+		assert!(
+			crate::Pallet::<Test>::publish_keys(RawOrigin::None.into(), keys, signed_message)
+				.is_ok()
+		);
+
+		assert_eq!(
+			pallet_session::KeyOwner::<Test>::get((DUMMY, UintAuthorityId(1).encode())),
+			Some(1)
+		);
+
+		let key = (DUMMY, UintAuthorityId(1).encode());
+		let proof = Historical::prove(key.clone()).unwrap();
+
+		assert_eq!(Historical::check_proof(key, proof), Some((1, 1)));
+	});
+}
+
+fn signed_rotation(
+	current: u64,
+	aura: u64,
+	grandpa: u64,
+) -> (
+	KeyRotation<UintAuthorityId, UintAuthorityId, u64>,
+	TestSignature,
+) {
+	let rotation = KeyRotation {
+		current: UintAuthorityId(current),
+		aura: UintAuthorityId(aura),
+		grandpa: UintAuthorityId(grandpa),
+		block_number: 1u64.into(),
+	};
+
+	let signature = rotation
+		.clone()
+		.using_encoded(|bytes| TestSignature(current, bytes.to_vec()));
+
+	(rotation, signature)
+}
+
+#[test]
+fn validator_rotates_keys() {
+	new_test_ext().execute_with(|| {
+		let (rotation, signature) = signed_rotation(1, 10, 10);
+
+		assert!(
+			crate::Pallet::<Test>::rotate_keys(RawOrigin::None.into(), rotation, signature).is_ok()
+		);
+
+		let keys = pallet_session::NextKeys::<Test>::get(1).unwrap();
+		assert_eq!(keys.dummy, UintAuthorityId(10));
+		assert!(pallet_session::QueuedKeys::<Test>::get().contains(&(1, keys)));
+		assert_eq!(
+			crate::Pallet::<Test>::key_owner(UintAuthorityId(10)),
+			Some(1)
+		);
+		assert_eq!(
+			crate::Pallet::<Test>::key_owner(UintAuthorityId(1)),
+			Some(1)
+		);
+
+		// The session key owners follow the rotation.
+		assert_eq!(
+			pallet_session::KeyOwner::<Test>::get((DUMMY, UintAuthorityId(10).encode())),
+			Some(1)
+		);
+		assert_eq!(
+			pallet_session::KeyOwner::<Test>::get((DUMMY, UintAuthorityId(1).encode())),
+			None
+		);
+	});
+}
+
+#[test]
+fn fail_key_rotation_with_invalid_signature() {
+	new_test_ext().execute_with(|| {
+		let (rotation, _) = signed_rotation(1, 10, 10);
+
+		let call = crate::Call::<Test>::rotate_keys {
+			rotation,
+			signature: TestSignature(5, vec![]),
+		};
+
+		let err = crate::Pallet::<Test>::validate_unsigned(TransactionSource::Local, &call).err();
+
+		assert_eq!(err, Some(InvalidTransaction::BadProof.into()));
+	});
+}
+
+#[test]
+fn fail_key_rotation_with_retired_key() {
+	new_test_ext().execute_with(|| {
+		let (rotation, signature) = signed_rotation(1, 10, 10);
+		assert!(
+			crate::Pallet::<Test>::rotate_keys(RawOrigin::None.into(), rotation, signature).is_ok()
+		);
+
+		// The previous key is no longer the current one.
+		let (rotation, signature) = signed_rotation(1, 11, 11);
+		let err =
+			crate::Pallet::<Test>::rotate_keys(RawOrigin::None.into(), rotation, signature).err();
+		assert_eq!(err, Some(Error::<Test>::NotCurrentKey.into()));
+
+		// And it can't be rotated back to.
+		let (rotation, signature) = signed_rotation(10, 1, 1);
+		let err =
+			crate::Pallet::<Test>::rotate_keys(RawOrigin::None.into(), rotation, signature).err();
+		assert_eq!(err, Some(Error::<Test>::KeyAlreadyInUse.into()));
+	});
+}
+
+#[test]
+fn fail_key_rotation_to_key_of_another_validator() {
+	new_test_ext().execute_with(|| {
+		let (rotation, signature) = signed_rotation(1, 2, 2);
+
+		let err =
+			crate::Pallet::<Test>::rotate_keys(RawOrigin::None.into(), rotation, signature).err();
+
+		assert_eq!(err, Some(Error::<Test>::KeyAlreadyInUse.into()));
+	});
+}
+
+#[test]
+fn offchain_worker_publishes_linked_keys_only() {
+	let (offchain, offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	offchain_state.write().is_validator = true;
+
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		pallet_session::NextKeys::<Test>::remove(1);
+		UintAuthorityId::set_all_keys(vec![1u64, 7u64]);
+
+		// Several keys in the keystore and no link record.
+		crate::Pallet::<Test>::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+
+		sp_io::offchain::local_storage_set(
+			StorageKind::PERSISTENT,
+			&[KEY_PAIRING_PREFIX, &UintAuthorityId(1).encode()].concat(),
+			&UintAuthorityId(7).encode(),
+		);
+
+		crate::Pallet::<Test>::offchain_worker(1);
+
+		let transaction = pool_state.write().transactions.pop().unwrap();
+		let extrinsic = MockUncheckedExtrinsic::<Test>::decode(&mut &*transaction).unwrap();
+		match extrinsic.function {
+			RuntimeCall::ValidatorKeysController(crate::Call::publish_keys { keys, .. }) => {
+				assert_eq!(keys.aura, UintAuthorityId(1));
+				assert_eq!(keys.grandpa, UintAuthorityId(7));
+			}
+			_ => panic!("unexpected call"),
+		}
+	});
+}
+
+#[test]
+fn offchain_worker_rotates_to_generated_keys_only() {
+	let (offchain, offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	offchain_state.write().is_validator = true;
+
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		// An unused key in the keystore is not rotated to.
+		UintAuthorityId::set_all_keys(vec![1u64, 7u64]);
+		crate::Pallet::<Test>::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+
+		let request = [KEY_ROTATION_REQUEST_PREFIX, &UintAuthorityId(1).encode()].concat();
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, &request, &[1]);
+
+		crate::Pallet::<Test>::offchain_worker(1);
+
+		let transaction = pool_state.write().transactions.pop().unwrap();
+		let extrinsic = MockUncheckedExtrinsic::<Test>::decode(&mut &*transaction).unwrap();
+		let rotation = match extrinsic.function {
+			RuntimeCall::ValidatorKeysController(crate::Call::rotate_keys { rotation, .. }) => {
+				rotation
+			}
+			_ => panic!("unexpected call"),
+		};
+		assert_eq!(rotation.current, UintAuthorityId(1));
+		assert_ne!(rotation.aura, UintAuthorityId(7));
+		assert_eq!(
+			sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &request),
+			None
+		);
+
+		// The generated keys are submitted again until the rotation applies.
+		crate::Pallet::<Test>::offchain_worker(2);
+
+		let transaction = pool_state.write().transactions.pop().unwrap();
+		let extrinsic = MockUncheckedExtrinsic::<Test>::decode(&mut &*transaction).unwrap();
+		match extrinsic.function {
+			RuntimeCall::ValidatorKeysController(crate::Call::rotate_keys {
+				rotation: resubmitted,
+				..
+			}) => {
+				assert_eq!(resubmitted.aura, rotation.aura);
+				assert_eq!(resubmitted.grandpa, rotation.grandpa);
+			}
+			_ => panic!("unexpected call"),
+		}
+	});
+}
+
+#[test]
+fn offchain_worker_rotates_to_the_linked_finalization_key() {
+	let (offchain, offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	offchain_state.write().is_validator = true;
+
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		UintAuthorityId::set_all_keys(vec![1u64]);
+
+		// Keys generated for a previous request, with the new aura key linked to
+		// another finalization key.
+		let record = [KEY_ROTATION_PREFIX, &UintAuthorityId(1).encode()].concat();
+		sp_io::offchain::local_storage_set(
+			StorageKind::PERSISTENT,
+			&record,
+			&(UintAuthorityId(10), UintAuthorityId(11)).encode(),
+		);
+		sp_io::offchain::local_storage_set(
+			StorageKind::PERSISTENT,
+			&[KEY_PAIRING_PREFIX, &UintAuthorityId(10).encode()].concat(),
+			&UintAuthorityId(12).encode(),
+		);
+
+		crate::Pallet::<Test>::offchain_worker(1);
+
+		let transaction = pool_state.write().transactions.pop().unwrap();
+		let extrinsic = MockUncheckedExtrinsic::<Test>::decode(&mut &*transaction).unwrap();
+		match extrinsic.function {
+			RuntimeCall::ValidatorKeysController(crate::Call::rotate_keys { rotation, .. }) => {
+				assert_eq!(rotation.current, UintAuthorityId(1));
+				assert_eq!(rotation.aura, UintAuthorityId(10));
+				assert_eq!(
+					crate::Pallet::<Test>::linked_finalization_key(&rotation.aura),
+					Some(rotation.grandpa.clone())
+				);
+				assert_eq!(rotation.grandpa, UintAuthorityId(12));
+			}
+			_ => panic!("unexpected call"),
+		}
+	});
+}
@@ -38,15 +38,29 @@ mod tests;
 use core::ops::Add;
 use frame_support::{
 	ensure,
+	inherent::MakeFatalError,
 	pallet_prelude::*,
 	traits::{EstimateNextSessionRotation, Get, ValidatorSet, ValidatorSetWithIdentification},
 };
-use frame_system::pallet_prelude::BlockNumberFor;
+use frame_system::{
+	offchain::SubmitTransaction,
+	pallet_prelude::{BlockNumberFor, HeaderFor},
+};
 use log;
 pub use pallet::*;
-use sp_runtime::traits::{Convert, Saturating, Zero};
+use sp_application_crypto::RuntimeAppPublic;
+use sp_consensus_aura::AURA_ENGINE_ID;
+use sp_runtime::{
+	traits::{Convert, Header, SaturatedConversion, Saturating, Zero},
+	DigestItem, Perbill,
+};
+use sp_staking::offence::{OffenceDetails, OnOffenceHandler};
 use sp_std::{collections::btree_set::BTreeSet, prelude::*};
 
+/// Proof that a validator sealed two different headers for the same Aura slot.
+pub type AuraEquivocationProof<T> =
+	sp_consensus_slots::EquivocationProof<HeaderFor<T>, <T as Config>::AuthorityId>;
+
 pub const LOG_TARGET: &'static str = "runtime::validator-set";
 
 #[frame_support::pallet]
@@ -95,6 +109,56 @@ pub mod pallet {
 		type MaxKeys: Get<u32>;
 
 		type AccountIdOfValidator: Convert<Self::AuthorityId, Self::AccountId>;
+
+		/// Source of the consensus slot and its expected author, used to keep
+		/// track of the slots assigned to each validator.
+		type SlotAuthorship: SlotAuthorship<Self::AccountId>;
+
+		/// Number of past sessions for which performance statistics are kept.
+		type StatsHistoryDepth: Get<u32>;
+
+		/// Maximum length, in blocks, of a maintenance window.
+		type MaxMaintenanceDuration: Get<BlockNumberFor<Self>>;
+
+		/// Maximum length, in bytes, of each text field of the validator
+		/// metadata.
+		type MaxMetadataLength: Get<u32>;
+
+		/// Number of blocks a jailed validator has to wait before it can
+		/// rejoin the validator set.
+		type JailDuration: Get<BlockNumberFor<Self>>;
+
+		/// Number of offline removals within `OfflineRemovalWindow` blocks
+		/// after which a validator is jailed.
+		type MaxOfflineRemovals: Get<u32>;
+
+		/// Length, in blocks, of the window offline removals are counted in.
+		type OfflineRemovalWindow: Get<BlockNumberFor<Self>>;
+
+		/// Maximum number of times a validator can rejoin the validator set
+		/// within `RejoinWindow` blocks.
+		type MaxRejoinAttempts: Get<u32>;
+
+		/// Length, in blocks, of the window rejoin attempts are counted in.
+		type RejoinWindow: Get<BlockNumberFor<Self>>;
+
+		/// Checks the finality votes reported by block authors and resolves
+		/// the validators that cast them.
+		type FinalityVoters: FinalityVoters<Self::AccountId>;
+
+		/// Number of sessions in a row a validator can miss finality votes
+		/// before being removed as offline.
+		type MaxMissedFinalityEpochs: Get<u32>;
+
+		/// Number of justifications that must be noted in a session for the
+		/// validators missing from all of them to miss the session.
+		type MinFinalityJustifications: Get<u32>;
+
+		/// Maximum number of finality votes that can be reported in a block.
+		type MaxFinalityVotes: Get<u32>;
+
+		/// Number of slots during which an Aura equivocation can be reported.
+		type EquivocationReportLongevity: Get<u64>;
 	}
 
 	#[pallet::pallet]
@@ -134,6 +198,91 @@ pub mod pallet {
 	pub type BlockAuthors<T: Config> =
 		StorageMap<_, Twox64Concat, BlockNumberFor<T>, T::AccountId, OptionQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn last_authored_block)]
+	pub type LastAuthoredBlock<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BlockNumberFor<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn last_slot)]
+	pub type LastSlot<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn current_session_stats)]
+	pub type CurrentSessionStats<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, SessionStats, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn validator_stats)]
+	pub type ValidatorStats<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, Vec<SessionStats>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn maintenance_windows)]
+	pub type MaintenanceWindows<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BlockNumberFor<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn jailed_until)]
+	pub type JailedValidators<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BlockNumberFor<T>, OptionQuery>;
+
+	/// Start of the current offline removal window of each validator and the
+	/// number of times it was removed for being offline in it.
+	#[pallet::storage]
+	#[pallet::getter(fn offline_removals)]
+	pub type OfflineRemovals<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (BlockNumberFor<T>, u32), ValueQuery>;
+
+	/// Validators that rejoined the validator set and didn't author a block
+	/// yet, with the block they rejoined at.
+	#[pallet::storage]
+	#[pallet::getter(fn probation_since)]
+	pub type Probation<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BlockNumberFor<T>, OptionQuery>;
+
+	/// Start of the current rejoin window of each validator and the number of
+	/// rejoin attempts made in it.
+	#[pallet::storage]
+	#[pallet::getter(fn rejoin_attempts)]
+	pub type RejoinAttempts<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (BlockNumberFor<T>, u32), ValueQuery>;
+
+	/// Validators whose finality votes were seen during the current session.
+	#[pallet::storage]
+	#[pallet::getter(fn session_finality_voters)]
+	pub type SessionFinalityVoters<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (), OptionQuery>;
+
+	/// Authority set and round of the last justification whose votes were
+	/// noted.
+	#[pallet::storage]
+	#[pallet::getter(fn last_finality_justification)]
+	pub type LastFinalityJustification<T: Config> = StorageValue<_, (u64, u64), OptionQuery>;
+
+	/// Number of justifications whose votes were noted during the current
+	/// session.
+	#[pallet::storage]
+	#[pallet::getter(fn session_finality_justifications)]
+	pub type SessionFinalityJustifications<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Number of sessions in a row each validator missed finality votes.
+	#[pallet::storage]
+	#[pallet::getter(fn finality_epochs_missed)]
+	pub type FinalityEpochsMissed<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Aura equivocations already reported, by offender and slot.
+	#[pallet::storage]
+	#[pallet::getter(fn reported_aura_equivocation)]
+	pub type ReportedAuraEquivocations<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, u64, (), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn validator_metadata)]
+	pub type ValidatorMetadataOf<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, ValidatorMetadata<T::AccountId>, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -142,6 +291,33 @@ pub mod pallet {
 
 		/// Validator removal initiated. Effective in ~2 sessions.
 		ValidatorRemovalInitiated(T::AccountId),
+
+		/// Validator announced a maintenance window ending at the given block.
+		MaintenanceAnnounced(T::AccountId, BlockNumberFor<T>),
+
+		/// Validator maintenance window ended. It rejoins the set once it sends
+		/// a heartbeat.
+		MaintenanceEnded(T::AccountId),
+
+		/// Offline validators kept in the set to not go below `MinAuthorities`.
+		OfflineRemovalLimited(Vec<T::AccountId>),
+
+		/// Validator published or updated its metadata.
+		ValidatorMetadataSet(T::AccountId),
+
+		/// Validator was proven to equivocate. It has been disabled, removed
+		/// and jailed.
+		ValidatorEquivocated(T::AccountId),
+
+		/// Validator jailed until the given block.
+		ValidatorJailed(T::AccountId, BlockNumberFor<T>),
+
+		/// Validator released from jail by governance.
+		ValidatorUnjailed(T::AccountId),
+
+		/// Validator on probation authored a block and counts again toward
+		/// `MinAuthorities`.
+		ProbationPassed(T::AccountId),
 	}
 
 	// Errors inform users that something went wrong.
@@ -155,6 +331,27 @@ pub mod pallet {
 		ValidatorNotApproved,
 		/// Only the validator can add itself back after coming online.
 		BadOrigin,
+		/// Validator is not in the validator set.
+		NotInValidatorSet,
+		/// Validator already announced a maintenance window.
+		AlreadyInMaintenance,
+		/// Validator has no maintenance window in progress.
+		NotInMaintenance,
+		/// Maintenance window ends in the past or is longer than allowed.
+		InvalidMaintenanceWindow,
+		/// A metadata field is longer than `MaxMetadataLength`.
+		MetadataTooLong,
+		/// The equivocation proof is invalid, too old or already reported, or
+		/// the offender is not approved.
+		InvalidEquivocationProof,
+		/// Validator is jailed and its cooldown is not over.
+		Jailed,
+		/// Validator is not jailed.
+		NotJailed,
+		/// Validator rejoined too many times within the rejoin window.
+		TooManyRejoinAttempts,
+		/// More finality votes were reported than `MaxFinalityVotes`.
+		TooManyFinalityVotes,
 	}
 
 	#[pallet::validate_unsigned]
@@ -180,6 +377,18 @@ pub mod pallet {
 						return InvalidTransaction::Call.into();
 					}
 
+					if MaintenanceWindows::<T>::contains_key(&account_id) {
+						return InvalidTransaction::Call.into();
+					}
+
+					if Self::is_jailed(&account_id) {
+						return InvalidTransaction::Call.into();
+					}
+
+					if !Self::can_rejoin(&account_id) {
+						return InvalidTransaction::Call.into();
+					}
+
 					let sesion_index = pallet_session::Pallet::<T>::current_index();
 					return ValidTransaction::with_tag_prefix("ValidatorSet")
 						.priority(u64::MAX)
@@ -187,6 +396,21 @@ pub mod pallet {
 						.propagate(true)
 						.build();
 				}
+				Call::report_aura_equivocation { equivocation_proof } => {
+					if Self::check_aura_equivocation(equivocation_proof).is_none() {
+						return InvalidTransaction::BadProof.into();
+					}
+
+					return ValidTransaction::with_tag_prefix("AuraEquivocation")
+						.priority(u64::MAX)
+						.and_provides((
+							equivocation_proof.offender.clone(),
+							equivocation_proof.slot,
+						))
+						.longevity(64)
+						.propagate(true)
+						.build();
+				}
 				_ => InvalidTransaction::Call.into(),
 			}
 		}
@@ -194,13 +418,26 @@ pub mod pallet {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_block_number: BlockNumberFor<T>) -> Weight {
+			Pallet::<T>::on_finalize_weight()
+		}
+
 		fn on_finalize(block_number: BlockNumberFor<T>) {
 			let digest = <frame_system::Pallet<T>>::digest();
 			let pre_runtime_digests = digest.logs.iter().filter_map(|d| d.as_pre_runtime());
 
 			if let Some(validator) = T::FindAuthor::find_author(pre_runtime_digests) {
+				CurrentSessionStats::<T>::mutate(validator.clone(), |stats| {
+					stats.blocks_authored = stats.blocks_authored.saturating_add(1);
+				});
+				LastAuthoredBlock::<T>::insert(validator.clone(), block_number);
+				if Probation::<T>::take(validator.clone()).is_some() {
+					Self::deposit_event(Event::ProbationPassed(validator.clone()));
+				}
 				BlockAuthors::<T>::insert(block_number, validator);
 			}
+
+			Self::note_assigned_slots();
 		}
 
 		fn offchain_worker(now: BlockNumberFor<T>) {
@@ -220,6 +457,9 @@ pub mod pallet {
 						);
 						if !Validators::<T>::get().contains(&validator_id)
 							&& !ToBeAddedValidators::<T>::get().contains(&validator_id)
+							&& !MaintenanceWindows::<T>::contains_key(&validator_id)
+							&& !Self::is_jailed(&validator_id)
+							&& Self::can_rejoin(&validator_id)
 						{
 							let heartbeat = Heartbeat {
 								block_number: now,
@@ -371,6 +611,32 @@ pub mod pallet {
 		pub authority_index: u32,
 	}
 
+	/// Public information published by a validator about itself.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct ValidatorMetadata<AccountId> {
+		/// Human readable name of the validator.
+		pub display_name: Vec<u8>,
+		/// Website of the entity running the validator.
+		pub website: Vec<u8>,
+		/// Contact of the validator operator, e.g. an email address.
+		pub contact: Vec<u8>,
+		/// libp2p peer id of the validator node, in its base58 form.
+		pub peer_id: Vec<u8>,
+		/// Account where the validator wants to receive its payouts.
+		pub payout_address: AccountId,
+	}
+
+	/// Performance counters of a validator during a session.
+	#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct SessionStats {
+		/// Index of the session the counters belong to.
+		pub session_index: sp_staking::SessionIndex,
+		/// Number of blocks authored by the validator.
+		pub blocks_authored: u32,
+		/// Number of slots in which the validator was expected to author.
+		pub slots_assigned: u32,
+	}
+
 	impl<T: Config> Pallet<T> {
 		fn add_validator_weight() -> Weight {
 			Weight::from_parts(21_330_000, 1602)
@@ -382,17 +648,93 @@ pub mod pallet {
 			Weight::from_parts(19_840_000, 1602)
 				.saturating_add(T::DbWeight::get().reads(2_u64))
 				.saturating_add(T::DbWeight::get().writes(2_u64))
+				.saturating_add(Pallet::<T>::remaining_validators_weight())
 		}
 
 		fn add_validator_again_weight() -> Weight {
 			Weight::from_parts(21_330_000, 1602)
-				.saturating_add(T::DbWeight::get().reads(2_u64))
-				.saturating_add(T::DbWeight::get().writes(1_u64))
+				.saturating_add(T::DbWeight::get().reads(4_u64))
+				.saturating_add(T::DbWeight::get().writes(3_u64))
 		}
 
 		fn update_max_missed_epochs_weight() -> Weight {
 			Weight::from_parts(21_330_000, 1602).saturating_add(T::DbWeight::get().writes(1_u64))
 		}
+
+		fn announce_maintenance_weight() -> Weight {
+			Weight::from_parts(21_330_000, 1602)
+				.saturating_add(T::DbWeight::get().reads(3_u64))
+				.saturating_add(T::DbWeight::get().writes(2_u64))
+				.saturating_add(Pallet::<T>::remaining_validators_weight())
+		}
+
+		fn end_maintenance_weight() -> Weight {
+			Weight::from_parts(19_840_000, 1602)
+				.saturating_add(T::DbWeight::get().reads(1_u64))
+				.saturating_add(T::DbWeight::get().writes(1_u64))
+		}
+
+		fn set_validator_metadata_weight() -> Weight {
+			Weight::from_parts(21_330_000, 1602)
+				.saturating_add(T::DbWeight::get().reads(1_u64))
+				.saturating_add(T::DbWeight::get().writes(1_u64))
+		}
+
+		fn jail_validator_weight() -> Weight {
+			Weight::from_parts(21_330_000, 1602)
+				.saturating_add(T::DbWeight::get().reads(2_u64))
+				.saturating_add(T::DbWeight::get().writes(4_u64))
+				.saturating_add(Pallet::<T>::remaining_validators_weight())
+		}
+
+		fn unjail_validator_weight() -> Weight {
+			Weight::from_parts(19_840_000, 1602)
+				.saturating_add(T::DbWeight::get().reads(1_u64))
+				.saturating_add(T::DbWeight::get().writes(2_u64))
+		}
+
+		fn report_aura_equivocation_weight() -> Weight {
+			Weight::from_parts(95_000_000, 1602)
+				.saturating_add(T::DbWeight::get().reads_writes(2_u64, 1_u64))
+				.saturating_add(Pallet::<T>::punish_equivocation_weight())
+		}
+
+		// Every vote has its signature verified and its voter resolved,
+		// taken off probation and noted.
+		fn note_finality_votes_weight(votes: u32) -> Weight {
+			Weight::from_parts(20_000_000, 1602)
+				.saturating_add(T::DbWeight::get().reads(4_u64))
+				.saturating_add(T::DbWeight::get().writes(2_u64))
+				.saturating_add(
+					Weight::from_parts(60_000_000, 0)
+						.saturating_add(T::DbWeight::get().reads(3_u64))
+						.saturating_add(T::DbWeight::get().writes(2_u64))
+						.saturating_mul(votes.into()),
+				)
+		}
+
+		// The block author is noted, and the slots elapsed since the previous
+		// block are credited to each authority once at most.
+		fn on_finalize_weight() -> Weight {
+			Weight::from_parts(15_000_000, 1602)
+				.saturating_add(T::DbWeight::get().reads(7_u64))
+				.saturating_add(T::DbWeight::get().writes(5_u64))
+				.saturating_add(
+					T::DbWeight::get()
+						.reads_writes(1_u64, 1_u64)
+						.saturating_mul(T::SlotAuthorship::authorities_len().into()),
+				)
+		}
+
+		// The maintenance window, jail and probation of every validator are
+		// read.
+		fn remaining_validators_weight() -> Weight {
+			T::DbWeight::get().reads(3_u64.saturating_mul(T::MaxKeys::get().into()))
+		}
+
+		pub(crate) fn punish_equivocation_weight() -> Weight {
+			T::DbWeight::get().reads_writes(4_u64, 6_u64)
+		}
 	}
 
 	#[pallet::call]
@@ -427,7 +769,13 @@ pub mod pallet {
 			T::AddRemoveOrigin::ensure_origin(origin)?;
 
 			Self::do_remove_validator(validator_id.clone())?;
-			Self::unapprove_validator(validator_id)?;
+			Self::unapprove_validator(validator_id.clone())?;
+			MaintenanceWindows::<T>::remove(validator_id.clone());
+			JailedValidators::<T>::remove(validator_id.clone());
+			OfflineRemovals::<T>::remove(validator_id.clone());
+			Probation::<T>::remove(validator_id.clone());
+			RejoinAttempts::<T>::remove(validator_id.clone());
+			ValidatorMetadataOf::<T>::remove(validator_id);
 
 			Ok(())
 		}
@@ -468,16 +816,317 @@ pub mod pallet {
 				return DispatchError::Other("Validator already in the validator set.").into();
 			}
 
+			ensure!(
+				!MaintenanceWindows::<T>::contains_key(&validator_account_id),
+				Error::<T>::AlreadyInMaintenance
+			);
+
+			ensure!(!Self::is_jailed(&validator_account_id), Error::<T>::Jailed);
+			ensure!(
+				Self::can_rejoin(&validator_account_id),
+				Error::<T>::TooManyRejoinAttempts
+			);
+			JailedValidators::<T>::remove(&validator_account_id);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			RejoinAttempts::<T>::mutate(&validator_account_id, |(window_start, attempts)| {
+				if now >= window_start.saturating_add(T::RejoinWindow::get()) {
+					*window_start = now;
+					*attempts = 0;
+				}
+				*attempts = attempts.saturating_add(1);
+			});
+			Probation::<T>::insert(&validator_account_id, now);
+
 			ToBeAddedValidators::<T>::mutate(|v| {
 				v.push(validator_account_id.clone());
 			});
 
 			Ok(())
 		}
+
+		/// Announce a maintenance window for the calling validator.
+		///
+		/// The validator leaves the validator set at the end of the current
+		/// session without being accounted as offline. At the first session end
+		/// after `until` its window is closed and it rejoins the set as any
+		/// offline validator, through a heartbeat.
+		///
+		/// For this call, the dispatch origin must be the validator itself,
+		/// which must be in the validator set.
+		#[pallet::call_index(4)]
+		#[pallet::weight(Pallet::<T>::announce_maintenance_weight())]
+		pub fn announce_maintenance(
+			origin: OriginFor<T>,
+			until: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let validator_id = ensure_signed(origin)?;
+
+			ensure!(
+				Self::approved_validators().contains(&validator_id),
+				Error::<T>::ValidatorNotApproved
+			);
+			ensure!(
+				!MaintenanceWindows::<T>::contains_key(&validator_id),
+				Error::<T>::AlreadyInMaintenance
+			);
+			ensure!(
+				Self::validators().contains(&validator_id),
+				Error::<T>::NotInValidatorSet
+			);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				until > now && until.saturating_sub(now) <= T::MaxMaintenanceDuration::get(),
+				Error::<T>::InvalidMaintenanceWindow
+			);
+
+			// Ensuring that the validators left online don't go below the minimum.
+			ensure!(
+				Self::remaining_validators(&validator_id) >= T::MinAuthorities::get(),
+				Error::<T>::TooLowValidatorCount
+			);
+
+			MaintenanceWindows::<T>::insert(validator_id.clone(), until);
+			ToBeAddedValidators::<T>::mutate(|v| v.retain(|x| *x != validator_id));
+
+			Self::deposit_event(Event::MaintenanceAnnounced(validator_id, until));
+
+			Ok(())
+		}
+
+		/// End the maintenance window of the calling validator ahead of time.
+		///
+		/// The window is closed at the end of the current session, after which
+		/// the validator rejoins the set through a heartbeat.
+		#[pallet::call_index(5)]
+		#[pallet::weight(Pallet::<T>::end_maintenance_weight())]
+		pub fn end_maintenance(origin: OriginFor<T>) -> DispatchResult {
+			let validator_id = ensure_signed(origin)?;
+
+			ensure!(
+				MaintenanceWindows::<T>::contains_key(&validator_id),
+				Error::<T>::NotInMaintenance
+			);
+
+			MaintenanceWindows::<T>::insert(
+				validator_id,
+				frame_system::Pallet::<T>::block_number(),
+			);
+
+			Ok(())
+		}
+
+		/// Publish or update the metadata of the calling validator.
+		///
+		/// For this call, the dispatch origin must be an approved validator.
+		#[pallet::call_index(6)]
+		#[pallet::weight(Pallet::<T>::set_validator_metadata_weight())]
+		pub fn set_validator_metadata(
+			origin: OriginFor<T>,
+			metadata: ValidatorMetadata<T::AccountId>,
+		) -> DispatchResult {
+			let validator_id = ensure_signed(origin)?;
+
+			ensure!(
+				Self::approved_validators().contains(&validator_id),
+				Error::<T>::ValidatorNotApproved
+			);
+
+			let max_length = T::MaxMetadataLength::get() as usize;
+			ensure!(
+				[
+					&metadata.display_name,
+					&metadata.website,
+					&metadata.contact,
+					&metadata.peer_id
+				]
+				.iter()
+				.all(|field| field.len() <= max_length),
+				Error::<T>::MetadataTooLong
+			);
+
+			ValidatorMetadataOf::<T>::insert(validator_id.clone(), metadata);
+
+			Self::deposit_event(Event::ValidatorMetadataSet(validator_id));
+
+			Ok(())
+		}
+
+		/// Report a validator that sealed two different blocks for the same
+		/// Aura slot.
+		///
+		/// The offender is disabled for the rest of the session, removed from
+		/// the validator set and jailed for `JailDuration` blocks. Submitted as
+		/// an unsigned extrinsic by the nodes that see the equivocation.
+		#[pallet::call_index(7)]
+		#[pallet::weight(Pallet::<T>::report_aura_equivocation_weight())]
+		pub fn report_aura_equivocation(
+			origin: OriginFor<T>,
+			equivocation_proof: Box<AuraEquivocationProof<T>>,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let validator_id = Self::check_aura_equivocation(&equivocation_proof)
+				.ok_or(Error::<T>::InvalidEquivocationProof)?;
+
+			ReportedAuraEquivocations::<T>::insert(
+				validator_id.clone(),
+				*equivocation_proof.slot,
+				(),
+			);
+			Self::punish_equivocation(validator_id);
+
+			Ok(())
+		}
+
+		/// Jail a validator.
+		///
+		/// The validator leaves the validator set at the end of the current
+		/// session and its heartbeats are rejected until `JailDuration` blocks
+		/// have passed or it is unjailed. Fails if the validators left would be
+		/// less than `MinAuthorities`.
+		///
+		/// The origin can be configured using the `AddRemoveOrigin` type in the
+		/// host runtime. Can also be set to sudo/root.
+		#[pallet::call_index(8)]
+		#[pallet::weight(Pallet::<T>::jail_validator_weight())]
+		pub fn jail_validator(origin: OriginFor<T>, validator_id: T::AccountId) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				Self::approved_validators().contains(&validator_id),
+				Error::<T>::ValidatorNotApproved
+			);
+			ensure!(
+				!Self::validators().contains(&validator_id)
+					|| Self::remaining_validators(&validator_id) >= T::MinAuthorities::get(),
+				Error::<T>::TooLowValidatorCount
+			);
+
+			Self::jail(validator_id);
+
+			Ok(())
+		}
+
+		/// Release a validator from jail before its cooldown is over.
+		///
+		/// The validator is added back by the offchain worker once it sends a
+		/// heartbeat.
+		///
+		/// The origin can be configured using the `AddRemoveOrigin` type in the
+		/// host runtime. Can also be set to sudo/root.
+		#[pallet::call_index(9)]
+		#[pallet::weight(Pallet::<T>::unjail_validator_weight())]
+		pub fn unjail_validator(
+			origin: OriginFor<T>,
+			validator_id: T::AccountId,
+		) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				JailedValidators::<T>::contains_key(&validator_id),
+				Error::<T>::NotJailed
+			);
+
+			JailedValidators::<T>::remove(&validator_id);
+			OfflineRemovals::<T>::remove(&validator_id);
+
+			Self::deposit_event(Event::ValidatorUnjailed(validator_id));
+
+			Ok(())
+		}
+
+		/// Note the validators whose finality votes were seen by the block
+		/// author. Validators on probation leave it. Votes of a justification
+		/// already noted, or older than it, are ignored.
+		///
+		/// This is an inherent and can't be submitted as a transaction.
+		#[pallet::call_index(10)]
+		#[pallet::weight((
+			Pallet::<T>::note_finality_votes_weight(T::FinalityVoters::votes_len(votes)),
+			DispatchClass::Mandatory
+		))]
+		pub fn note_finality_votes(
+			origin: OriginFor<T>,
+			votes: FinalityVotesOf<T>,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			ensure!(
+				T::FinalityVoters::votes_len(&votes) <= T::MaxFinalityVotes::get(),
+				Error::<T>::TooManyFinalityVotes
+			);
+
+			if !Self::is_new_justification(&votes) {
+				return Ok(());
+			}
+
+			LastFinalityJustification::<T>::put(T::FinalityVoters::justification(&votes));
+			SessionFinalityJustifications::<T>::mutate(|n| *n = n.saturating_add(1));
+
+			let voters = T::FinalityVoters::voters(&votes);
+
+			voters.into_iter().for_each(|voter| {
+				if Probation::<T>::take(voter.clone()).is_some() {
+					Self::deposit_event(Event::ProbationPassed(voter.clone()));
+				}
+
+				SessionFinalityVoters::<T>::insert(voter, ());
+			});
+
+			Ok(())
+		}
+	}
+
+	#[pallet::inherent]
+	impl<T: Config> ProvideInherent for Pallet<T> {
+		type Call = Call<T>;
+		type Error = MakeFatalError<()>;
+		const INHERENT_IDENTIFIER: InherentIdentifier =
+			stbl_primitives_finality_votes::INHERENT_IDENTIFIER;
+
+		fn create_inherent(data: &InherentData) -> Option<Self::Call> {
+			let votes = data
+				.get_data::<FinalityVotesOf<T>>(&Self::INHERENT_IDENTIFIER)
+				.ok()
+				.flatten()
+				.filter(|votes| {
+					Self::is_new_justification(votes)
+						&& T::FinalityVoters::votes_len(votes) <= T::MaxFinalityVotes::get()
+				})?;
+
+			Some(Call::note_finality_votes { votes })
+		}
+
+		// Only votes the block author would have reported are accepted, as
+		// they are paid for by the block.
+		fn check_inherent(call: &Self::Call, _data: &InherentData) -> Result<(), Self::Error> {
+			match call {
+				Call::note_finality_votes { votes }
+					if !Self::is_new_justification(votes)
+						|| T::FinalityVoters::votes_len(votes) > T::MaxFinalityVotes::get() =>
+				{
+					Err(MakeFatalError::from(()))
+				}
+				_ => Ok(()),
+			}
+		}
+
+		fn is_inherent(call: &Self::Call) -> bool {
+			matches!(call, Call::note_finality_votes { .. })
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	// Whether the votes come from a justification later than the last one
+	// noted. Block authors keep reporting their latest justification until
+	// they see another one.
+	fn is_new_justification(votes: &FinalityVotesOf<T>) -> bool {
+		LastFinalityJustification::<T>::get()
+			.map_or(true, |last| T::FinalityVoters::justification(votes) > last)
+	}
+
 	fn initialize_validators(account_ids: Vec<T::AccountId>) {
 		assert!(
 			<Validators<T>>::get().is_empty(),
@@ -489,20 +1138,14 @@ impl<T: Config> Pallet<T> {
 	}
 
 	fn do_remove_validator(validator_id: T::AccountId) -> DispatchResult {
-		let mut validators = <Validators<T>>::get();
-
-		let validators_count = match validators.len().checked_sub(1) {
-			Some(v) => v,
-			None => return Err(DispatchError::Other("Arithmetic error due to underflow.")),
-		};
-
 		// Ensuring that the post removal, target validator count doesn't go
 		// below the minimum.
 		ensure!(
-			validators_count as u32 >= T::MinAuthorities::get(),
+			Self::remaining_validators(&validator_id) >= T::MinAuthorities::get(),
 			Error::<T>::TooLowValidatorCount
 		);
 
+		let mut validators = <Validators<T>>::get();
 		validators.retain(|v| *v != validator_id);
 
 		<Validators<T>>::put(validators);
@@ -517,6 +1160,153 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Submits an unsigned extrinsic reporting an Aura equivocation. Meant to
+	/// be called from the runtime API used by the node.
+	pub fn submit_unsigned_aura_equivocation_report(
+		equivocation_proof: AuraEquivocationProof<T>,
+	) -> Option<()> {
+		let call = Call::<T>::report_aura_equivocation {
+			equivocation_proof: Box::new(equivocation_proof),
+		};
+
+		match SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()) {
+			Ok(()) => Some(()),
+			Err(()) => {
+				log::error!(target: LOG_TARGET, "Failed to submit Aura equivocation report");
+				None
+			}
+		}
+	}
+
+	// Checks that both headers of the proof were sealed by the offender for the
+	// proof slot and returns the offending validator if it is still approved.
+	// Proofs older than `EquivocationReportLongevity` slots or already reported
+	// are rejected, so that an offender is only punished once per offence.
+	fn check_aura_equivocation(proof: &AuraEquivocationProof<T>) -> Option<T::AccountId> {
+		if proof.first_header.hash() == proof.second_header.hash() {
+			return None;
+		}
+
+		if T::SlotAuthorship::current_slot().saturating_sub(*proof.slot)
+			> T::EquivocationReportLongevity::get()
+		{
+			return None;
+		}
+
+		let first_slot = Self::aura_sealed_slot(proof.first_header.clone(), &proof.offender)?;
+		let second_slot = Self::aura_sealed_slot(proof.second_header.clone(), &proof.offender)?;
+		if first_slot != *proof.slot || second_slot != *proof.slot {
+			return None;
+		}
+
+		let validator_id = T::AccountIdOfValidator::convert(proof.offender.clone());
+		if !Self::approved_validators().contains(&validator_id)
+			|| ReportedAuraEquivocations::<T>::contains_key(&validator_id, *proof.slot)
+		{
+			return None;
+		}
+
+		Some(validator_id)
+	}
+
+	// Returns the Aura slot of a header if its seal was signed by `author`.
+	fn aura_sealed_slot(mut header: HeaderFor<T>, author: &T::AuthorityId) -> Option<u64> {
+		let signature = match header.digest_mut().pop()? {
+			DigestItem::Seal(id, signature) if id == AURA_ENGINE_ID => {
+				<T::AuthorityId as RuntimeAppPublic>::Signature::decode(&mut &signature[..]).ok()?
+			}
+			_ => return None,
+		};
+
+		let slot = header.digest().logs().iter().find_map(|log| match log {
+			DigestItem::PreRuntime(id, slot) if *id == AURA_ENGINE_ID => {
+				u64::decode(&mut &slot[..]).ok()
+			}
+			_ => None,
+		})?;
+
+		if author.verify(&header.hash(), &signature) {
+			Some(slot)
+		} else {
+			None
+		}
+	}
+
+	// Takes an equivocating validator out of consensus. There is no stake to
+	// slash, so the validator is disabled for the current session, removed from
+	// the set regardless of `MinAuthorities` and jailed.
+	fn punish_equivocation(validator_id: T::AccountId) {
+		log::warn!(
+			target: LOG_TARGET,
+			"Jailing equivocating validator {:?}",
+			validator_id.clone()
+		);
+
+		if let Some(session_validator) = T::ValidatorIdOf::convert(validator_id.clone()) {
+			pallet_session::Pallet::<T>::disable(&session_validator);
+		}
+
+		Validators::<T>::mutate(|v| v.retain(|x| *x != validator_id));
+		EpochsMissed::<T>::remove(validator_id.clone());
+		Self::jail(validator_id.clone());
+
+		Self::deposit_event(Event::ValidatorEquivocated(validator_id));
+	}
+
+	// Whether the validator has rejoin attempts left in its current window.
+	fn can_rejoin(validator_id: &T::AccountId) -> bool {
+		let (window_start, attempts) = RejoinAttempts::<T>::get(validator_id);
+
+		attempts < T::MaxRejoinAttempts::get()
+			|| frame_system::Pallet::<T>::block_number()
+				>= window_start.saturating_add(T::RejoinWindow::get())
+	}
+
+	// Number of validators counting toward `MinAuthorities` once the given one
+	// leaves the validator set. Validators under maintenance or jailed are
+	// leaving it too, and those on probation don't count, as in
+	// `update_validators`.
+	fn remaining_validators(leaving: &T::AccountId) -> u32 {
+		Self::validators()
+			.iter()
+			.filter(|v| {
+				*v != leaving
+					&& !MaintenanceWindows::<T>::contains_key(v)
+					&& !JailedValidators::<T>::contains_key(v)
+					&& !Probation::<T>::contains_key(v)
+			})
+			.count() as u32
+	}
+
+	// Whether the validator is jailed and its cooldown is not over yet.
+	fn is_jailed(validator_id: &T::AccountId) -> bool {
+		JailedValidators::<T>::get(validator_id).map_or(false, |until| {
+			frame_system::Pallet::<T>::block_number() < until
+		})
+	}
+
+	// Jails a validator for `JailDuration` blocks. It is taken out of the
+	// validator set at the end of the session, as any offline validator.
+	fn jail(validator_id: T::AccountId) {
+		let until =
+			frame_system::Pallet::<T>::block_number().saturating_add(T::JailDuration::get());
+
+		log::debug!(
+			target: LOG_TARGET,
+			"Jailing validator {:?} until {:?}",
+			validator_id.clone(),
+			until
+		);
+
+		JailedValidators::<T>::insert(validator_id.clone(), until);
+		OfflineRemovals::<T>::remove(validator_id.clone());
+		Probation::<T>::remove(validator_id.clone());
+		ToBeAddedValidators::<T>::mutate(|v| v.retain(|x| *x != validator_id));
+		MaintenanceWindows::<T>::remove(validator_id.clone());
+
+		Self::deposit_event(Event::ValidatorJailed(validator_id, until));
+	}
+
 	fn approve_validator(validator_id: T::AccountId) -> DispatchResult {
 		ensure!(
 			!<ApprovedValidators<T>>::get().contains(&validator_id),
@@ -545,24 +1335,112 @@ impl<T: Config> Pallet<T> {
 		});
 	}
 
+	// Counts the slots elapsed since the last block towards the validators that
+	// were expected to author them. A skipped slot still counts as assigned.
+	fn note_assigned_slots() {
+		let current_slot = T::SlotAuthorship::current_slot();
+		let last_slot = LastSlot::<T>::get();
+
+		if current_slot <= last_slot {
+			return;
+		}
+
+		let authorities = T::SlotAuthorship::authorities();
+		let authorities_len = authorities.len() as u64;
+
+		if authorities_len == 0 {
+			return;
+		}
+
+		LastSlot::<T>::put(current_slot);
+
+		// Nothing is known about the slots preceding the first tracked block.
+		let elapsed = if last_slot == 0 {
+			1
+		} else {
+			current_slot - last_slot
+		};
+		let first_slot = current_slot - elapsed + 1;
+		let rounds = elapsed / authorities_len;
+		let remainder = elapsed % authorities_len;
+
+		for offset in 0..elapsed.min(authorities_len) {
+			let assigned = rounds + if offset < remainder { 1 } else { 0 };
+			let slot = first_slot + offset;
+
+			if let Some(validator) = authorities.get((slot % authorities_len) as usize) {
+				CurrentSessionStats::<T>::mutate(validator, |stats| {
+					stats.slots_assigned = stats
+						.slots_assigned
+						.saturating_add(assigned.saturated_into::<u32>());
+				});
+			}
+		}
+	}
+
+	// Moves the counters of the ending session into each validator's history,
+	// keeping only the last `StatsHistoryDepth` sessions.
+	fn archive_session_stats(session_index: sp_staking::SessionIndex) {
+		let depth = T::StatsHistoryDepth::get() as usize;
+
+		for (validator, mut stats) in CurrentSessionStats::<T>::drain() {
+			stats.session_index = session_index;
+
+			ValidatorStats::<T>::mutate(validator, |history| {
+				history.push(stats);
+				let excess = history.len().saturating_sub(depth);
+				history.drain(..excess);
+			});
+		}
+	}
+
 	// Removes offline validators from the validator set and clears the offline
 	// cache. It is called in the session change hook and removes the validators
-	// who were reported offline during the session that is ending. We do not
-	// check for `MinAuthorities` here, because the offline validators will not
-	// produce blocks and will have the same overall effect on the runtime.
+	// who were reported offline during the session that is ending. Offline
+	// validators are never removed below `MinAuthorities`: the least bad ones,
+	// ranked by missed epochs and then by their last authored block, are kept.
+	// Validators under maintenance are taken out of the set. The windows that
+	// are over are closed, and those validators rejoin through heartbeats, as
+	// any offline validator does, so they go on probation and count toward
+	// the rejoin limit.
 	fn update_validators() {
+		let now = frame_system::Pallet::<T>::block_number();
+		let back_from_maintenance = MaintenanceWindows::<T>::iter()
+			.filter(|(_, until)| *until <= now)
+			.map(|(validator, _)| validator)
+			.collect::<Vec<_>>();
+
+		back_from_maintenance.into_iter().for_each(|x| {
+			MaintenanceWindows::<T>::remove(&x);
+			EpochsMissed::<T>::remove(&x);
+			Self::deposit_event(Event::MaintenanceEnded(x));
+		});
+
+		let mut offline_validators = Vec::<T::AccountId>::new();
+
 		Validators::<T>::mutate(|validators| {
 			validators.retain(|x| {
-				let missed_epochs = EpochsMissed::<T>::get(x.clone());
-				if missed_epochs < MaxMissedEpochs::<T>::get() {
-					true
-				} else {
+				if JailedValidators::<T>::contains_key(x) {
+					log::debug!(target: LOG_TARGET, "Removing jailed validator {:?}", x.clone());
+					return false;
+				}
+
+				if MaintenanceWindows::<T>::contains_key(x) {
 					log::debug!(
 						target: LOG_TARGET,
-						"Removing offline validator {:?}",
+						"Removing validator under maintenance {:?}",
 						x.clone()
 					);
-					EpochsMissed::<T>::remove(x.clone());
+					return false;
+				}
+
+				let missed_epochs = EpochsMissed::<T>::get(x.clone());
+				if missed_epochs < MaxMissedEpochs::<T>::get()
+					&& FinalityEpochsMissed::<T>::get(x.clone()) < T::MaxMissedFinalityEpochs::get()
+				{
+					true
+				} else {
+					offline_validators.push(x.clone());
 					false
 				}
 			});
@@ -571,6 +1449,61 @@ impl<T: Config> Pallet<T> {
 				log::debug!(target: LOG_TARGET, "Adding validator {:?}", x.clone());
 				validators.push(x.clone());
 			});
+
+			// Validators on probation don't count toward `MinAuthorities` and
+			// are never kept by it.
+			let min_authorities = T::MinAuthorities::get() as usize;
+			let proven = validators
+				.iter()
+				.filter(|x| !Probation::<T>::contains_key(x))
+				.count();
+			let mut kept = offline_validators
+				.iter()
+				.filter(|x| !Probation::<T>::contains_key(x))
+				.cloned()
+				.collect::<Vec<_>>();
+
+			if proven < min_authorities && !kept.is_empty() {
+				kept.sort_by(|a, b| {
+					EpochsMissed::<T>::get(a)
+						.cmp(&EpochsMissed::<T>::get(b))
+						.then_with(|| {
+							LastAuthoredBlock::<T>::get(b).cmp(&LastAuthoredBlock::<T>::get(a))
+						})
+				});
+				kept.truncate(min_authorities - proven);
+				offline_validators.retain(|x| !kept.contains(x));
+
+				log::warn!(
+					target: LOG_TARGET,
+					"Keeping offline validators {:?} to honour MinAuthorities",
+					kept
+				);
+				validators.extend(kept.iter().cloned());
+				Self::deposit_event(Event::OfflineRemovalLimited(kept));
+			}
+
+			offline_validators.into_iter().for_each(|x| {
+				log::debug!(target: LOG_TARGET, "Removing offline validator {:?}", x.clone());
+				EpochsMissed::<T>::remove(x.clone());
+				FinalityEpochsMissed::<T>::remove(x.clone());
+				Probation::<T>::remove(x.clone());
+
+				let removals =
+					OfflineRemovals::<T>::mutate(x.clone(), |(window_start, removals)| {
+						if *removals == 0
+							|| now >= window_start.saturating_add(T::OfflineRemovalWindow::get())
+						{
+							*window_start = now;
+							*removals = 0;
+						}
+						*removals = removals.saturating_add(1);
+						*removals
+					});
+				if removals >= T::MaxOfflineRemovals::get() {
+					Self::jail(x);
+				}
+			});
 		})
 	}
 }
@@ -614,20 +1547,86 @@ impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
 			i.saturating_inc();
 		}
 
+		// A justification only holds the precommits its voter collected first,
+		// so validators are only deemed to miss finality when they are missing
+		// from enough of them. Nodes without the inherent provider report none.
+		let finality_accounted = SessionFinalityJustifications::<T>::take()
+			>= T::MinFinalityJustifications::get().max(1);
+
 		for validator in validators {
+			if MaintenanceWindows::<T>::contains_key(&validator) {
+				continue;
+			}
+
 			if epoch_block_authors.contains(&validator) {
-				EpochsMissed::<T>::remove(validator);
+				EpochsMissed::<T>::remove(validator.clone());
 			} else {
-				Self::increment_missed_block(validator);
+				Self::increment_missed_block(validator.clone());
+			}
+
+			if finality_accounted {
+				if SessionFinalityVoters::<T>::contains_key(&validator) {
+					FinalityEpochsMissed::<T>::remove(validator);
+				} else {
+					FinalityEpochsMissed::<T>::mutate(validator, |v| *v = v.saturating_add(1));
+				}
 			}
 		}
 
+		let _ = SessionFinalityVoters::<T>::clear(u32::MAX, None);
+
+		Self::archive_session_stats(end_index);
+
 		Self::update_validators();
 	}
 
 	fn start_session(_start_index: u32) {}
 }
 
+// Validators are identified by their own account, which lets the session
+// historical pallet keep the key ownership proofs needed for equivocations.
+impl<T: Config> pallet_session::historical::SessionManager<T::AccountId, T::AccountId>
+	for Pallet<T>
+{
+	fn new_session(new_index: u32) -> Option<Vec<(T::AccountId, T::AccountId)>> {
+		<Self as pallet_session::SessionManager<T::AccountId>>::new_session(new_index)
+			.map(|validators| validators.into_iter().map(|v| (v.clone(), v)).collect())
+	}
+
+	fn end_session(end_index: u32) {
+		<Self as pallet_session::SessionManager<T::AccountId>>::end_session(end_index)
+	}
+
+	fn start_session(start_index: u32) {
+		<Self as pallet_session::SessionManager<T::AccountId>>::start_session(start_index)
+	}
+}
+
+// Handles the offences reported through `pallet_offences`, e.g. GRANDPA
+// equivocations. Every proven offender is taken out of consensus, whatever the
+// slash fraction, as there is no stake to slash.
+impl<T: Config> OnOffenceHandler<T::AccountId, (T::AccountId, T::AccountId), Weight> for Pallet<T> {
+	fn on_offence(
+		offenders: &[OffenceDetails<T::AccountId, (T::AccountId, T::AccountId)>],
+		_slash_fraction: &[Perbill],
+		_session: sp_staking::SessionIndex,
+	) -> Weight {
+		let mut weight = Weight::zero();
+
+		for details in offenders {
+			let (validator_id, _) = details.offender.clone();
+			weight = weight.saturating_add(T::DbWeight::get().reads(1));
+
+			if Self::approved_validators().contains(&validator_id) {
+				Self::punish_equivocation(validator_id);
+				weight = weight.saturating_add(Self::punish_equivocation_weight());
+			}
+		}
+
+		weight
+	}
+}
+
 impl<T: Config> EstimateNextSessionRotation<BlockNumberFor<T>> for Pallet<T> {
 	fn average_session_length() -> BlockNumberFor<T> {
 		Zero::zero()
@@ -691,3 +1690,59 @@ impl<T: Config> ValidatorSetWithIdentification<T::AccountId> for Pallet<T> {
 pub trait SessionBlockManager<BlockNumber> {
 	fn session_start_block(session_index: sp_staking::SessionIndex) -> BlockNumber;
 }
+
+pub trait FinalityVoters<AccountId> {
+	/// Finality votes reported by the block author.
+	type Votes: Parameter;
+	/// Authority set and round of the justification the votes come from,
+	/// increasing with every justification.
+	fn justification(votes: &Self::Votes) -> (u64, u64);
+	/// Number of votes, each checked when resolving the voters.
+	fn votes_len(votes: &Self::Votes) -> u32;
+	/// Validators that cast valid votes.
+	fn voters(votes: &Self::Votes) -> Vec<AccountId>;
+}
+
+impl<AccountId> FinalityVoters<AccountId> for () {
+	type Votes = ();
+
+	fn justification(_votes: &Self::Votes) -> (u64, u64) {
+		(0, 0)
+	}
+
+	fn votes_len(_votes: &Self::Votes) -> u32 {
+		0
+	}
+
+	fn voters(_votes: &Self::Votes) -> Vec<AccountId> {
+		Vec::new()
+	}
+}
+
+pub type FinalityVotesOf<T> = <<T as Config>::FinalityVoters as FinalityVoters<
+	<T as frame_system::Config>::AccountId,
+>>::Votes;
+
+pub trait SlotAuthorship<AccountId> {
+	/// Slot of the block being built.
+	fn current_slot() -> u64;
+	/// Number of authorities the slots are distributed among.
+	fn authorities_len() -> u32;
+	/// Validators the slots are distributed among, in order: the author of a
+	/// slot is the one at the slot modulo their number.
+	fn authorities() -> Vec<AccountId>;
+}
+
+impl<AccountId> SlotAuthorship<AccountId> for () {
+	fn current_slot() -> u64 {
+		0
+	}
+
+	fn authorities_len() -> u32 {
+		0
+	}
+
+	fn authorities() -> Vec<AccountId> {
+		Vec::new()
+	}
+}
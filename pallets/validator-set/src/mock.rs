@@ -212,6 +212,31 @@ impl FindAuthor<u64> for FindBlockAuthorityId {
 	}
 }
 
+pub struct SlotPrefix;
+impl StorageInstance for SlotPrefix {
+	fn pallet_prefix() -> &'static str {
+		"test"
+	}
+	const STORAGE_PREFIX: &'static str = "slot";
+}
+
+pub type NextBlockSlot = StorageValue<SlotPrefix, u64, ValueQuery>;
+
+pub struct MockSlotAuthorship;
+impl SlotAuthorship<u64> for MockSlotAuthorship {
+	fn current_slot() -> u64 {
+		NextBlockSlot::get()
+	}
+
+	fn authorities_len() -> u32 {
+		Session::validators().len() as u32
+	}
+
+	fn authorities() -> Vec<u64> {
+		Session::validators()
+	}
+}
+
 impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
 where
 	RuntimeCall: From<C>,
@@ -225,18 +250,62 @@ impl Convert<UintAuthorityId, u64> for AccountIdOfValidator {
 		authority_id.0.clone()
 	}
 }
+/// Votes are the round of the justification and the validators that voted.
+pub struct MockFinalityVoters;
+impl validator_set::FinalityVoters<u64> for MockFinalityVoters {
+	type Votes = (u64, Vec<u64>);
+
+	fn justification(votes: &(u64, Vec<u64>)) -> (u64, u64) {
+		(0, votes.0)
+	}
+
+	fn votes_len(votes: &(u64, Vec<u64>)) -> u32 {
+		votes.1.len() as u32
+	}
+
+	fn voters(votes: &(u64, Vec<u64>)) -> Vec<u64> {
+		votes.1.clone()
+	}
+}
 parameter_types! {
 	pub const MaxKeys: u32 = 100;
+	pub const StatsHistoryDepth: u32 = 2;
+	pub const MaxMaintenanceDuration: u64 = 100;
+	pub static MinAuthorities: u32 = 0;
+	pub const MaxMetadataLength: u32 = 32;
+	pub const JailDuration: u64 = 50;
+	pub static MaxOfflineRemovals: u32 = 2;
+	pub const OfflineRemovalWindow: u64 = 100;
+	pub const MaxRejoinAttempts: u32 = 2;
+	pub const RejoinWindow: u64 = 100;
+	pub const MaxMissedFinalityEpochs: u32 = 2;
+	pub static MinFinalityJustifications: u32 = 1;
+	pub const MaxFinalityVotes: u32 = 3;
+	pub const EquivocationReportLongevity: u64 = 100;
 }
 impl validator_set::Config for Test {
 	type AddRemoveOrigin = EnsureRoot<Self::AccountId>;
 	type RuntimeEvent = RuntimeEvent;
-	type MinAuthorities = ();
+	type MinAuthorities = MinAuthorities;
 	type SessionBlockManager = PeriodicSessionBlockManager;
 	type FindAuthor = FindBlockAuthorityId;
 	type AuthorityId = UintAuthorityId;
 	type MaxKeys = MaxKeys;
 	type AccountIdOfValidator = AccountIdOfValidator;
+	type SlotAuthorship = MockSlotAuthorship;
+	type StatsHistoryDepth = StatsHistoryDepth;
+	type MaxMaintenanceDuration = MaxMaintenanceDuration;
+	type MaxMetadataLength = MaxMetadataLength;
+	type JailDuration = JailDuration;
+	type MaxOfflineRemovals = MaxOfflineRemovals;
+	type OfflineRemovalWindow = OfflineRemovalWindow;
+	type MaxRejoinAttempts = MaxRejoinAttempts;
+	type RejoinWindow = RejoinWindow;
+	type FinalityVoters = MockFinalityVoters;
+	type MaxMissedFinalityEpochs = MaxMissedFinalityEpochs;
+	type MinFinalityJustifications = MinFinalityJustifications;
+	type MaxFinalityVotes = MaxFinalityVotes;
+	type EquivocationReportLongevity = EquivocationReportLongevity;
 }
 
 impl pallet_session::Config for Test {
@@ -23,14 +23,18 @@
 
 use super::*;
 use crate::mock::{
-	authorities, ExtBuilder, NextBlockValidator, RuntimeOrigin, Session, Test, ValidatorSet,
-	SESSION_BLOCK_LENGTH,
+	authorities, EquivocationReportLongevity, ExtBuilder, MinAuthorities,
+	MinFinalityJustifications, NextBlockSlot, NextBlockValidator, RuntimeEvent, RuntimeOrigin,
+	Session, System, Test, ValidatorSet, SESSION_BLOCK_LENGTH,
 };
 use frame_support::{assert_noop, assert_ok, pallet_prelude::*};
 use frame_system::RawOrigin;
 use sp_application_crypto::RuntimeAppPublic;
-use sp_core::U256;
-use sp_runtime::testing::UintAuthorityId;
+use sp_core::{H256, U256};
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	Digest,
+};
 
 #[test]
 fn simple_setup_should_work() {
@@ -74,6 +78,31 @@ fn add_validator_fails_with_invalid_origin() {
 	});
 }
 
+#[test]
+fn validators_on_probation_do_not_count_toward_min_authorities() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		MinAuthorities::set(2);
+		Probation::<Test>::insert(3, 1);
+
+		assert_noop!(
+			ValidatorSet::remove_validator(RuntimeOrigin::root(), 2),
+			Error::<Test>::TooLowValidatorCount
+		);
+		assert_noop!(
+			ValidatorSet::announce_maintenance(RuntimeOrigin::signed(2), 20),
+			Error::<Test>::TooLowValidatorCount
+		);
+		assert_noop!(
+			ValidatorSet::jail_validator(RuntimeOrigin::root(), 2),
+			Error::<Test>::TooLowValidatorCount
+		);
+
+		Probation::<Test>::remove(3);
+		assert_ok!(ValidatorSet::remove_validator(RuntimeOrigin::root(), 2));
+	});
+}
+
 #[test]
 fn remove_validator_fails_with_invalid_origin() {
 	ExtBuilder::build().execute_with(|| {
@@ -238,9 +267,911 @@ fn non_approved_validator_tries_to_connect() {
 	});
 }
 
+#[test]
+fn session_stats_track_authored_blocks_and_assigned_slots() {
+	ExtBuilder::build().execute_with(|| {
+		mock_mine_block_at_slot(2, 0, 1);
+		mock_mine_block_at_slot(3, 1, 2);
+		// Slot 3 is skipped by validator 1
+		mock_mine_block_at_slot(2, 2, 4);
+
+		assert_eq!(ValidatorSet::current_session_stats(2).blocks_authored, 2);
+		assert_eq!(ValidatorSet::current_session_stats(2).slots_assigned, 2);
+
+		<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(0);
+
+		assert_eq!(
+			ValidatorSet::validator_stats(1),
+			vec![SessionStats {
+				session_index: 0,
+				blocks_authored: 0,
+				slots_assigned: 1,
+			}]
+		);
+		assert_eq!(
+			ValidatorSet::validator_stats(2),
+			vec![SessionStats {
+				session_index: 0,
+				blocks_authored: 2,
+				slots_assigned: 2,
+			}]
+		);
+		assert_eq!(
+			ValidatorSet::validator_stats(3),
+			vec![SessionStats {
+				session_index: 0,
+				blocks_authored: 1,
+				slots_assigned: 1,
+			}]
+		);
+		assert_eq!(
+			ValidatorSet::current_session_stats(2),
+			SessionStats::default()
+		);
+	});
+}
+
+#[test]
+fn session_stats_count_every_slot_of_a_long_gap() {
+	ExtBuilder::build().execute_with(|| {
+		mock_mine_block_at_slot(2, 0, 1);
+		// Slots 2 to 7 are skipped
+		mock_mine_block_at_slot(3, 1, 8);
+
+		assert_eq!(ValidatorSet::current_session_stats(1).slots_assigned, 2);
+		assert_eq!(ValidatorSet::current_session_stats(2).slots_assigned, 3);
+		assert_eq!(ValidatorSet::current_session_stats(3).slots_assigned, 3);
+	});
+}
+
+#[test]
+fn session_stats_history_is_bounded() {
+	ExtBuilder::build().execute_with(|| {
+		for session in 0..3u32 {
+			let block = session as u64 * SESSION_BLOCK_LENGTH;
+			mock_mine_block_at_slot(1, block, block + 3);
+			<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(session);
+		}
+
+		let sessions = ValidatorSet::validator_stats(1)
+			.iter()
+			.map(|stats| stats.session_index)
+			.collect::<Vec<_>>();
+
+		assert_eq!(sessions, vec![1, 2]);
+	});
+}
+
+#[test]
+fn validator_in_maintenance_leaves_without_missing_epochs() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ValidatorSet::announce_maintenance(
+			RuntimeOrigin::signed(2),
+			20
+		));
+
+		for session in 0..2u32 {
+			for i in 0..SESSION_BLOCK_LENGTH {
+				mock_mine_block(1, i + session as u64 * SESSION_BLOCK_LENGTH);
+			}
+			<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(session);
+
+			assert!(!ValidatorSet::validators().contains(&2));
+			assert_eq!(EpochsMissed::<Test>::get(2), U256::zero());
+		}
+
+		System::set_block_number(20);
+		<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(2);
+
+		assert!(!ValidatorSet::validators().contains(&2));
+		assert_eq!(ValidatorSet::maintenance_windows(2), None);
+		System::assert_has_event(RuntimeEvent::ValidatorSet(Event::MaintenanceEnded(2)));
+
+		// The validator rejoins as any offline validator.
+		let (heartbeat, signature) = heartbeat_of(2);
+		assert_ok!(ValidatorSet::add_validator_again(
+			RawOrigin::None.into(),
+			heartbeat,
+			signature
+		));
+		assert_eq!(ValidatorSet::probation_since(2), Some(20));
+		assert_eq!(ValidatorSet::rejoin_attempts(2).1, 1);
+	});
+}
+
+#[test]
+fn end_maintenance_lets_validator_rejoin() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ValidatorSet::announce_maintenance(
+			RuntimeOrigin::signed(2),
+			50
+		));
+		<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(0);
+		assert!(!ValidatorSet::validators().contains(&2));
+
+		assert_ok!(ValidatorSet::end_maintenance(RuntimeOrigin::signed(2)));
+		<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(1);
+
+		assert!(!ValidatorSet::validators().contains(&2));
+		assert_noop!(
+			ValidatorSet::end_maintenance(RuntimeOrigin::signed(2)),
+			Error::<Test>::NotInMaintenance
+		);
+
+		let (heartbeat, signature) = heartbeat_of(2);
+		assert_ok!(ValidatorSet::add_validator_again(
+			RawOrigin::None.into(),
+			heartbeat,
+			signature
+		));
+		assert!(ValidatorSet::to_be_added_validators().contains(&2));
+	});
+}
+
+#[test]
+fn announce_maintenance_fails_outside_validator_set() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		Validators::<Test>::mutate(|v| v.retain(|x| *x != 2));
+
+		assert_noop!(
+			ValidatorSet::announce_maintenance(RuntimeOrigin::signed(2), 20),
+			Error::<Test>::NotInValidatorSet
+		);
+	});
+}
+
+#[test]
+fn announce_maintenance_fails_for_non_approved_validator() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			ValidatorSet::announce_maintenance(RuntimeOrigin::signed(4), 20),
+			Error::<Test>::ValidatorNotApproved
+		);
+	});
+}
+
+#[test]
+fn announce_maintenance_fails_with_invalid_window() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(10);
+		assert_noop!(
+			ValidatorSet::announce_maintenance(RuntimeOrigin::signed(2), 10),
+			Error::<Test>::InvalidMaintenanceWindow
+		);
+		assert_noop!(
+			ValidatorSet::announce_maintenance(RuntimeOrigin::signed(2), 111),
+			Error::<Test>::InvalidMaintenanceWindow
+		);
+		assert_ok!(ValidatorSet::announce_maintenance(
+			RuntimeOrigin::signed(2),
+			110
+		));
+		assert_noop!(
+			ValidatorSet::announce_maintenance(RuntimeOrigin::signed(2), 20),
+			Error::<Test>::AlreadyInMaintenance
+		);
+	});
+}
+
+#[test]
+fn heartbeat_is_rejected_during_maintenance() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ValidatorSet::announce_maintenance(
+			RuntimeOrigin::signed(2),
+			20
+		));
+		<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(0);
+
+		let heartbeat = Heartbeat {
+			block_number: 7,
+			session_index: 1,
+			authority_id: UintAuthorityId(2),
+			authority_index: 1,
+		};
+
+		let signature = UintAuthorityId(2).sign(&heartbeat.encode()).unwrap();
+
+		assert_noop!(
+			pallet::Pallet::<Test>::add_validator_again(
+				RawOrigin::None.into(),
+				heartbeat,
+				signature
+			),
+			Error::<Test>::AlreadyInMaintenance
+		);
+	});
+}
+
+#[test]
+fn offline_removal_keeps_min_authorities() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		MinAuthorities::set(2);
+
+		mock_mine_block(2, 0);
+		mock_mine_block(3, 1);
+		for i in 2..SESSION_BLOCK_LENGTH {
+			mock_mine_block(1, i);
+		}
+		<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(0);
+
+		for session in 1..3u32 {
+			for i in 0..SESSION_BLOCK_LENGTH {
+				mock_mine_block(1, i + session as u64 * SESSION_BLOCK_LENGTH);
+			}
+			<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(session);
+		}
+
+		// Both missed the same epochs, 3 authored a block more recently.
+		assert_eq!(ValidatorSet::validators(), vec![1, 3]);
+		assert_eq!(EpochsMissed::<Test>::get(2), U256::zero());
+		assert_eq!(EpochsMissed::<Test>::get(3), U256::from(2));
+		System::assert_has_event(RuntimeEvent::ValidatorSet(Event::OfflineRemovalLimited(
+			vec![3],
+		)));
+	});
+}
+
+#[test]
+fn offline_removal_prefers_fewer_missed_epochs() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		MinAuthorities::set(2);
+		EpochsMissed::<Test>::insert(2, U256::from(3));
+		EpochsMissed::<Test>::insert(3, U256::from(2));
+		LastAuthoredBlock::<Test>::insert(2, 5);
+
+		for i in 0..SESSION_BLOCK_LENGTH {
+			mock_mine_block(1, i);
+		}
+		<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(0);
+
+		assert_eq!(ValidatorSet::validators(), vec![1, 3]);
+	});
+}
+
+#[test]
+fn offline_removal_is_not_limited_above_min_authorities() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		MinAuthorities::set(1);
+
+		for session in 0..2u32 {
+			for i in 0..SESSION_BLOCK_LENGTH {
+				mock_mine_block(1, i + session as u64 * SESSION_BLOCK_LENGTH);
+			}
+			<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(session);
+		}
+
+		assert_eq!(ValidatorSet::validators(), vec![1]);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::ValidatorSet(Event::OfflineRemovalLimited(_))
+		)));
+	});
+}
+
+fn validator_metadata(display_name: &[u8]) -> ValidatorMetadata<u64> {
+	ValidatorMetadata {
+		display_name: display_name.to_vec(),
+		website: b"https://stability.test".to_vec(),
+		contact: b"ops@stability.test".to_vec(),
+		peer_id: b"12D3KooWExample".to_vec(),
+		payout_address: 42,
+	}
+}
+
+#[test]
+fn approved_validator_sets_its_metadata() {
+	ExtBuilder::build().execute_with(|| {
+		assert_ok!(ValidatorSet::set_validator_metadata(
+			RuntimeOrigin::signed(1),
+			validator_metadata(b"Validator 1")
+		));
+		assert_eq!(
+			ValidatorSet::validator_metadata(1),
+			Some(validator_metadata(b"Validator 1"))
+		);
+
+		assert_ok!(ValidatorSet::remove_validator(RuntimeOrigin::root(), 1));
+		assert_eq!(ValidatorSet::validator_metadata(1), None);
+	});
+}
+
+#[test]
+fn set_validator_metadata_fails_for_non_approved_validator() {
+	ExtBuilder::build().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::set_validator_metadata(
+				RuntimeOrigin::signed(4),
+				validator_metadata(b"Validator 4")
+			),
+			Error::<Test>::ValidatorNotApproved
+		);
+	});
+}
+
+#[test]
+fn set_validator_metadata_fails_with_too_long_fields() {
+	ExtBuilder::build().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::set_validator_metadata(
+				RuntimeOrigin::signed(1),
+				validator_metadata(b"A validator name that is too long")
+			),
+			Error::<Test>::MetadataTooLong
+		);
+	});
+}
+
+#[test]
+fn aura_equivocation_removes_and_jails_validator() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(ValidatorSet::report_aura_equivocation(
+			RawOrigin::None.into(),
+			Box::new(aura_equivocation_proof(2, 7, 7))
+		));
+
+		assert!(!ValidatorSet::validators().contains(&2));
+		assert_eq!(ValidatorSet::jailed_until(2), Some(51));
+		assert!(Session::disabled_validators().contains(&1));
+		System::assert_has_event(RuntimeEvent::ValidatorSet(Event::ValidatorEquivocated(2)));
+	});
+}
+
+#[test]
+fn aura_equivocation_is_reported_once() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(ValidatorSet::report_aura_equivocation(
+			RawOrigin::None.into(),
+			Box::new(aura_equivocation_proof(2, 7, 7))
+		));
+		assert_eq!(ValidatorSet::reported_aura_equivocation(2, 7), Some(()));
+
+		// The same proof can't jail the validator again once it is released.
+		assert_ok!(ValidatorSet::unjail_validator(RawOrigin::Root.into(), 2));
+		let call = Call::report_aura_equivocation {
+			equivocation_proof: Box::new(aura_equivocation_proof(2, 7, 7)),
+		};
+		assert!(ValidatorSet::validate_unsigned(TransactionSource::External, &call).is_err());
+		assert_noop!(
+			ValidatorSet::report_aura_equivocation(
+				RawOrigin::None.into(),
+				Box::new(aura_equivocation_proof(2, 7, 7))
+			),
+			Error::<Test>::InvalidEquivocationProof
+		);
+	});
+}
+
+#[test]
+fn aura_equivocation_fails_when_too_old() {
+	ExtBuilder::build().execute_with(|| {
+		NextBlockSlot::set(7 + EquivocationReportLongevity::get() + 1);
+
+		assert_noop!(
+			ValidatorSet::report_aura_equivocation(
+				RawOrigin::None.into(),
+				Box::new(aura_equivocation_proof(2, 7, 7))
+			),
+			Error::<Test>::InvalidEquivocationProof
+		);
+
+		NextBlockSlot::set(7 + EquivocationReportLongevity::get());
+		assert_ok!(ValidatorSet::report_aura_equivocation(
+			RawOrigin::None.into(),
+			Box::new(aura_equivocation_proof(2, 7, 7))
+		));
+	});
+}
+
+#[test]
+fn aura_equivocation_fails_with_invalid_proof() {
+	ExtBuilder::build().execute_with(|| {
+		// Headers sealed for different slots.
+		assert_noop!(
+			ValidatorSet::report_aura_equivocation(
+				RawOrigin::None.into(),
+				Box::new(aura_equivocation_proof(2, 7, 8))
+			),
+			Error::<Test>::InvalidEquivocationProof
+		);
+
+		// The same header twice.
+		let mut proof = aura_equivocation_proof(2, 7, 7);
+		proof.second_header = proof.first_header.clone();
+		assert_noop!(
+			ValidatorSet::report_aura_equivocation(RawOrigin::None.into(), Box::new(proof)),
+			Error::<Test>::InvalidEquivocationProof
+		);
+
+		// Headers sealed by someone else than the offender.
+		let mut proof = aura_equivocation_proof(2, 7, 7);
+		proof.offender = UintAuthorityId(3);
+		assert_noop!(
+			ValidatorSet::report_aura_equivocation(RawOrigin::None.into(), Box::new(proof)),
+			Error::<Test>::InvalidEquivocationProof
+		);
+	});
+}
+
+#[test]
+fn offence_removes_and_jails_validator() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+
+		<ValidatorSet as OnOffenceHandler<u64, (u64, u64), Weight>>::on_offence(
+			&[OffenceDetails {
+				offender: (3, 3),
+				reporters: vec![],
+			}],
+			&[Perbill::from_percent(10)],
+			0,
+		);
+
+		assert_eq!(ValidatorSet::validators(), vec![1, 2]);
+		assert_eq!(ValidatorSet::jailed_until(3), Some(51));
+		System::assert_has_event(RuntimeEvent::ValidatorSet(Event::ValidatorEquivocated(3)));
+	});
+}
+
+#[test]
+fn jailed_validator_leaves_at_end_of_session() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ValidatorSet::jail_validator(RuntimeOrigin::root(), 2));
+		System::assert_last_event(RuntimeEvent::ValidatorSet(Event::ValidatorJailed(2, 51)));
+
+		<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(0);
+
+		assert_eq!(ValidatorSet::validators(), vec![1, 3]);
+		assert!(ValidatorSet::approved_validators().contains(&2));
+	});
+}
+
+#[test]
+fn jail_validator_fails_with_invalid_origin() {
+	ExtBuilder::build().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::jail_validator(RuntimeOrigin::signed(1), 2),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ValidatorSet::jail_validator(RuntimeOrigin::root(), 4),
+			Error::<Test>::ValidatorNotApproved
+		);
+	});
+}
+
+#[test]
+fn jail_validator_keeps_min_authorities() {
+	ExtBuilder::build().execute_with(|| {
+		MinAuthorities::set(2);
+		assert_ok!(ValidatorSet::jail_validator(RuntimeOrigin::root(), 2));
+
+		assert_noop!(
+			ValidatorSet::jail_validator(RuntimeOrigin::root(), 3),
+			Error::<Test>::TooLowValidatorCount
+		);
+	});
+}
+
+#[test]
+fn heartbeat_is_rejected_while_jailed() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ValidatorSet::jail_validator(RuntimeOrigin::root(), 2));
+		<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(0);
+
+		let heartbeat = Heartbeat {
+			block_number: 7,
+			session_index: 1,
+			authority_id: UintAuthorityId(2),
+			authority_index: 1,
+		};
+		let signature = UintAuthorityId(2).sign(&heartbeat.encode()).unwrap();
+
+		assert_noop!(
+			pallet::Pallet::<Test>::add_validator_again(
+				RawOrigin::None.into(),
+				heartbeat.clone(),
+				signature.clone()
+			),
+			Error::<Test>::Jailed
+		);
+
+		// The cooldown is over.
+		System::set_block_number(51);
+		assert_ok!(pallet::Pallet::<Test>::add_validator_again(
+			RawOrigin::None.into(),
+			heartbeat,
+			signature
+		));
+		assert_eq!(ValidatorSet::jailed_until(2), None);
+
+		<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(1);
+
+		assert!(ValidatorSet::validators().contains(&2));
+	});
+}
+
+#[test]
+fn unjail_validator_lifts_cooldown() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ValidatorSet::jail_validator(RuntimeOrigin::root(), 2));
+		<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(0);
+
+		assert_ok!(ValidatorSet::unjail_validator(RuntimeOrigin::root(), 2));
+		System::assert_last_event(RuntimeEvent::ValidatorSet(Event::ValidatorUnjailed(2)));
+		assert_eq!(ValidatorSet::jailed_until(2), None);
+
+		let heartbeat = Heartbeat {
+			block_number: 7,
+			session_index: 1,
+			authority_id: UintAuthorityId(2),
+			authority_index: 1,
+		};
+		let signature = UintAuthorityId(2).sign(&heartbeat.encode()).unwrap();
+
+		assert_ok!(pallet::Pallet::<Test>::add_validator_again(
+			RawOrigin::None.into(),
+			heartbeat,
+			signature
+		));
+
+		assert_noop!(
+			ValidatorSet::unjail_validator(RuntimeOrigin::root(), 2),
+			Error::<Test>::NotJailed
+		);
+	});
+}
+
+#[test]
+fn flapping_validator_is_jailed() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+
+		for session in 0..2u32 {
+			EpochsMissed::<Test>::insert(2, U256::one());
+			mock_mine_block(1, session as u64 * SESSION_BLOCK_LENGTH);
+			mock_mine_block(3, session as u64 * SESSION_BLOCK_LENGTH + 1);
+			<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(session);
+			assert!(!ValidatorSet::validators().contains(&2));
+
+			if session == 0 {
+				assert_eq!(ValidatorSet::offline_removals(2), (1, 1));
+				// The validator rejoins the set.
+				Validators::<Test>::mutate(|v| v.push(2));
+			}
+		}
+
+		assert_eq!(ValidatorSet::offline_removals(2), (0, 0));
+		assert_eq!(ValidatorSet::jailed_until(2), Some(51));
+		System::assert_has_event(RuntimeEvent::ValidatorSet(Event::ValidatorJailed(2, 51)));
+	});
+}
+
+#[test]
+fn offline_removals_are_counted_within_a_window() {
+	ExtBuilder::build().execute_with(|| {
+		for session in 0..2u32 {
+			// Removals are further apart than the window.
+			System::set_block_number(1 + session as u64 * 100);
+			EpochsMissed::<Test>::insert(2, U256::one());
+			mock_mine_block(1, session as u64 * SESSION_BLOCK_LENGTH);
+			mock_mine_block(3, session as u64 * SESSION_BLOCK_LENGTH + 1);
+			<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(session);
+			assert!(!ValidatorSet::validators().contains(&2));
+
+			// The validator rejoins the set.
+			Validators::<Test>::mutate(|v| v.push(2));
+		}
+
+		assert_eq!(ValidatorSet::offline_removals(2), (101, 1));
+		assert_eq!(ValidatorSet::jailed_until(2), None);
+	});
+}
+
+fn heartbeat_of(validator: u64) -> (Heartbeat<u64, UintAuthorityId>, TestSignature) {
+	let heartbeat = Heartbeat {
+		block_number: 7,
+		session_index: 1,
+		authority_id: UintAuthorityId(validator),
+		authority_index: (validator - 1) as u32,
+	};
+	let signature = UintAuthorityId(validator)
+		.sign(&heartbeat.encode())
+		.unwrap();
+
+	(heartbeat, signature)
+}
+
+#[test]
+fn rejoining_validator_is_on_probation_until_it_authors() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		Validators::<Test>::put(vec![1, 3]);
+
+		let (heartbeat, signature) = heartbeat_of(2);
+		assert_ok!(ValidatorSet::add_validator_again(
+			RawOrigin::None.into(),
+			heartbeat,
+			signature
+		));
+		assert_eq!(ValidatorSet::probation_since(2), Some(1));
+		assert_eq!(ValidatorSet::rejoin_attempts(2), (1, 1));
+
+		mock_mine_block(2, 1);
+
+		assert_eq!(ValidatorSet::probation_since(2), None);
+		System::assert_last_event(RuntimeEvent::ValidatorSet(Event::ProbationPassed(2)));
+	});
+}
+
+#[test]
+fn validator_on_probation_does_not_count_toward_min_authorities() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		MinAuthorities::set(2);
+		Probation::<Test>::insert(2, 1);
+		EpochsMissed::<Test>::insert(3, U256::one());
+
+		mock_mine_block(1, 0);
+		<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(0);
+
+		// Only 1 has proven to be live, so the offline 3 is kept.
+		assert_eq!(ValidatorSet::validators(), vec![1, 2, 3]);
+		System::assert_has_event(RuntimeEvent::ValidatorSet(Event::OfflineRemovalLimited(
+			vec![3],
+		)));
+	});
+}
+
+#[test]
+fn rejoin_attempts_are_capped() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		Validators::<Test>::put(vec![1, 3]);
+		RejoinAttempts::<Test>::insert(2, (1, 2));
+
+		let (heartbeat, signature) = heartbeat_of(2);
+		assert_noop!(
+			ValidatorSet::add_validator_again(
+				RawOrigin::None.into(),
+				heartbeat.clone(),
+				signature.clone()
+			),
+			Error::<Test>::TooManyRejoinAttempts
+		);
+
+		// A new window starts.
+		System::set_block_number(101);
+		assert_ok!(ValidatorSet::add_validator_again(
+			RawOrigin::None.into(),
+			heartbeat,
+			signature
+		));
+		assert_eq!(ValidatorSet::rejoin_attempts(2), (101, 1));
+	});
+}
+
+#[test]
+fn finality_votes_end_probation() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		Probation::<Test>::insert(2, 1);
+
+		assert_ok!(ValidatorSet::note_finality_votes(
+			RawOrigin::None.into(),
+			(1, vec![2, 2])
+		));
+
+		assert_eq!(ValidatorSet::probation_since(2), None);
+		assert_eq!(ValidatorSet::session_finality_voters(2), Some(()));
+		System::assert_last_event(RuntimeEvent::ValidatorSet(Event::ProbationPassed(2)));
+	});
+}
+
+#[test]
+fn finality_votes_are_reported_through_an_inherent() {
+	ExtBuilder::build().execute_with(|| {
+		let mut data = InherentData::new();
+		assert_eq!(ValidatorSet::create_inherent(&data), None);
+
+		data.put_data(
+			stbl_primitives_finality_votes::INHERENT_IDENTIFIER,
+			&(1u64, vec![1u64, 3]),
+		)
+		.unwrap();
+		let call = ValidatorSet::create_inherent(&data).unwrap();
+
+		assert!(ValidatorSet::is_inherent(&call));
+		assert_eq!(
+			call,
+			Call::note_finality_votes {
+				votes: (1, vec![1, 3])
+			}
+		);
+
+		// The same justification is not reported again once noted.
+		assert_ok!(ValidatorSet::note_finality_votes(
+			RawOrigin::None.into(),
+			(1, vec![1, 3])
+		));
+		assert_eq!(ValidatorSet::create_inherent(&data), None);
+	});
+}
+
+#[test]
+fn finality_votes_of_a_justification_are_noted_once() {
+	ExtBuilder::build().execute_with(|| {
+		assert_ok!(ValidatorSet::note_finality_votes(
+			RawOrigin::None.into(),
+			(2, vec![1])
+		));
+		assert_ok!(ValidatorSet::note_finality_votes(
+			RawOrigin::None.into(),
+			(2, vec![1, 2])
+		));
+		assert_ok!(ValidatorSet::note_finality_votes(
+			RawOrigin::None.into(),
+			(1, vec![3])
+		));
+
+		assert_eq!(ValidatorSet::last_finality_justification(), Some((0, 2)));
+		assert_eq!(ValidatorSet::session_finality_justifications(), 1);
+		assert_eq!(ValidatorSet::session_finality_voters(1), Some(()));
+		assert_eq!(ValidatorSet::session_finality_voters(2), None);
+	});
+}
+
+#[test]
+fn finality_votes_are_bounded() {
+	ExtBuilder::build().execute_with(|| {
+		let votes = (1u64, vec![1u64, 2, 3, 4]);
+		assert_noop!(
+			ValidatorSet::note_finality_votes(RawOrigin::None.into(), votes.clone()),
+			Error::<Test>::TooManyFinalityVotes
+		);
+
+		let mut data = InherentData::new();
+		data.put_data(stbl_primitives_finality_votes::INHERENT_IDENTIFIER, &votes)
+			.unwrap();
+		assert_eq!(ValidatorSet::create_inherent(&data), None);
+
+		let call = Call::note_finality_votes { votes };
+		assert!(ValidatorSet::check_inherent(&call, &data).is_err());
+	});
+}
+
+#[test]
+fn stale_finality_votes_fail_the_inherent_check() {
+	ExtBuilder::build().execute_with(|| {
+		let data = InherentData::new();
+		let call = Call::note_finality_votes {
+			votes: (2, vec![1, 2]),
+		};
+		assert!(ValidatorSet::check_inherent(&call, &data).is_ok());
+
+		assert_ok!(ValidatorSet::note_finality_votes(
+			RawOrigin::None.into(),
+			(2, vec![1, 2])
+		));
+		assert!(ValidatorSet::check_inherent(&call, &data).is_err());
+	});
+}
+
+#[test]
+fn validator_missing_finality_votes_is_removed() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+
+		for session in 0..2u32 {
+			let start = session as u64 * SESSION_BLOCK_LENGTH;
+			mock_mine_block(1, start);
+			mock_mine_block(2, start + 1);
+			mock_mine_block(3, start + 2);
+			assert_ok!(ValidatorSet::note_finality_votes(
+				RawOrigin::None.into(),
+				(session as u64 + 1, vec![1, 2])
+			));
+			<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(session);
+
+			if session == 0 {
+				assert_eq!(ValidatorSet::finality_epochs_missed(3), 1);
+				assert_eq!(ValidatorSet::validators(), vec![1, 2, 3]);
+			}
+		}
+
+		assert_eq!(ValidatorSet::validators(), vec![1, 2]);
+		assert_eq!(ValidatorSet::finality_epochs_missed(3), 0);
+		assert_eq!(SessionFinalityVoters::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn finality_is_not_accounted_without_votes() {
+	ExtBuilder::build().execute_with(|| {
+		for session in 0..3u32 {
+			let start = session as u64 * SESSION_BLOCK_LENGTH;
+			mock_mine_block(1, start);
+			mock_mine_block(2, start + 1);
+			mock_mine_block(3, start + 2);
+			<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(session);
+		}
+
+		assert_eq!(ValidatorSet::validators(), vec![1, 2, 3]);
+		assert_eq!(ValidatorSet::finality_epochs_missed(3), 0);
+	});
+}
+
+#[test]
+fn finality_is_not_accounted_with_too_few_justifications() {
+	ExtBuilder::build().execute_with(|| {
+		MinFinalityJustifications::set(2);
+
+		mock_mine_block(1, 0);
+		mock_mine_block(2, 1);
+		mock_mine_block(3, 2);
+		assert_ok!(ValidatorSet::note_finality_votes(
+			RawOrigin::None.into(),
+			(1, vec![1, 2])
+		));
+		<pallet::Pallet<Test> as pallet_session::SessionManager<u64>>::end_session(0);
+
+		assert_eq!(ValidatorSet::finality_epochs_missed(3), 0);
+		assert_eq!(ValidatorSet::session_finality_justifications(), 0);
+	});
+}
+
 // tools
 
 fn mock_mine_block(validator: u64, block_number: u64) {
 	NextBlockValidator::set(Some(validator));
 	pallet::Pallet::<Test>::on_finalize(block_number);
 }
+
+fn mock_mine_block_at_slot(validator: u64, block_number: u64, slot: u64) {
+	NextBlockSlot::set(slot);
+	mock_mine_block(validator, block_number);
+}
+
+fn aura_sealed_header(author: u64, slot: u64, parent_hash: H256) -> HeaderFor<Test> {
+	let mut header = HeaderFor::<Test>::new(
+		1,
+		Default::default(),
+		Default::default(),
+		parent_hash,
+		Digest {
+			logs: vec![DigestItem::PreRuntime(AURA_ENGINE_ID, slot.encode())],
+		},
+	);
+	let signature = UintAuthorityId(author).sign(&header.hash()).unwrap();
+	header
+		.digest_mut()
+		.push(DigestItem::Seal(AURA_ENGINE_ID, signature.encode()));
+	header
+}
+
+fn aura_equivocation_proof(
+	author: u64,
+	first_slot: u64,
+	second_slot: u64,
+) -> AuraEquivocationProof<Test> {
+	AuraEquivocationProof::<Test> {
+		offender: UintAuthorityId(author),
+		slot: first_slot.into(),
+		first_header: aura_sealed_header(author, first_slot, H256::repeat_byte(1)),
+		second_header: aura_sealed_header(author, second_slot, H256::repeat_byte(2)),
+	}
+}
@@ -4,7 +4,17 @@ pragma solidity >=0.8.3;
 interface FeeRewardsVaultController {
     event RewardClaimed(address dapp, address claimer, address token);
     event WhitelistStatusUpdated(address dapp, bool isWhitelisted);
+    event FrozenStatusUpdated(address holder, bool isFrozen);
+    event PayoutAddressUpdated(address indexed holder, address indexed payoutAddress);
+    event ClaimDelegateUpdated(address indexed holder, address indexed delegate);
+    event AutoPayoutUpdated(address indexed holder, uint256 minimum);
+    event AutoPayoutDisabled(address indexed holder);
     event ValidatorPercentageUpdated(uint256 validatorPercentage);
+    event ValidatorShareUpdated(uint256 validatorShare);
+    event TreasuryShareUpdated(uint256 treasuryShare);
+    event TreasuryUpdated(address indexed treasury);
+    event DappShareUpdated(address indexed dapp, uint256 dappShare);
+    event DappShareCleared(address indexed dapp);
     event TransactionFee(
         address token,
         uint256 totalFee,
@@ -16,18 +26,77 @@ interface FeeRewardsVaultController {
 
     function claimReward(address dapp, address token) external;
 
+    function claimAll(address dapp) external;
+
     function setWhitelisted(address dapp, bool isWhitelisted) external; // onlyOwner
 
     function canClaimReward(address, address) external view returns (bool);
 
+    function getPayoutAddress(address holder) external view returns (address);
+
+    // Only the holder or its owner. The zero address sends rewards to the claimant again
+    function setPayoutAddress(
+        address holder,
+        address payoutAddress
+    ) external returns (bool);
+
+    function getClaimDelegate(address holder) external view returns (address);
+
+    // Only the holder or its owner. The zero address removes the delegate
+    function setClaimDelegate(
+        address holder,
+        address delegate
+    ) external returns (bool);
+
+    function getAutoPayout(
+        address holder
+    ) external view returns (bool enabled, uint256 minimum);
+
+    // Only the holder or its owner
+    function setAutoPayout(
+        address holder,
+        uint256 minimum
+    ) external returns (bool);
+
+    // Only the holder or its owner
+    function disableAutoPayout(address holder) external returns (bool);
+
     function getClaimableReward(
         address dapp,
         address token
     ) external view returns (uint256);
 
+    function getClaimableRewards(
+        address dapp
+    ) external view returns (address[] memory tokens, uint256[] memory amounts);
+
     function isWhitelisted(address dapp) external view returns (bool);
 
+    function isFrozen(address holder) external view returns (bool);
+
+    function setFrozen(address holder, bool isFrozen) external returns (bool); // onlyOwner
+
     function getValidatorPercentage() external view returns (uint256);
 
-    function setValidatorPercentage(uint256) external returns (bool);
+    function setValidatorPercentage(uint256) external returns (bool); // onlyOwner
+
+    // Shares are expressed in basis points (1/10000 of the fee)
+    function getValidatorShare() external view returns (uint256);
+
+    function setValidatorShare(uint256) external returns (bool); // onlyOwner
+
+    function getTreasuryShare() external view returns (uint256);
+
+    function setTreasuryShare(uint256) external returns (bool); // onlyOwner
+
+    function getTreasury() external view returns (address);
+
+    function setTreasury(address) external returns (bool); // onlyOwner
+
+    // Share the dapp receives: its override if set, otherwise the default split
+    function getDappShare(address dapp) external view returns (uint256);
+
+    function setDappShare(address dapp, uint256 share) external returns (bool); // onlyOwner
+
+    function clearDappShare(address dapp) external returns (bool); // onlyOwner
 }
@@ -58,8 +58,21 @@ pub const SELECTOR_LOG_TRANSFER_OWNER: [u8; 32] =
 pub const SELECTOR_REWARD_CLAIMED: [u8; 32] = keccak256!("RewardClaimed(address,address,address)");
 pub const SELECTOR_WHITELIST_STATUS_UPDATED: [u8; 32] =
 	keccak256!("WhitelistStatusUpdated(address,bool)");
+pub const SELECTOR_PAYOUT_ADDRESS_UPDATED: [u8; 32] =
+	keccak256!("PayoutAddressUpdated(address,address)");
+pub const SELECTOR_CLAIM_DELEGATE_UPDATED: [u8; 32] =
+	keccak256!("ClaimDelegateUpdated(address,address)");
+pub const SELECTOR_AUTO_PAYOUT_UPDATED: [u8; 32] = keccak256!("AutoPayoutUpdated(address,uint256)");
+pub const SELECTOR_AUTO_PAYOUT_DISABLED: [u8; 32] = keccak256!("AutoPayoutDisabled(address)");
+pub const SELECTOR_FROZEN_STATUS_UPDATED: [u8; 32] =
+	keccak256!("FrozenStatusUpdated(address,bool)");
 pub const SELECTOR_VALIDATOR_PERCENTAGE_UPDATED: [u8; 32] =
 	keccak256!("ValidatorPercentageUpdated(uint256)");
+pub const SELECTOR_VALIDATOR_SHARE_UPDATED: [u8; 32] = keccak256!("ValidatorShareUpdated(uint256)");
+pub const SELECTOR_TREASURY_SHARE_UPDATED: [u8; 32] = keccak256!("TreasuryShareUpdated(uint256)");
+pub const SELECTOR_TREASURY_UPDATED: [u8; 32] = keccak256!("TreasuryUpdated(address)");
+pub const SELECTOR_DAPP_SHARE_UPDATED: [u8; 32] = keccak256!("DappShareUpdated(address,uint256)");
+pub const SELECTOR_DAPP_SHARE_CLEARED: [u8; 32] = keccak256!("DappShareCleared(address)");
 
 /// Storage prefix for owner.
 pub struct OwnerPrefix;
@@ -85,6 +98,37 @@ pub type OwnerStorage<DefaultOwner> = StorageValue<OwnerPrefix, H160, ValueQuery
 
 pub type ClaimableOwnerStorage = StorageValue<ClaimableOwner, H160, ValueQuery, ZeroAddress>;
 
+/// Whether `holder` is an approved validator, or was one when it accrued validator rewards.
+/// Approved validators include the ones temporarily out of the validator set, for example
+/// after being removed for going offline.
+pub fn holder_is_validator<Runtime>(holder: H160) -> bool
+where
+	Runtime: pallet_fee_rewards_vault::Config + pallet_evm::Config + pallet_validator_set::Config,
+{
+	let holder_account_id: <Runtime as frame_system::Config>::AccountId =
+		<Runtime as pallet_evm::Config>::AddressMapping::into_account_id(holder);
+	pallet_validator_set::ApprovedValidators::<Runtime>::get().contains(&holder_account_id)
+		|| pallet_fee_rewards_vault::Pallet::<Runtime>::has_accrued_validator_rewards(holder)
+}
+
+/// Whether the rewards of `holder` can be claimed at all. The automatic payouts of the runtime
+/// use the same rule.
+pub fn holder_can_claim<Runtime>(holder: H160) -> bool
+where
+	Runtime: pallet_fee_rewards_vault::Config
+		+ pallet_evm::Config
+		+ pallet_dnt_fee_controller::Config
+		+ pallet_validator_set::Config,
+{
+	if pallet_fee_rewards_vault::Pallet::<Runtime>::is_frozen(holder) {
+		return false;
+	}
+
+	pallet_fee_rewards_vault::Pallet::<Runtime>::is_whitelisted(holder)
+		|| holder_is_validator::<Runtime>(holder)
+		|| pallet_dnt_fee_controller::Pallet::<Runtime>::get_treasury() == Some(holder)
+}
+
 pub struct FeeRewardsVaultControllerPrecompile<Runtime, DefaultOwner: Get<H160> + 'static>(
 	PhantomData<(Runtime, DefaultOwner)>,
 );
@@ -186,57 +230,33 @@ where
 	) -> EvmResult {
 		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
 
-		let msg_sender = handle.context().caller;
-
-		if !Self::can_claim_reward(handle, msg_sender.into(), holder)? {
-			return Err(revert("sender is not allowed to claim reward"));
-		}
+		let recipient = Self::claim_recipient(handle, holder.into())?;
 
 		let reward = pallet_fee_rewards_vault::Pallet::<Runtime>::get_claimable_reward(
 			holder.into(),
 			token.into(),
 		);
 
-		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
-		pallet_fee_rewards_vault::Pallet::<Runtime>::sub_claimable_reward(
-			holder.into(),
-			token.into(),
-			reward,
-		)
-		.map_err(|_| revert("fail trying to sub claimable reward"))?;
+		Self::transfer_reward(handle, holder.into(), token.into(), recipient, reward)?;
 
-		let encoded_data = stbl_tools::eth::generate_calldata(
-			&"transfer(address,uint256)",
-			&vec![msg_sender.into(), stbl_tools::misc::u256_to_h256(reward)],
-		);
+		Ok(())
+	}
 
-		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
-		let (reason, _) = handle.call(
-			token.into(),
-			None,
-			encoded_data,
-			Some(handle.remaining_gas()),
-			false,
-			&Context {
-				address: token.into(),
-				caller: handle.context().address,
-				apparent_value: U256::zero(),
-			},
-		);
+	#[precompile::public("claimAll(address)")]
+	fn claim_all(handle: &mut impl PrecompileHandle, holder: Address) -> EvmResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
 
-		if reason != ExitReason::Succeed(ExitSucceed::Returned) {
-			return Err(revert("fail trying to transfer reward"));
-		}
+		let recipient = Self::claim_recipient(handle, holder.into())?;
 
-		handle.record_log_costs_manual(3, 32)?;
-		log3(
-			handle.context().address,
-			SELECTOR_REWARD_CLAIMED,
-			holder.0,
-			msg_sender,
-			Vec::from(token.0.to_fixed_bytes()),
-		)
-		.record(handle)?;
+		let rewards =
+			pallet_fee_rewards_vault::Pallet::<Runtime>::get_claimable_rewards(holder.into());
+		handle.record_cost(
+			RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(rewards.len() as u64),
+		)?;
+
+		for (token, reward) in rewards {
+			Self::transfer_reward(handle, holder.into(), token, recipient, reward)?;
+		}
 
 		Ok(())
 	}
@@ -250,53 +270,169 @@ where
 	) -> EvmResult<bool> {
 		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
 
-		let holder_account_id: <Runtime as frame_system::Config>::AccountId =
-			<Runtime as pallet_evm::Config>::AddressMapping::into_account_id(holder.into());
-		let is_whitelisted =
-			pallet_fee_rewards_vault::Pallet::<Runtime>::is_whitelisted(holder.into());
-		let is_validator =
-			pallet_validator_set::Validators::<Runtime>::get().contains(&holder_account_id);
-
-		if !is_whitelisted && !is_validator {
+		if !holder_can_claim::<Runtime>(holder.into()) {
 			return Ok(false);
 		}
 
-		if claimant == holder {
+		if pallet_fee_rewards_vault::Pallet::<Runtime>::get_claim_delegate(holder.into())
+			== Some(claimant.into())
+		{
 			return Ok(true);
 		}
 
-		let code = pallet_evm::AccountCodes::<Runtime>::get(Into::<H160>::into(holder));
+		Self::is_holder_or_owner(handle, claimant.into(), holder.into())
+	}
 
-		if code.is_empty() {
-			return Ok(false);
-		}
+	#[precompile::public("getPayoutAddress(address)")]
+	#[precompile::view]
+	fn get_payout_address(
+		handle: &mut impl PrecompileHandle,
+		holder: Address,
+	) -> EvmResult<Address> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
 
-		if !stbl_tools::eth::code_implements_function(code.as_slice(), &"owner()") {
-			return Ok(false);
-		}
+		let payout_address =
+			pallet_fee_rewards_vault::Pallet::<Runtime>::get_payout_address(holder.into());
 
-		let call_data = stbl_tools::eth::generate_calldata(&"owner()", &vec![]);
+		Ok(Address(payout_address.unwrap_or_default()))
+	}
 
-		let (reason, output) = handle.call(
+	/// Setting the zero address sends the rewards to the claimant again.
+	#[precompile::public("setPayoutAddress(address,address)")]
+	fn set_payout_address(
+		handle: &mut impl PrecompileHandle,
+		holder: Address,
+		payout_address: Address,
+	) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		Self::ensure_manages_rewards(handle, holder.into())?;
+
+		let payout_address: H160 = payout_address.into();
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		pallet_fee_rewards_vault::Pallet::<Runtime>::set_payout_address(
 			holder.into(),
-			None,
-			call_data,
-			Some(handle.remaining_gas()),
-			true,
-			&Context {
-				address: holder.into(),
-				caller: handle.context().address,
-				apparent_value: U256::zero(),
-			},
+			Some(payout_address).filter(|address| !address.is_zero()),
 		);
 
-		match reason {
-			ExitReason::Succeed(ExitSucceed::Returned) => {
-				let owner = H160::from_slice(&output[12..32]);
-				Ok(owner == claimant.into())
-			}
-			_ => Err(revert("call to owner() failed")),
-		}
+		handle.record_log_costs_manual(3, 0)?;
+		log3(
+			handle.context().address,
+			SELECTOR_PAYOUT_ADDRESS_UPDATED,
+			holder.0,
+			payout_address,
+			vec![],
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("getClaimDelegate(address)")]
+	#[precompile::view]
+	fn get_claim_delegate(
+		handle: &mut impl PrecompileHandle,
+		holder: Address,
+	) -> EvmResult<Address> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let delegate =
+			pallet_fee_rewards_vault::Pallet::<Runtime>::get_claim_delegate(holder.into());
+
+		Ok(Address(delegate.unwrap_or_default()))
+	}
+
+	/// Setting the zero address removes the delegate.
+	#[precompile::public("setClaimDelegate(address,address)")]
+	fn set_claim_delegate(
+		handle: &mut impl PrecompileHandle,
+		holder: Address,
+		delegate: Address,
+	) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		Self::ensure_manages_rewards(handle, holder.into())?;
+
+		let delegate: H160 = delegate.into();
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		pallet_fee_rewards_vault::Pallet::<Runtime>::set_claim_delegate(
+			holder.into(),
+			Some(delegate).filter(|address| !address.is_zero()),
+		);
+
+		handle.record_log_costs_manual(3, 0)?;
+		log3(
+			handle.context().address,
+			SELECTOR_CLAIM_DELEGATE_UPDATED,
+			holder.0,
+			delegate,
+			vec![],
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("getAutoPayout(address)")]
+	#[precompile::view]
+	fn get_auto_payout(
+		handle: &mut impl PrecompileHandle,
+		holder: Address,
+	) -> EvmResult<(bool, U256)> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let minimum = pallet_fee_rewards_vault::Pallet::<Runtime>::get_auto_payout(holder.into());
+
+		Ok((minimum.is_some(), minimum.unwrap_or_default()))
+	}
+
+	/// Opts `holder` in to the automatic payouts of every reward of at least `minimum`.
+	#[precompile::public("setAutoPayout(address,uint256)")]
+	fn set_auto_payout(
+		handle: &mut impl PrecompileHandle,
+		holder: Address,
+		minimum: U256,
+	) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		Self::ensure_manages_rewards(handle, holder.into())?;
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		pallet_fee_rewards_vault::Pallet::<Runtime>::set_auto_payout(holder.into(), Some(minimum));
+
+		handle.record_log_costs_manual(2, 32)?;
+		log2(
+			handle.context().address,
+			SELECTOR_AUTO_PAYOUT_UPDATED,
+			holder.0,
+			u256_to_vec_u8(minimum),
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("disableAutoPayout(address)")]
+	fn disable_auto_payout(handle: &mut impl PrecompileHandle, holder: Address) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		Self::ensure_manages_rewards(handle, holder.into())?;
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		pallet_fee_rewards_vault::Pallet::<Runtime>::set_auto_payout(holder.into(), None);
+
+		handle.record_log_costs_manual(2, 0)?;
+		log2(
+			handle.context().address,
+			SELECTOR_AUTO_PAYOUT_DISABLED,
+			holder.0,
+			vec![],
+		)
+		.record(handle)?;
+
+		Ok(true)
 	}
 
 	#[precompile::public("getClaimableReward(address,address)")]
@@ -316,6 +452,26 @@ where
 		Ok(reward)
 	}
 
+	#[precompile::public("getClaimableRewards(address)")]
+	#[precompile::view]
+	fn get_claimable_rewards(
+		handle: &mut impl PrecompileHandle,
+		holder: Address,
+	) -> EvmResult<(Vec<Address>, Vec<U256>)> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let rewards =
+			pallet_fee_rewards_vault::Pallet::<Runtime>::get_claimable_rewards(holder.into());
+		handle.record_cost(
+			RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(rewards.len() as u64),
+		)?;
+
+		Ok(rewards
+			.into_iter()
+			.map(|(token, reward)| (Address(token), reward))
+			.unzip())
+	}
+
 	#[precompile::public("isWhitelisted(address)")]
 	#[precompile::view]
 	fn is_whitelisted(handle: &mut impl PrecompileHandle, holder: Address) -> EvmResult<bool> {
@@ -363,6 +519,46 @@ where
 		Ok(true)
 	}
 
+	#[precompile::public("isFrozen(address)")]
+	#[precompile::view]
+	fn is_frozen(handle: &mut impl PrecompileHandle, holder: Address) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let frozen = pallet_fee_rewards_vault::Pallet::<Runtime>::is_frozen(holder.into());
+
+		Ok(frozen)
+	}
+
+	#[precompile::public("setFrozen(address,bool)")]
+	fn set_frozen(
+		handle: &mut impl PrecompileHandle,
+		holder: Address,
+		is_frozen: bool,
+	) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let msg_sender = handle.context().caller;
+		let owner = OwnerStorage::<DefaultOwner>::get();
+
+		if msg_sender != owner {
+			return Err(revert("sender is not owner"));
+		}
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		pallet_fee_rewards_vault::Pallet::<Runtime>::set_frozen(holder.into(), is_frozen);
+
+		handle.record_log_costs_manual(2, 32)?;
+		log2(
+			handle.context().address,
+			SELECTOR_FROZEN_STATUS_UPDATED,
+			holder.0,
+			bool_to_vec_u8(is_frozen),
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
 	#[precompile::public("getValidatorPercentage()")]
 	#[precompile::view]
 	fn get_validator_percentage(handle: &mut impl PrecompileHandle) -> EvmResult<U256> {
@@ -392,7 +588,8 @@ where
 		}
 
 		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
-		pallet_dnt_fee_controller::Pallet::<Runtime>::set_validator_percentage(percentage).unwrap();
+		pallet_dnt_fee_controller::Pallet::<Runtime>::set_validator_percentage(percentage)
+			.map_err(|_| revert("shares exceed the fee"))?;
 
 		handle.record_log_costs_manual(1, 32)?;
 		log1(
@@ -404,4 +601,342 @@ where
 
 		Ok(true)
 	}
+
+	#[precompile::public("getValidatorShare()")]
+	#[precompile::view]
+	fn get_validator_share(handle: &mut impl PrecompileHandle) -> EvmResult<U256> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let share = pallet_dnt_fee_controller::Pallet::<Runtime>::get_validator_share();
+
+		Ok(share)
+	}
+
+	#[precompile::public("setValidatorShare(uint256)")]
+	fn set_validator_share(handle: &mut impl PrecompileHandle, share: U256) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let msg_sender = handle.context().caller;
+		let owner = OwnerStorage::<DefaultOwner>::get();
+
+		if msg_sender != owner {
+			return Err(revert("sender is not owner"));
+		}
+
+		if share > U256::from(pallet_dnt_fee_controller::BASIS_POINTS) {
+			return Err(revert("share is too high"));
+		}
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		pallet_dnt_fee_controller::Pallet::<Runtime>::set_validator_share(share)
+			.map_err(|_| revert("shares exceed the fee"))?;
+
+		handle.record_log_costs_manual(1, 32)?;
+		log1(
+			handle.context().address,
+			SELECTOR_VALIDATOR_SHARE_UPDATED,
+			u256_to_vec_u8(share),
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("getTreasuryShare()")]
+	#[precompile::view]
+	fn get_treasury_share(handle: &mut impl PrecompileHandle) -> EvmResult<U256> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let share = pallet_dnt_fee_controller::Pallet::<Runtime>::get_treasury_share();
+
+		Ok(share)
+	}
+
+	#[precompile::public("setTreasuryShare(uint256)")]
+	fn set_treasury_share(handle: &mut impl PrecompileHandle, share: U256) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let msg_sender = handle.context().caller;
+		let owner = OwnerStorage::<DefaultOwner>::get();
+
+		if msg_sender != owner {
+			return Err(revert("sender is not owner"));
+		}
+
+		if share > U256::from(pallet_dnt_fee_controller::BASIS_POINTS) {
+			return Err(revert("share is too high"));
+		}
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		pallet_dnt_fee_controller::Pallet::<Runtime>::set_treasury_share(share)
+			.map_err(|_| revert("shares exceed the fee"))?;
+
+		handle.record_log_costs_manual(1, 32)?;
+		log1(
+			handle.context().address,
+			SELECTOR_TREASURY_SHARE_UPDATED,
+			u256_to_vec_u8(share),
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("getTreasury()")]
+	#[precompile::view]
+	fn get_treasury(handle: &mut impl PrecompileHandle) -> EvmResult<Address> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let treasury = pallet_dnt_fee_controller::Pallet::<Runtime>::get_treasury();
+
+		Ok(treasury.unwrap_or_default().into())
+	}
+
+	/// Setting the zero address disables the treasury share.
+	#[precompile::public("setTreasury(address)")]
+	fn set_treasury(handle: &mut impl PrecompileHandle, treasury: Address) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let msg_sender = handle.context().caller;
+		let owner = OwnerStorage::<DefaultOwner>::get();
+
+		if msg_sender != owner {
+			return Err(revert("sender is not owner"));
+		}
+
+		let treasury: H160 = treasury.into();
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		pallet_dnt_fee_controller::Pallet::<Runtime>::set_treasury(
+			Some(treasury).filter(|treasury| !treasury.is_zero()),
+		);
+
+		handle.record_log_costs_manual(2, 0)?;
+		log2(
+			handle.context().address,
+			SELECTOR_TREASURY_UPDATED,
+			treasury,
+			vec![],
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("getDappShare(address)")]
+	#[precompile::view]
+	fn get_dapp_share(handle: &mut impl PrecompileHandle, dapp: Address) -> EvmResult<U256> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(4))?;
+
+		let share = pallet_dnt_fee_controller::Pallet::<Runtime>::get_dapp_share(dapp.into());
+
+		Ok(share)
+	}
+
+	#[precompile::public("setDappShare(address,uint256)")]
+	fn set_dapp_share(
+		handle: &mut impl PrecompileHandle,
+		dapp: Address,
+		share: U256,
+	) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let msg_sender = handle.context().caller;
+		let owner = OwnerStorage::<DefaultOwner>::get();
+
+		if msg_sender != owner {
+			return Err(revert("sender is not owner"));
+		}
+
+		if share > U256::from(pallet_dnt_fee_controller::BASIS_POINTS) {
+			return Err(revert("share is too high"));
+		}
+
+		if !pallet_fee_rewards_vault::Pallet::<Runtime>::is_whitelisted(dapp.into()) {
+			return Err(revert("dapp is not whitelisted"));
+		}
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		pallet_dnt_fee_controller::Pallet::<Runtime>::set_dapp_share(dapp.into(), Some(share))
+			.map_err(|_| revert("shares exceed the fee"))?;
+
+		handle.record_log_costs_manual(2, 32)?;
+		log2(
+			handle.context().address,
+			SELECTOR_DAPP_SHARE_UPDATED,
+			dapp.0,
+			u256_to_vec_u8(share),
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("clearDappShare(address)")]
+	fn clear_dapp_share(handle: &mut impl PrecompileHandle, dapp: Address) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let msg_sender = handle.context().caller;
+		let owner = OwnerStorage::<DefaultOwner>::get();
+
+		if msg_sender != owner {
+			return Err(revert("sender is not owner"));
+		}
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		pallet_dnt_fee_controller::Pallet::<Runtime>::set_dapp_share(dapp.into(), None)
+			.map_err(|_| revert("shares exceed the fee"))?;
+
+		handle.record_log_costs_manual(2, 0)?;
+		log2(
+			handle.context().address,
+			SELECTOR_DAPP_SHARE_CLEARED,
+			dapp.0,
+			vec![],
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+}
+
+impl<Runtime, DefaultOwner> FeeRewardsVaultControllerPrecompile<Runtime, DefaultOwner>
+where
+	DefaultOwner: Get<H160> + 'static,
+	Runtime: pallet_fee_rewards_vault::Config
+		+ pallet_timestamp::Config
+		+ pallet_evm::Config
+		+ pallet_dnt_fee_controller::Config
+		+ pallet_validator_set::Config,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime as pallet_timestamp::Config>::Moment: Into<U256>,
+{
+	// Whether `claimant` is `holder` itself or, for a contract, its `owner()`.
+	fn is_holder_or_owner(
+		handle: &mut impl PrecompileHandle,
+		claimant: H160,
+		holder: H160,
+	) -> EvmResult<bool> {
+		if claimant == holder {
+			return Ok(true);
+		}
+
+		let code = pallet_evm::AccountCodes::<Runtime>::get(holder);
+
+		if code.is_empty() {
+			return Ok(false);
+		}
+
+		if !stbl_tools::eth::code_implements_function(code.as_slice(), &"owner()") {
+			return Ok(false);
+		}
+
+		let call_data = stbl_tools::eth::generate_calldata(&"owner()", &vec![]);
+
+		let (reason, output) = handle.call(
+			holder,
+			None,
+			call_data,
+			Some(handle.remaining_gas()),
+			true,
+			&Context {
+				address: holder,
+				caller: handle.context().address,
+				apparent_value: U256::zero(),
+			},
+		);
+
+		match reason {
+			ExitReason::Succeed(ExitSucceed::Returned) => {
+				let owner = H160::from_slice(&output[12..32]);
+				Ok(owner == claimant)
+			}
+			_ => Err(revert("call to owner() failed")),
+		}
+	}
+
+	// Only the holder or its owner manage where its rewards go and who claims them.
+	fn ensure_manages_rewards(handle: &mut impl PrecompileHandle, holder: H160) -> EvmResult {
+		let msg_sender = handle.context().caller;
+
+		if !holder_can_claim::<Runtime>(holder)
+			|| !Self::is_holder_or_owner(handle, msg_sender, holder)?
+		{
+			return Err(revert("sender is not allowed to manage rewards"));
+		}
+
+		Ok(())
+	}
+
+	// Checks the sender can claim the rewards of `holder` and returns where they are sent: the
+	// payout address if set, otherwise the holder for its delegate and the sender for anyone else.
+	fn claim_recipient(handle: &mut impl PrecompileHandle, holder: H160) -> EvmResult<H160> {
+		let msg_sender = handle.context().caller;
+
+		if !Self::can_claim_reward(handle, msg_sender.into(), holder.into())? {
+			return Err(revert("sender is not allowed to claim reward"));
+		}
+
+		let recipient =
+			match pallet_fee_rewards_vault::Pallet::<Runtime>::get_payout_address(holder) {
+				Some(payout_address) => payout_address,
+				_ if pallet_fee_rewards_vault::Pallet::<Runtime>::get_claim_delegate(holder)
+					== Some(msg_sender) =>
+				{
+					holder
+				}
+				_ => msg_sender,
+			};
+
+		Ok(recipient)
+	}
+
+	// Moves the reward of `holder` in `token` out of the vault and transfers it to `recipient`.
+	fn transfer_reward(
+		handle: &mut impl PrecompileHandle,
+		holder: H160,
+		token: H160,
+		recipient: H160,
+		reward: U256,
+	) -> EvmResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		pallet_fee_rewards_vault::Pallet::<Runtime>::sub_claimable_reward(holder, token, reward)
+			.map_err(|_| revert("fail trying to sub claimable reward"))?;
+
+		let encoded_data = stbl_tools::eth::generate_calldata(
+			&"transfer(address,uint256)",
+			&vec![recipient.into(), stbl_tools::misc::u256_to_h256(reward)],
+		);
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		let (reason, _) = handle.call(
+			token,
+			None,
+			encoded_data,
+			Some(handle.remaining_gas()),
+			false,
+			&Context {
+				address: token,
+				caller: handle.context().address,
+				apparent_value: U256::zero(),
+			},
+		);
+
+		if reason != ExitReason::Succeed(ExitSucceed::Returned) {
+			return Err(revert("fail trying to transfer reward"));
+		}
+
+		handle.record_log_costs_manual(3, 32)?;
+		log3(
+			handle.context().address,
+			SELECTOR_REWARD_CLAIMED,
+			holder,
+			recipient,
+			Vec::from(token.to_fixed_bytes()),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
 }
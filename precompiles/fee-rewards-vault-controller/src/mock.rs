@@ -194,7 +194,14 @@ impl pallet_balances::Config for Test {
 	type RuntimeFreezeReason = ();
 }
 
-impl pallet_fee_rewards_vault::Config for Test {}
+impl pallet_fee_rewards_vault::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ERC20Manager = pallet_erc20_manager::Pallet<Self>;
+	type VaultAddress = SmartContractWithOwner;
+	type PayoutFilter = frame_support::traits::Everything;
+	type PayoutPeriod = ConstU64<100>;
+	type MaxPayoutsPerBlock = ConstU32<10>;
+}
 
 impl pallet_dnt_fee_controller::Config for Test {
 	type ERC20Manager = pallet_erc20_manager::Pallet<Self>;
@@ -217,6 +224,9 @@ impl pallet_user_fee_selector::Config for Test {
 impl pallet_validator_fee_selector::Config for Test {
 	type SupportedTokensManager = pallet_supported_tokens_manager::Pallet<Self>;
 	type SimulatorRunner = pallet_evm::runner::stack::Runner<Self>;
+	type RuntimeEvent = RuntimeEvent;
+	type DefaultConversionRateGasLimit = ConstU64<3_000_000>;
+	type MaxConversionRateGasLimit = ConstU64<10_000_000>;
 }
 
 impl_opaque_keys! {
@@ -312,6 +322,18 @@ impl Convert<UintAuthorityId, AccountId> for AccountIdOfValidator {
 parameter_types! {
 	pub const MinAuthorities: u32 = 0;
 	pub const MaxKeys: u32 = 1000u32;
+	pub const MaxMissedFinalityEpochs: u32 = 3;
+	pub const MinFinalityJustifications: u32 = 1;
+	pub const MaxFinalityVotes: u32 = 3;
+	pub const EquivocationReportLongevity: u64 = 100;
+	pub const MaxRejoinAttempts: u32 = 3;
+	pub const RejoinWindow: u64 = 100;
+	pub const JailDuration: u64 = 100;
+	pub const MaxOfflineRemovals: u32 = 3;
+	pub const OfflineRemovalWindow: u64 = 100;
+	pub const MaxMetadataLength: u32 = 128;
+	pub const MaxMaintenanceDuration: u64 = 100;
+	pub const StatsHistoryDepth: u32 = 10;
 }
 impl pallet_validator_set::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
@@ -329,6 +351,32 @@ impl pallet_validator_set::Config for Test {
 	type MaxKeys = MaxKeys;
 
 	type AccountIdOfValidator = AccountIdOfValidator;
+
+	type SlotAuthorship = ();
+
+	type StatsHistoryDepth = StatsHistoryDepth;
+
+	type MaxMaintenanceDuration = MaxMaintenanceDuration;
+
+	type MaxMetadataLength = MaxMetadataLength;
+
+	type JailDuration = JailDuration;
+
+	type MaxOfflineRemovals = MaxOfflineRemovals;
+	type OfflineRemovalWindow = OfflineRemovalWindow;
+
+	type MaxRejoinAttempts = MaxRejoinAttempts;
+
+	type RejoinWindow = RejoinWindow;
+
+	type FinalityVoters = ();
+
+	type MaxMissedFinalityEpochs = MaxMissedFinalityEpochs;
+
+	type MinFinalityJustifications = MinFinalityJustifications;
+	type MaxFinalityVotes = MaxFinalityVotes;
+
+	type EquivocationReportLongevity = EquivocationReportLongevity;
 }
 
 impl pallet_session::Config for Test {
@@ -343,7 +391,10 @@ impl pallet_session::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 }
 
-impl pallet_supported_tokens_manager::Config for Test {}
+impl pallet_supported_tokens_manager::Config for Test {
+	type BalanceLayoutVerifier = ();
+	type TokenViewCaller = ();
+}
 
 frame_support::construct_runtime!(
 	pub enum Test {
@@ -428,6 +479,8 @@ impl ExtBuilder {
 			_config: Default::default(),
 			fee_vault_precompile_address: SmartContractWithOwner::get(),
 			validator_percentage: U256::from(0),
+			treasury: None,
+			treasury_share: U256::from(0),
 		}
 		.assimilate_storage(&mut t)
 		.expect("Pallet dnt fee controller storage can be assimilated");
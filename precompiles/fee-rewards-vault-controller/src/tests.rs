@@ -39,6 +39,8 @@ fn selectors() {
 	assert!(PCall::can_claim_reward_selectors().contains(&0xa4630c85));
 	assert!(PCall::get_claimable_reward_selectors().contains(&0x21e91dea));
 	assert!(PCall::is_whitelisted_selectors().contains(&0x3af32abf));
+	assert!(PCall::claim_all_selectors().contains(&0x77329f35));
+	assert!(PCall::get_claimable_rewards_selectors().contains(&0x308e401e));
 
 	assert_eq!(
 		crate::SELECTOR_LOG_NEW_OWNER,
@@ -71,6 +73,8 @@ fn modifiers() {
 		tester.test_view_modifier(PCall::can_claim_reward_selectors());
 		tester.test_view_modifier(PCall::get_claimable_reward_selectors());
 		tester.test_view_modifier(PCall::is_whitelisted_selectors());
+		tester.test_default_modifier(PCall::claim_all_selectors());
+		tester.test_view_modifier(PCall::get_claimable_rewards_selectors());
 	});
 }
 
@@ -642,3 +646,788 @@ fn test_set_validator_percentage_fails_if_greater() {
 			.execute_reverts(|x| x.eq_ignore_ascii_case(b"percentage is too high"));
 	});
 }
+
+#[test]
+fn test_set_validator_share() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::set_validator_share {
+					share: sp_core::U256::from(1_234),
+				},
+			)
+			.expect_log(log1(
+				Precompile1,
+				SELECTOR_VALIDATOR_SHARE_UPDATED,
+				u256_to_vec_u8(sp_core::U256::from(1_234)),
+			))
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::get_validator_share {},
+			)
+			.execute_returns(sp_core::U256::from(1_234));
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::get_validator_percentage {},
+			)
+			.execute_returns(sp_core::U256::from(12));
+	});
+}
+
+#[test]
+fn test_set_validator_share_fails_if_not_owner() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				SmartContractWithoutOwner::get(),
+				Precompile1,
+				PCall::set_validator_share {
+					share: sp_core::U256::from(1_000),
+				},
+			)
+			.execute_reverts(|x| x.eq_ignore_ascii_case(b"sender is not owner"));
+	});
+}
+
+#[test]
+fn test_set_treasury_share() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::set_treasury_share {
+					share: sp_core::U256::from(500),
+				},
+			)
+			.expect_log(log1(
+				Precompile1,
+				SELECTOR_TREASURY_SHARE_UPDATED,
+				u256_to_vec_u8(sp_core::U256::from(500)),
+			))
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::get_treasury_share {},
+			)
+			.execute_returns(sp_core::U256::from(500));
+	});
+}
+
+#[test]
+fn test_set_treasury_share_fails_if_shares_exceed_the_fee() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::set_validator_share {
+					share: sp_core::U256::from(9_000),
+				},
+			)
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::set_treasury_share {
+					share: sp_core::U256::from(1_001),
+				},
+			)
+			.execute_reverts(|x| x.eq_ignore_ascii_case(b"shares exceed the fee"));
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::set_treasury_share {
+					share: sp_core::U256::from(10_001),
+				},
+			)
+			.execute_reverts(|x| x.eq_ignore_ascii_case(b"share is too high"));
+	});
+}
+
+#[test]
+fn test_set_treasury() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(DefaultOwner::get(), Precompile1, PCall::get_treasury {})
+			.execute_returns(Address(H160::zero()));
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::set_treasury {
+					treasury: Address(Dapp2::get()),
+				},
+			)
+			.expect_log(log2(
+				Precompile1,
+				SELECTOR_TREASURY_UPDATED,
+				Dapp2::get(),
+				vec![],
+			))
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(DefaultOwner::get(), Precompile1, PCall::get_treasury {})
+			.execute_returns(Address(Dapp2::get()));
+
+		precompiles()
+			.prepare_test(
+				Dapp2::get(),
+				Precompile1,
+				PCall::can_claim_reward {
+					claimant: Dapp2::get().into(),
+					holder: Dapp2::get().into(),
+				},
+			)
+			.execute_returns(true);
+	});
+}
+
+#[test]
+fn test_set_treasury_fails_if_not_owner() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Dapp2::get(),
+				Precompile1,
+				PCall::set_treasury {
+					treasury: Address(Dapp2::get()),
+				},
+			)
+			.execute_reverts(|x| x.eq_ignore_ascii_case(b"sender is not owner"));
+	});
+}
+
+#[test]
+fn test_set_dapp_share() {
+	ExtBuilder::default().build().execute_with(|| {
+		let dapp = SmartContractWithoutOwner::get();
+
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::set_validator_share {
+					share: sp_core::U256::from(5_000),
+				},
+			)
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::set_whitelist {
+					holder: dapp.into(),
+					is_whitelisted: true,
+				},
+			)
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::get_dapp_share { dapp: dapp.into() },
+			)
+			.execute_returns(sp_core::U256::from(5_000));
+
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::set_dapp_share {
+					dapp: dapp.into(),
+					share: sp_core::U256::from(7_000),
+				},
+			)
+			.expect_log(log2(
+				Precompile1,
+				SELECTOR_DAPP_SHARE_UPDATED,
+				dapp,
+				u256_to_vec_u8(sp_core::U256::from(7_000)),
+			))
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::get_dapp_share { dapp: dapp.into() },
+			)
+			.execute_returns(sp_core::U256::from(7_000));
+
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::clear_dapp_share { dapp: dapp.into() },
+			)
+			.expect_log(log2(Precompile1, SELECTOR_DAPP_SHARE_CLEARED, dapp, vec![]))
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::get_dapp_share { dapp: dapp.into() },
+			)
+			.execute_returns(sp_core::U256::from(5_000));
+	});
+}
+
+#[test]
+fn test_set_dapp_share_fails_if_not_whitelisted() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::set_dapp_share {
+					dapp: SmartContractWithoutOwner::get().into(),
+					share: sp_core::U256::from(7_000),
+				},
+			)
+			.execute_reverts(|x| x.eq_ignore_ascii_case(b"dapp is not whitelisted"));
+	});
+}
+
+#[test]
+fn test_set_dapp_share_fails_if_not_owner() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				SmartContractWithoutOwner::get(),
+				Precompile1,
+				PCall::set_dapp_share {
+					dapp: SmartContractWithoutOwner::get().into(),
+					share: sp_core::U256::from(7_000),
+				},
+			)
+			.execute_reverts(|x| x.eq_ignore_ascii_case(b"sender is not owner"));
+	});
+}
+
+#[test]
+fn test_can_claim_reward_should_return_true_if_holder_was_a_validator() {
+	ExtBuilder::default().build().execute_with(|| {
+		let former_validator = UnpermissionedAccount::get();
+
+		precompiles()
+			.prepare_test(
+				former_validator,
+				Precompile1,
+				PCall::can_claim_reward {
+					claimant: former_validator.into(),
+					holder: former_validator.into(),
+				},
+			)
+			.execute_returns(false);
+
+		FeeRewardsVault::add_validator_reward(
+			former_validator,
+			Token1::get(),
+			sp_core::U256::from(100),
+		)
+		.unwrap();
+
+		precompiles()
+			.prepare_test(
+				former_validator,
+				Precompile1,
+				PCall::can_claim_reward {
+					claimant: former_validator.into(),
+					holder: former_validator.into(),
+				},
+			)
+			.execute_returns(true);
+	});
+}
+
+#[test]
+fn test_can_claim_reward_should_return_false_if_frozen() {
+	ExtBuilder::default().build().execute_with(|| {
+		let validator = Validators::get()[0].into();
+
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::set_frozen {
+					holder: validator,
+					is_frozen: true,
+				},
+			)
+			.expect_log(log2(
+				Precompile1,
+				SELECTOR_FROZEN_STATUS_UPDATED,
+				Into::<H160>::into(validator),
+				bool_to_vec_u8(true),
+			))
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(
+				validator,
+				Precompile1,
+				PCall::is_frozen { holder: validator },
+			)
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(
+				validator,
+				Precompile1,
+				PCall::can_claim_reward {
+					claimant: validator,
+					holder: validator,
+				},
+			)
+			.execute_returns(false);
+	});
+}
+
+#[test]
+fn test_set_frozen_fails_if_not_owner() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				UnpermissionedAccount::get(),
+				Precompile1,
+				PCall::set_frozen {
+					holder: UnpermissionedAccount::get().into(),
+					is_frozen: true,
+				},
+			)
+			.execute_reverts(|x| x.eq_ignore_ascii_case(b"sender is not owner"));
+	});
+}
+
+#[test]
+fn test_get_claimable_rewards() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::get_claimable_rewards {
+					holder: SmartContractWithoutOwner::get().into(),
+				},
+			)
+			.execute_returns((Vec::<Address>::new(), Vec::<sp_core::U256>::new()));
+
+		FeeRewardsVault::add_claimable_reward(
+			SmartContractWithoutOwner::get(),
+			Token1::get(),
+			sp_core::U256::from(100),
+		)
+		.unwrap();
+
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::get_claimable_rewards {
+					holder: SmartContractWithoutOwner::get().into(),
+				},
+			)
+			.execute_returns((vec![Address(Token1::get())], vec![sp_core::U256::from(100)]));
+	});
+}
+
+#[test]
+fn test_claim_all() {
+	ExtBuilder::default().build().execute_with(|| {
+		let precompile_address: H160 = Precompile1.into();
+
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::set_whitelist {
+					holder: SmartContractWithoutOwner::get().into(),
+					is_whitelisted: true,
+				},
+			)
+			.execute_returns(true);
+
+		FeeRewardsVault::add_claimable_reward(
+			SmartContractWithoutOwner::get(),
+			Token1::get(),
+			sp_core::U256::from(100),
+		)
+		.unwrap();
+		FeeRewardsVault::add_claimable_reward(
+			SmartContractWithoutOwner::get(),
+			Token2::get(),
+			sp_core::U256::from(50),
+		)
+		.unwrap();
+
+		let rewards = FeeRewardsVault::get_claimable_rewards(SmartContractWithoutOwner::get());
+		assert_eq!(rewards.len(), 2);
+
+		let precompiles = precompiles();
+		let mut tester = precompiles
+			.prepare_test(
+				SmartContractWithoutOwner::get(),
+				Precompile1,
+				PCall::claim_all {
+					holder: SmartContractWithoutOwner::get().into(),
+				},
+			)
+			.with_subcall_handle(move |subcall| {
+				let Subcall {
+					address,
+					input,
+					is_static,
+					context,
+					..
+				} = subcall;
+
+				assert_eq!(context.caller, precompile_address);
+				assert_eq!(is_static, false);
+
+				let amount = if address == Token1::get() { 100 } else { 50 };
+				assert_eq!(
+					input,
+					stbl_tools::eth::generate_calldata(
+						&"transfer(address,uint256)",
+						&vec![
+							SmartContractWithoutOwner::get().into(),
+							stbl_tools::misc::u256_to_h256(sp_core::U256::from(amount))
+						]
+					)
+				);
+
+				SubcallOutput {
+					output: vec![],
+					cost: 1,
+					..SubcallOutput::succeed()
+				}
+			});
+
+		for (token, _) in rewards {
+			tester = tester.expect_log(log3(
+				precompile_address,
+				SELECTOR_REWARD_CLAIMED,
+				SmartContractWithoutOwner::get(),
+				SmartContractWithoutOwner::get(),
+				Vec::from(token.to_fixed_bytes()),
+			));
+		}
+
+		tester.execute_some();
+
+		assert_eq!(
+			FeeRewardsVault::get_claimable_rewards(SmartContractWithoutOwner::get()),
+			vec![]
+		);
+	});
+}
+
+#[test]
+fn test_claim_all_fails_if_not_allowed() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				UnpermissionedAccount::get(),
+				Precompile1,
+				PCall::claim_all {
+					holder: SmartContractWithoutOwner::get().into(),
+				},
+			)
+			.execute_reverts(|x| x.eq_ignore_ascii_case(b"sender is not allowed to claim reward"));
+	});
+}
+
+#[test]
+fn test_approved_validator_out_of_the_set_can_claim() {
+	ExtBuilder::default().build().execute_with(|| {
+		let validator: H160 = Validators::get()[0];
+		pallet_validator_set::Validators::<Test>::kill();
+
+		assert!(holder_is_validator::<Test>(validator));
+		assert!(holder_can_claim::<Test>(validator));
+
+		pallet_validator_set::ApprovedValidators::<Test>::kill();
+
+		assert!(!holder_is_validator::<Test>(validator));
+		assert!(!holder_can_claim::<Test>(validator));
+	});
+}
+
+#[test]
+fn test_claim_reward_sends_to_payout_address() {
+	ExtBuilder::default().build().execute_with(|| {
+		let precompile_address: H160 = Precompile1.into();
+		let validator: H160 = Validators::get()[0];
+		let payout_address = UnpermissionedAccount2::get();
+
+		precompiles()
+			.prepare_test(
+				validator,
+				Precompile1,
+				PCall::set_payout_address {
+					holder: validator.into(),
+					payout_address: payout_address.into(),
+				},
+			)
+			.expect_log(log3(
+				precompile_address,
+				SELECTOR_PAYOUT_ADDRESS_UPDATED,
+				validator,
+				payout_address,
+				vec![],
+			))
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(
+				validator,
+				Precompile1,
+				PCall::get_payout_address {
+					holder: validator.into(),
+				},
+			)
+			.execute_returns(Address(payout_address));
+
+		FeeRewardsVault::add_claimable_reward(validator, Token1::get(), sp_core::U256::from(100))
+			.unwrap();
+
+		precompiles()
+			.prepare_test(
+				validator,
+				Precompile1,
+				PCall::claim_reward {
+					holder: validator.into(),
+					token: Token1::get().into(),
+				},
+			)
+			.with_subcall_handle(move |subcall| {
+				assert_eq!(subcall.address, Token1::get());
+				assert_eq!(
+					subcall.input,
+					stbl_tools::eth::generate_calldata(
+						&"transfer(address,uint256)",
+						&vec![
+							payout_address.into(),
+							stbl_tools::misc::u256_to_h256(sp_core::U256::from(100))
+						]
+					)
+				);
+
+				SubcallOutput {
+					output: vec![],
+					cost: 1,
+					..SubcallOutput::succeed()
+				}
+			})
+			.expect_log(log3(
+				precompile_address,
+				SELECTOR_REWARD_CLAIMED,
+				validator,
+				payout_address,
+				Vec::from(Token1::get().to_fixed_bytes()),
+			))
+			.execute_some();
+	});
+}
+
+#[test]
+fn test_claim_delegate_claims_to_holder() {
+	ExtBuilder::default().build().execute_with(|| {
+		let precompile_address: H160 = Precompile1.into();
+		let validator: H160 = Validators::get()[0];
+		let delegate = UnpermissionedAccount::get();
+
+		precompiles()
+			.prepare_test(
+				validator,
+				Precompile1,
+				PCall::set_claim_delegate {
+					holder: validator.into(),
+					delegate: delegate.into(),
+				},
+			)
+			.expect_log(log3(
+				precompile_address,
+				SELECTOR_CLAIM_DELEGATE_UPDATED,
+				validator,
+				delegate,
+				vec![],
+			))
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(
+				delegate,
+				Precompile1,
+				PCall::can_claim_reward {
+					claimant: delegate.into(),
+					holder: validator.into(),
+				},
+			)
+			.execute_returns(true);
+
+		FeeRewardsVault::add_claimable_reward(validator, Token1::get(), sp_core::U256::from(100))
+			.unwrap();
+
+		precompiles()
+			.prepare_test(
+				delegate,
+				Precompile1,
+				PCall::claim_reward {
+					holder: validator.into(),
+					token: Token1::get().into(),
+				},
+			)
+			.with_subcall_handle(move |subcall| {
+				assert_eq!(
+					subcall.input,
+					stbl_tools::eth::generate_calldata(
+						&"transfer(address,uint256)",
+						&vec![
+							validator.into(),
+							stbl_tools::misc::u256_to_h256(sp_core::U256::from(100))
+						]
+					)
+				);
+
+				SubcallOutput {
+					output: vec![],
+					cost: 1,
+					..SubcallOutput::succeed()
+				}
+			})
+			.expect_log(log3(
+				precompile_address,
+				SELECTOR_REWARD_CLAIMED,
+				validator,
+				validator,
+				Vec::from(Token1::get().to_fixed_bytes()),
+			))
+			.execute_some();
+
+		precompiles()
+			.prepare_test(
+				delegate,
+				Precompile1,
+				PCall::set_payout_address {
+					holder: validator.into(),
+					payout_address: delegate.into(),
+				},
+			)
+			.execute_reverts(|x| {
+				x.eq_ignore_ascii_case(b"sender is not allowed to manage rewards")
+			});
+	});
+}
+
+#[test]
+fn test_set_payout_address_fails_if_not_holder() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				UnpermissionedAccount::get(),
+				Precompile1,
+				PCall::set_payout_address {
+					holder: Validators::get()[0].into(),
+					payout_address: UnpermissionedAccount::get().into(),
+				},
+			)
+			.execute_reverts(|x| {
+				x.eq_ignore_ascii_case(b"sender is not allowed to manage rewards")
+			});
+	});
+}
+
+#[test]
+fn test_set_and_disable_auto_payout() {
+	ExtBuilder::default().build().execute_with(|| {
+		let precompile_address: H160 = Precompile1.into();
+		let validator: H160 = Validators::get()[0];
+
+		precompiles()
+			.prepare_test(
+				validator,
+				Precompile1,
+				PCall::get_auto_payout {
+					holder: validator.into(),
+				},
+			)
+			.execute_returns((false, sp_core::U256::zero()));
+
+		precompiles()
+			.prepare_test(
+				validator,
+				Precompile1,
+				PCall::set_auto_payout {
+					holder: validator.into(),
+					minimum: sp_core::U256::from(50),
+				},
+			)
+			.expect_log(log2(
+				precompile_address,
+				SELECTOR_AUTO_PAYOUT_UPDATED,
+				validator,
+				u256_to_vec_u8(sp_core::U256::from(50)),
+			))
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(
+				validator,
+				Precompile1,
+				PCall::get_auto_payout {
+					holder: validator.into(),
+				},
+			)
+			.execute_returns((true, sp_core::U256::from(50)));
+
+		precompiles()
+			.prepare_test(
+				validator,
+				Precompile1,
+				PCall::disable_auto_payout {
+					holder: validator.into(),
+				},
+			)
+			.expect_log(log2(
+				precompile_address,
+				SELECTOR_AUTO_PAYOUT_DISABLED,
+				validator,
+				vec![],
+			))
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(
+				validator,
+				Precompile1,
+				PCall::get_auto_payout {
+					holder: validator.into(),
+				},
+			)
+			.execute_returns((false, sp_core::U256::zero()));
+	});
+}
+
+#[test]
+fn test_set_auto_payout_fails_if_not_holder() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				UnpermissionedAccount::get(),
+				Precompile1,
+				PCall::set_auto_payout {
+					holder: Validators::get()[0].into(),
+					minimum: sp_core::U256::zero(),
+				},
+			)
+			.execute_reverts(|x| {
+				x.eq_ignore_ascii_case(b"sender is not allowed to manage rewards")
+			});
+	});
+}
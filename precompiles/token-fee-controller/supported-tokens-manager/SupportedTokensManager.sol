@@ -4,6 +4,48 @@ pragma solidity >=0.8.3;
 interface SupportedTokensManager {
     function addToken(address token, bytes32 slot) external;
 
+    // Finds the slot of the mapping(address => uint256) holding the balances from the
+    // storage read by `balanceOf`
+    function addToken(address token) external;
+
+    // For tokens not keeping balances in a plain mapping(address => uint256) at `slot`.
+    // The balance of a holder is looked up in the mappings at `slot` with `outerKeys`, the
+    // holder and `innerKeys`, `offset` slots after the entry, in the bits of `mask`
+    function addTokenWithLayout(
+        address token,
+        bytes32 slot,
+        bytes32[] calldata outerKeys,
+        bytes32[] calldata innerKeys,
+        uint256 offset,
+        uint256 mask
+    ) external;
+
+    function tokenBalanceLayout(
+        address token
+    )
+        external
+        view
+        returns (
+            bytes32 slot,
+            bytes32[] memory outerKeys,
+            bytes32[] memory innerKeys,
+            uint256 offset,
+            uint256 mask
+        );
+
+    // Views of the token returning a bool, consulted before fees move its balances, such as
+    // "isBlacklisted(address)" and "paused()". Empty signatures skip the check
+    function setComplianceHook(
+        address token,
+        string calldata blacklist,
+        string calldata pause
+    ) external;
+
+    // Selectors of the compliance views of the token, zero if not set
+    function complianceHook(
+        address token
+    ) external view returns (uint32 blacklist, uint32 pause);
+
     function supportedTokens() external view returns (address[] memory);
 
     function isTokenSupported(address token) external view returns (bool);
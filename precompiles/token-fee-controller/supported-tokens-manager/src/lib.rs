@@ -31,6 +31,7 @@ use frame_support::parameter_types;
 use sp_runtime::traits::Dispatchable;
 
 use frame_support::traits::StorageInstance;
+use pallet_supported_tokens_manager::{BalanceLayout, ComplianceHook, BALANCE_PROBE_GAS_LIMIT};
 use precompile_utils::prelude::*;
 use sp_core::{Get, H160, H256, U256};
 use sp_std::marker::PhantomData;
@@ -117,6 +118,10 @@ mod tests;
 /// Solidity selector of the Transfer log, which is the Keccak of the Log signature.
 pub const SELECTOR_LOG_TOKEN_SUPPORT_CHANGE: [u8; 32] = keccak256!("TokenSupportChange(address)");
 
+/// Solidity selector of the ComplianceHookChange log.
+pub const SELECTOR_LOG_COMPLIANCE_HOOK_CHANGE: [u8; 32] =
+	keccak256!("ComplianceHookChange(address,uint32,uint32)");
+
 /// Solidity selector of the Withdraw log, which is the Keccak of the Log signature.
 pub const SELECTOR_LOG_NEW_OWNER: [u8; 32] = keccak256!("NewOwner(address)");
 pub const SELECTOR_LOG_TRANSFER_OWNER: [u8; 32] =
@@ -157,6 +162,8 @@ where
 	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
 	<Runtime as pallet_timestamp::Config>::Moment: Into<U256>,
 {
+	/// Adds a token whose balances are in a `mapping(address => uint256)` at `slot`. The slot is
+	/// checked by writing a balance through it and reading it back with `balanceOf`.
 	#[precompile::public("addToken(address,bytes32)")]
 	fn add_token(handle: &mut impl PrecompileHandle, token: Address, slot: H256) -> EvmResult<()> {
 		let msg_sender = handle.context().caller;
@@ -167,21 +174,151 @@ where
 			return Err(revert("SupportedTokensManager: Invalid address"));
 		}
 
-		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
-		match SupportedTokensManager::add_supported_token(token.into(), slot) {
-			Ok(_) => {
-				handle.record_log_costs_manual(2, 32)?;
-				log2(
-					handle.context().address,
-					SELECTOR_LOG_TOKEN_SUPPORT_CHANGE,
-					Into::<H160>::into(token),
-					solidity::encode_event_data(true),
-				)
-				.record(handle)?;
-				Ok(())
-			}
-			Err(_) => Err(revert("SupportedTokensManager: Token is already supported")),
+		Self::insert_token(handle, token, |token| {
+			SupportedTokensManager::add_supported_token(token, slot)
+		})
+	}
+
+	/// Adds a token whose balances are in a `mapping(address => uint256)`, finding the slot of
+	/// the mapping from the storage read by `balanceOf`.
+	#[precompile::public("addToken(address)")]
+	fn add_discovered_token(handle: &mut impl PrecompileHandle, token: Address) -> EvmResult<()> {
+		let msg_sender = handle.context().caller;
+
+		Self::require_owner(handle, msg_sender)?;
+
+		if token == H160::zero().into() {
+			return Err(revert("SupportedTokensManager: Invalid address"));
+		}
+
+		// Reading the balance, then verifying the slot found
+		handle.record_cost(BALANCE_PROBE_GAS_LIMIT.saturating_mul(3))?;
+		if SupportedTokensManager::is_supported_token(token.into()) {
+			return Err(revert("SupportedTokensManager: Token is already supported"));
+		}
+		let slot = SupportedTokensManager::discover_token_balance_slot(token.into())
+			.ok_or_else(|| revert("SupportedTokensManager: Balance slot not found"))?;
+
+		Self::insert_token(handle, token, |token| {
+			SupportedTokensManager::add_supported_token(token, slot)
+		})
+	}
+
+	/// Adds a token that does not keep balances in a plain `mapping(address => uint256)`. See
+	/// `BalanceLayout` for how the balance of a holder is found from the arguments.
+	#[precompile::public("addTokenWithLayout(address,bytes32,bytes32[],bytes32[],uint256,uint256)")]
+	fn add_token_with_layout(
+		handle: &mut impl PrecompileHandle,
+		token: Address,
+		slot: H256,
+		outer_keys: Vec<H256>,
+		inner_keys: Vec<H256>,
+		offset: U256,
+		mask: U256,
+	) -> EvmResult<()> {
+		let msg_sender = handle.context().caller;
+
+		Self::require_owner(handle, msg_sender)?;
+
+		if token == H160::zero().into() {
+			return Err(revert("SupportedTokensManager: Invalid address"));
 		}
+
+		let layout = BalanceLayout {
+			slot,
+			outer_keys,
+			inner_keys,
+			offset: offset
+				.try_into()
+				.map_err(|_| revert("SupportedTokensManager: Invalid balance layout"))?,
+			mask,
+		};
+
+		if !layout.is_valid() {
+			return Err(revert("SupportedTokensManager: Invalid balance layout"));
+		}
+
+		Self::insert_token(handle, token, |token| {
+			SupportedTokensManager::add_supported_token_with_layout(token, layout)
+		})
+	}
+
+	#[precompile::public("tokenBalanceLayout(address)")]
+	#[precompile::view]
+	fn token_balance_layout(
+		handle: &mut impl PrecompileHandle,
+		token: Address,
+	) -> EvmResult<(H256, Vec<H256>, Vec<H256>, U256, U256)> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(2))?;
+
+		let layout = SupportedTokensManager::get_token_balance_layout(token.into())
+			.ok_or_else(|| revert("SupportedTokensManager: Token not found in supported tokens"))?;
+
+		Ok((
+			layout.slot,
+			layout.outer_keys,
+			layout.inner_keys,
+			layout.offset.into(),
+			layout.mask,
+		))
+	}
+
+	/// Sets the views of `token` consulted before fees move its balances, given by their
+	/// signatures such as `isBlacklisted(address)` and `paused()`. An empty signature skips that
+	/// check, and two empty signatures remove the hook.
+	#[precompile::public("setComplianceHook(address,string,string)")]
+	fn set_compliance_hook(
+		handle: &mut impl PrecompileHandle,
+		token: Address,
+		blacklist: UnboundedString,
+		pause: UnboundedString,
+	) -> EvmResult<()> {
+		let msg_sender = handle.context().caller;
+
+		Self::require_owner(handle, msg_sender)?;
+
+		let hook = ComplianceHook {
+			blacklist: Self::function_selector(blacklist.into()),
+			pause: Self::function_selector(pause.into()),
+		};
+		let selectors = (
+			Self::selector_to_u32(hook.blacklist),
+			Self::selector_to_u32(hook.pause),
+		);
+		let hook = (hook.blacklist.is_some() || hook.pause.is_some()).then_some(hook);
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		SupportedTokensManager::set_token_compliance_hook(token.into(), hook)
+			.map_err(|_| revert("SupportedTokensManager: Token not found in supported tokens"))?;
+
+		handle.record_log_costs_manual(2, 64)?;
+		log2(
+			handle.context().address,
+			SELECTOR_LOG_COMPLIANCE_HOOK_CHANGE,
+			Into::<H160>::into(token),
+			solidity::encode_event_data(selectors),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+
+	/// Selectors of the blacklist and pause views of `token`, zero for the ones not set.
+	#[precompile::public("complianceHook(address)")]
+	#[precompile::view]
+	fn compliance_hook(
+		handle: &mut impl PrecompileHandle,
+		token: Address,
+	) -> EvmResult<(u32, u32)> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let hook = SupportedTokensManager::get_token_compliance_hook(token.into());
+
+		Ok((
+			Self::selector_to_u32(hook.as_ref().and_then(|hook| hook.blacklist)),
+			Self::selector_to_u32(hook.as_ref().and_then(|hook| hook.pause)),
+		))
 	}
 
 	#[precompile::public("supportedTokens()")]
@@ -253,6 +390,46 @@ where
 		Ok(SupportedTokensManager::get_default_token().into())
 	}
 
+	// Adds `token` with `add`, which fails if the runtime cannot verify its balance layout, and
+	// logs the change.
+	fn insert_token(
+		handle: &mut impl PrecompileHandle,
+		token: Address,
+		add: impl FnOnce(H160) -> Result<(), SupportedTokensManager::Error>,
+	) -> EvmResult<()> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		if SupportedTokensManager::is_supported_token(token.into()) {
+			return Err(revert("SupportedTokensManager: Token is already supported"));
+		}
+
+		handle.record_cost(BALANCE_PROBE_GAS_LIMIT.saturating_mul(2))?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		add(token.into())
+			.map_err(|_| revert("SupportedTokensManager: Balance layout could not be verified"))?;
+
+		handle.record_log_costs_manual(2, 32)?;
+		log2(
+			handle.context().address,
+			SELECTOR_LOG_TOKEN_SUPPORT_CHANGE,
+			Into::<H160>::into(token),
+			solidity::encode_event_data(true),
+		)
+		.record(handle)?;
+		Ok(())
+	}
+
+	fn function_selector(signature: Vec<u8>) -> Option<[u8; 4]> {
+		if signature.is_empty() {
+			return None;
+		}
+		let hash = sp_io::hashing::keccak_256(&signature);
+		Some([hash[0], hash[1], hash[2], hash[3]])
+	}
+
+	fn selector_to_u32(selector: Option<[u8; 4]>) -> u32 {
+		selector.map_or(0, u32::from_be_bytes)
+	}
+
 	fn require_owner(handle: &mut impl PrecompileHandle, caller: H160) -> EvmResult<()> {
 		let owner = Self::owner(handle)?;
 
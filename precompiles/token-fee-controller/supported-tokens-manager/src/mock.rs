@@ -126,7 +126,30 @@ pub type PCall = SupportedTokensManagerPrecompileCall<
 	(),
 >;
 
-impl pallet_supported_tokens_manager::Config for Runtime {}
+parameter_types! {
+	pub DiscoveredBalanceSlot: H256 = H256::from_low_u64_be(2);
+	pub UnverifiableBalanceSlot: H256 = H256::from_low_u64_be(7);
+	pub UndiscoverableTokenAddress: H160 = H160::from_str("0x4000000000000000000000000000000000000000").expect("invalid address");
+}
+
+/// Finds `DiscoveredBalanceSlot` for every token but `UndiscoverableTokenAddress`, and accepts
+/// every layout but the ones at `UnverifiableBalanceSlot`.
+pub struct MockBalanceLayoutVerifier;
+
+impl pallet_supported_tokens_manager::BalanceLayoutVerifier for MockBalanceLayoutVerifier {
+	fn discover_balance_slot(token: H160) -> Option<H256> {
+		(token != UndiscoverableTokenAddress::get()).then(DiscoveredBalanceSlot::get)
+	}
+
+	fn verify_balance_layout(_token: H160, layout: &BalanceLayout) -> bool {
+		layout.slot != UnverifiableBalanceSlot::get()
+	}
+}
+
+impl pallet_supported_tokens_manager::Config for Runtime {
+	type BalanceLayoutVerifier = MockBalanceLayoutVerifier;
+	type TokenViewCaller = ();
+}
 
 parameter_types! {
 	pub BlockGasLimit: U256 = U256::max_value();
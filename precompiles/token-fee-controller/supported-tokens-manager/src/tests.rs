@@ -26,14 +26,15 @@ use precompile_utils::{
 	prelude::{log1, Address},
 	testing::{Precompile1, PrecompileTesterExt},
 };
-use sp_core::{H160, H256};
+use sp_core::{H160, H256, U256};
 
 use crate::{
 	mock::{
-		DefaultOwner, ExtBuilder, InitialDefaultTokenFee, PCall, Precompiles, PrecompilesValue,
-		Runtime, SupportedTokensManager,
+		DefaultOwner, DiscoveredBalanceSlot, ExtBuilder, InitialDefaultTokenFee, PCall,
+		Precompiles, PrecompilesValue, Runtime, SupportedTokensManager, UndiscoverableTokenAddress,
+		UnverifiableBalanceSlot,
 	},
-	SELECTOR_LOG_NEW_OWNER,
+	SELECTOR_LOG_COMPLIANCE_HOOK_CHANGE, SELECTOR_LOG_NEW_OWNER, SELECTOR_LOG_TOKEN_SUPPORT_CHANGE,
 };
 
 // No test of invalid selectors since we have a fallback behavior (deposit).
@@ -390,3 +391,306 @@ fn fail_update_default_token_is_not_supported() {
 			});
 	})
 }
+
+#[test]
+fn add_token_with_layout() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::add_token_with_layout {
+					token: solidity::codec::Address(MeaninglessTokenAddress::get()),
+					slot: H256::from_low_u64_be(2),
+					outer_keys: vec![H256::from_low_u64_be(1)],
+					inner_keys: vec![],
+					offset: U256::one(),
+					mask: U256::from(u128::MAX),
+				},
+			)
+			.execute_some();
+
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::token_balance_layout {
+					token: solidity::codec::Address(MeaninglessTokenAddress::get()),
+				},
+			)
+			.execute_returns((
+				H256::from_low_u64_be(2),
+				vec![H256::from_low_u64_be(1)],
+				Vec::<H256>::new(),
+				U256::one(),
+				U256::from(u128::MAX),
+			));
+
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::token_balance_layout {
+					token: solidity::codec::Address(InitialDefaultTokenFee::get()),
+				},
+			)
+			.execute_returns((
+				H256::zero(),
+				Vec::<H256>::new(),
+				Vec::<H256>::new(),
+				U256::zero(),
+				U256::MAX,
+			));
+	});
+}
+
+#[test]
+fn fail_add_token_with_invalid_layout() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::add_token_with_layout {
+					token: solidity::codec::Address(MeaninglessTokenAddress::get()),
+					slot: H256::from_low_u64_be(2),
+					outer_keys: vec![],
+					inner_keys: vec![],
+					offset: U256::zero(),
+					mask: U256::from(0b101),
+				},
+			)
+			.execute_reverts(|x| {
+				x.eq_ignore_ascii_case(b"SupportedTokensManager: Invalid balance layout")
+			});
+
+		precompiles()
+			.prepare_test(
+				UnpermissionedAccount::get(),
+				Precompile1,
+				PCall::add_token_with_layout {
+					token: solidity::codec::Address(MeaninglessTokenAddress::get()),
+					slot: H256::from_low_u64_be(2),
+					outer_keys: vec![],
+					inner_keys: vec![],
+					offset: U256::zero(),
+					mask: U256::MAX,
+				},
+			)
+			.execute_reverts(|x| {
+				x.eq_ignore_ascii_case(b"SupportedTokensManager: Caller is not the owner")
+			});
+	});
+}
+
+#[test]
+fn fail_add_token_with_unverified_slot() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::add_token {
+					token: solidity::codec::Address(MeaninglessTokenAddress::get()),
+					slot: UnverifiableBalanceSlot::get(),
+				},
+			)
+			.execute_reverts(|x| {
+				x.eq_ignore_ascii_case(
+					b"SupportedTokensManager: Balance layout could not be verified",
+				)
+			});
+
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::add_token_with_layout {
+					token: solidity::codec::Address(MeaninglessTokenAddress::get()),
+					slot: UnverifiableBalanceSlot::get(),
+					outer_keys: vec![],
+					inner_keys: vec![],
+					offset: U256::one(),
+					mask: U256::MAX,
+				},
+			)
+			.execute_reverts(|x| {
+				x.eq_ignore_ascii_case(
+					b"SupportedTokensManager: Balance layout could not be verified",
+				)
+			});
+
+		assert!(!SupportedTokensManager::is_supported_token(
+			MeaninglessTokenAddress::get()
+		));
+	});
+}
+
+#[test]
+fn add_token_with_discovered_slot() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::add_discovered_token {
+					token: solidity::codec::Address(MeaninglessTokenAddress::get()),
+				},
+			)
+			.expect_log(log2(
+				Precompile1,
+				SELECTOR_LOG_TOKEN_SUPPORT_CHANGE,
+				MeaninglessTokenAddress::get(),
+				solidity::encode_event_data(true),
+			))
+			.execute_some();
+
+		assert_eq!(
+			SupportedTokensManager::get_token_balance_slot(MeaninglessTokenAddress::get()),
+			Some(DiscoveredBalanceSlot::get())
+		);
+
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::add_discovered_token {
+					token: solidity::codec::Address(MeaninglessTokenAddress::get()),
+				},
+			)
+			.execute_reverts(|x| {
+				x.eq_ignore_ascii_case(b"SupportedTokensManager: Token is already supported")
+			});
+	});
+}
+
+#[test]
+fn fail_add_token_with_undiscovered_slot() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::add_discovered_token {
+					token: solidity::codec::Address(UndiscoverableTokenAddress::get()),
+				},
+			)
+			.execute_reverts(|x| {
+				x.eq_ignore_ascii_case(b"SupportedTokensManager: Balance slot not found")
+			});
+
+		precompiles()
+			.prepare_test(
+				UnpermissionedAccount::get(),
+				Precompile1,
+				PCall::add_discovered_token {
+					token: solidity::codec::Address(MeaninglessTokenAddress::get()),
+				},
+			)
+			.execute_reverts(|x| {
+				x.eq_ignore_ascii_case(b"SupportedTokensManager: Caller is not the owner")
+			});
+	});
+}
+
+#[test]
+fn set_and_remove_compliance_hook() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::set_compliance_hook {
+					token: Address(InitialDefaultTokenFee::get()),
+					blacklist: UnboundedString::from("isBlacklisted(address)"),
+					pause: UnboundedString::from("paused()"),
+				},
+			)
+			.expect_log(log2(
+				Precompile1,
+				SELECTOR_LOG_COMPLIANCE_HOOK_CHANGE,
+				InitialDefaultTokenFee::get(),
+				solidity::encode_event_data((0xfe575a87u32, 0x5c975abbu32)),
+			))
+			.execute_some();
+
+		assert_eq!(
+			SupportedTokensManager::get_token_compliance_hook(InitialDefaultTokenFee::get()),
+			Some(pallet_supported_tokens_manager::ComplianceHook {
+				blacklist: Some([0xfe, 0x57, 0x5a, 0x87]),
+				pause: Some([0x5c, 0x97, 0x5a, 0xbb]),
+			})
+		);
+
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::compliance_hook {
+					token: Address(InitialDefaultTokenFee::get()),
+				},
+			)
+			.execute_returns((0xfe575a87u32, 0x5c975abbu32));
+
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::set_compliance_hook {
+					token: Address(InitialDefaultTokenFee::get()),
+					blacklist: UnboundedString::from(""),
+					pause: UnboundedString::from(""),
+				},
+			)
+			.execute_some();
+
+		assert_eq!(
+			SupportedTokensManager::get_token_compliance_hook(InitialDefaultTokenFee::get()),
+			None
+		);
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::compliance_hook {
+					token: Address(InitialDefaultTokenFee::get()),
+				},
+			)
+			.execute_returns((0u32, 0u32));
+	});
+}
+
+#[test]
+fn fail_set_compliance_hook() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				UnpermissionedAccount::get(),
+				Precompile1,
+				PCall::set_compliance_hook {
+					token: Address(InitialDefaultTokenFee::get()),
+					blacklist: UnboundedString::from("isBlacklisted(address)"),
+					pause: UnboundedString::from(""),
+				},
+			)
+			.execute_reverts(|x| {
+				x.eq_ignore_ascii_case(b"SupportedTokensManager: Caller is not the owner")
+			});
+
+		precompiles()
+			.prepare_test(
+				DefaultOwner::get(),
+				Precompile1,
+				PCall::set_compliance_hook {
+					token: Address(MeaninglessTokenAddress::get()),
+					blacklist: UnboundedString::from(""),
+					pause: UnboundedString::from("paused()"),
+				},
+			)
+			.execute_reverts(|x| {
+				x.eq_ignore_ascii_case(
+					b"SupportedTokensManager: Token not found in supported tokens",
+				)
+			});
+	});
+}
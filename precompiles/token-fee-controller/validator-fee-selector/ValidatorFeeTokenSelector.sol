@@ -13,8 +13,32 @@ interface ValidatorFeeTokenSelector {
 
     function updateDefaultController(address tokenAddress) external;
 
+    function setConversionRateGasLimit(uint64 gasLimit) external;
+
+    function setConversionRateFallback(uint256 numerator, uint256 denominator) external;
+
+    function setConversionRateFailClosed() external;
+
+    function clearConversionRateFallback() external;
+
     // view functions
     function conversionRateController(address validator) external view returns (address);
 
     function defaultController() external view returns (address);
+
+    function conversionRateGasLimit(address validator) external view returns (uint64);
+
+    // mode: 0 = none (1:1), 1 = stored rate, 2 = reject
+    function conversionRateFallback(
+        address validator
+    ) external view returns (uint8 mode, uint256 numerator, uint256 denominator);
+
+    event ValidatorConversionRateGasLimitChanged(address indexed validator, uint64 gasLimit);
+
+    event ValidatorConversionRateFallbackChanged(
+        address indexed validator,
+        uint8 mode,
+        uint256 numerator,
+        uint256 denominator
+    );
 }
@@ -31,6 +31,7 @@ use sp_runtime::traits::Dispatchable;
 use frame_support::pallet_prelude::{StorageValue, ValueQuery};
 use frame_support::traits::StorageInstance;
 
+use pallet_validator_fee_selector::FallbackConversionRate;
 use precompile_utils::prelude::*;
 use sp_core::{Get, H160, H256, U256};
 use sp_std::marker::PhantomData;
@@ -118,6 +119,19 @@ pub const SELECTOR_LOG_VALIDATOR_TOKEN_ACCEPTANCE_CHANGED: [u8; 32] =
 pub const SELECTOR_LOG_VALIDATOR_CONTROLLER_CHANGED: [u8; 32] =
 	keccak256!("ValidatorTokenRateControllerChanged(address,address)");
 
+/// Solidity selector of the Conversion Rate Gas Limit Changed log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_VALIDATOR_CONVERSION_RATE_GAS_LIMIT_CHANGED: [u8; 32] =
+	keccak256!("ValidatorConversionRateGasLimitChanged(address,uint64)");
+
+/// Solidity selector of the Conversion Rate Fallback Changed log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_VALIDATOR_CONVERSION_RATE_FALLBACK_CHANGED: [u8; 32] =
+	keccak256!("ValidatorConversionRateFallbackChanged(address,uint8,uint256,uint256)");
+
+/// Fallback modes returned by `conversionRateFallback(address)`.
+pub const FALLBACK_MODE_NONE: u8 = 0;
+pub const FALLBACK_MODE_RATE: u8 = 1;
+pub const FALLBACK_MODE_REJECT: u8 = 2;
+
 parameter_types! {
 	pub ZeroAddress:H160 = H160::from_str("0x0000000000000000000000000000000000000000").expect("invalid address");
 	pub DefaultAcceptance:bool = false;
@@ -248,6 +262,85 @@ where
 		Ok(ValidatorFeeTokenController::conversion_rate_controller(validator.into()).into())
 	}
 
+	#[precompile::public("setConversionRateGasLimit(uint64)")]
+	fn set_conversion_rate_gas_limit(
+		handle: &mut impl PrecompileHandle,
+		gas_limit: u64,
+	) -> EvmResult {
+		let msg_sender = handle.context().caller;
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let validators = pallet_validator_set::Pallet::<Runtime>::approved_validators();
+
+		if !validators.contains(&msg_sender.into()) {
+			return Err(revert(
+				"ValidatorFeeTokenController: sender is not an approved validator",
+			));
+		}
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+
+		ValidatorFeeTokenController::update_conversion_rate_gas_limit(msg_sender, gas_limit)
+			.map_err(|_| revert("ValidatorFeeTokenController: gas limit too high"))?;
+
+		handle.record_log_costs_manual(2, 32)?;
+		log2(
+			handle.context().address,
+			SELECTOR_LOG_VALIDATOR_CONVERSION_RATE_GAS_LIMIT_CHANGED,
+			msg_sender,
+			solidity::encode_event_data(gas_limit),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("conversionRateGasLimit(address)")]
+	#[precompile::view]
+	fn conversion_rate_gas_limit(
+		handle: &mut impl PrecompileHandle,
+		validator: Address,
+	) -> EvmResult<u64> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		Ok(ValidatorFeeTokenController::conversion_rate_gas_limit(
+			validator.into(),
+		))
+	}
+
+	#[precompile::public("setConversionRateFallback(uint256,uint256)")]
+	fn set_conversion_rate_fallback(
+		handle: &mut impl PrecompileHandle,
+		numerator: U256,
+		denominator: U256,
+	) -> EvmResult {
+		Self::update_conversion_rate_fallback(
+			handle,
+			Some(FallbackConversionRate::Rate(numerator, denominator)),
+		)
+	}
+
+	#[precompile::public("setConversionRateFailClosed()")]
+	fn set_conversion_rate_fail_closed(handle: &mut impl PrecompileHandle) -> EvmResult {
+		Self::update_conversion_rate_fallback(handle, Some(FallbackConversionRate::Reject))
+	}
+
+	#[precompile::public("clearConversionRateFallback()")]
+	fn clear_conversion_rate_fallback(handle: &mut impl PrecompileHandle) -> EvmResult {
+		Self::update_conversion_rate_fallback(handle, None)
+	}
+
+	#[precompile::public("conversionRateFallback(address)")]
+	#[precompile::view]
+	fn conversion_rate_fallback(
+		handle: &mut impl PrecompileHandle,
+		validator: Address,
+	) -> EvmResult<(u8, U256, U256)> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		Ok(Self::fallback_to_solidity(
+			ValidatorFeeTokenController::conversion_rate_fallback(validator.into()),
+		))
+	}
+
 	#[precompile::public("updateDefaultController(address)")]
 	fn update_default_controller(
 		handle: &mut impl PrecompileHandle,
@@ -354,3 +447,60 @@ where
 		Ok(())
 	}
 }
+
+impl<Runtime, ValidatorFeeTokenController, DefaultOwner, Instance>
+	ValidatorFeeManagerPrecompile<Runtime, ValidatorFeeTokenController, DefaultOwner, Instance>
+where
+	DefaultOwner: Get<H160> + 'static,
+	ValidatorFeeTokenController: pallet_validator_fee_selector::ValidatorFeeTokenController,
+	Instance: 'static + InstanceToPrefix,
+	Runtime: pallet_evm::Config + pallet_timestamp::Config + pallet_validator_set::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	<Runtime as pallet_timestamp::Config>::Moment: Into<U256>,
+	<Runtime as frame_system::Config>::AccountId: From<H160>,
+{
+	fn update_conversion_rate_fallback(
+		handle: &mut impl PrecompileHandle,
+		fallback: Option<FallbackConversionRate>,
+	) -> EvmResult {
+		let msg_sender = handle.context().caller;
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let validators = pallet_validator_set::Pallet::<Runtime>::approved_validators();
+
+		if !validators.contains(&msg_sender.into()) {
+			return Err(revert(
+				"ValidatorFeeTokenController: sender is not an approved validator",
+			));
+		}
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+
+		ValidatorFeeTokenController::update_conversion_rate_fallback(msg_sender, fallback)
+			.map_err(|_| revert("ValidatorFeeTokenController: invalid fallback rate"))?;
+
+		handle.record_log_costs_manual(2, 96)?;
+		log2(
+			handle.context().address,
+			SELECTOR_LOG_VALIDATOR_CONVERSION_RATE_FALLBACK_CHANGED,
+			msg_sender,
+			solidity::encode_event_data(Self::fallback_to_solidity(fallback)),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+
+	fn fallback_to_solidity(fallback: Option<FallbackConversionRate>) -> (u8, U256, U256) {
+		match fallback {
+			None => (FALLBACK_MODE_NONE, U256::zero(), U256::zero()),
+			Some(FallbackConversionRate::Rate(numerator, denominator)) => {
+				(FALLBACK_MODE_RATE, numerator, denominator)
+			}
+			Some(FallbackConversionRate::Reject) => {
+				(FALLBACK_MODE_REJECT, U256::zero(), U256::zero())
+			}
+		}
+	}
+}
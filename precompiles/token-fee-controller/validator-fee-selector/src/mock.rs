@@ -21,7 +21,11 @@
 
 use super::*;
 
-use frame_support::{construct_runtime, parameter_types, traits::Everything, weights::Weight};
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{ConstU64, Everything},
+	weights::Weight,
+};
 use frame_system::EnsureRoot;
 use pallet_evm::{EnsureAddressNever, EnsureAddressRoot};
 use pallet_session::{SessionHandler, ShouldEndSession};
@@ -149,6 +153,20 @@ impl pallet_supported_tokens_manager::SupportedTokensManager for MockSupportedTo
 		Ok(())
 	}
 
+	fn add_supported_token_with_layout(
+		_token: H160,
+		_layout: pallet_supported_tokens_manager::BalanceLayout,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+
+	fn set_token_compliance_hook(
+		_token: H160,
+		_hook: Option<pallet_supported_tokens_manager::ComplianceHook>,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+
 	fn remove_supported_token(_token: H160) -> Result<(), Self::Error> {
 		Ok(())
 	}
@@ -171,6 +189,9 @@ impl pallet_supported_tokens_manager::SupportedTokensManager for MockSupportedTo
 impl pallet_validator_fee_selector::Config for Runtime {
 	type SupportedTokensManager = MockSupportedTokensManager;
 	type SimulatorRunner = pallet_evm::runner::stack::Runner<Self>;
+	type RuntimeEvent = RuntimeEvent;
+	type DefaultConversionRateGasLimit = ConstU64<3_000_000>;
+	type MaxConversionRateGasLimit = ConstU64<10_000_000>;
 }
 
 parameter_types! {
@@ -283,6 +304,18 @@ impl Convert<UintAuthorityId, AccountId> for AccountIdOfValidator {
 parameter_types! {
 	pub const MinAuthorities: u32 = 0u32;
 	pub const MaxKeys: u32 = 1000u32;
+	pub const MaxMissedFinalityEpochs: u32 = 3;
+	pub const MinFinalityJustifications: u32 = 1;
+	pub const MaxFinalityVotes: u32 = 3;
+	pub const EquivocationReportLongevity: u64 = 100;
+	pub const MaxRejoinAttempts: u32 = 3;
+	pub const RejoinWindow: u64 = 100;
+	pub const JailDuration: u64 = 100;
+	pub const MaxOfflineRemovals: u32 = 3;
+	pub const OfflineRemovalWindow: u64 = 100;
+	pub const MaxMetadataLength: u32 = 128;
+	pub const MaxMaintenanceDuration: u64 = 100;
+	pub const StatsHistoryDepth: u32 = 10;
 }
 impl pallet_validator_set::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
@@ -293,6 +326,20 @@ impl pallet_validator_set::Config for Runtime {
 	type AuthorityId = UintAuthorityId;
 	type AccountIdOfValidator = AccountIdOfValidator;
 	type MaxKeys = MaxKeys;
+	type SlotAuthorship = ();
+	type StatsHistoryDepth = StatsHistoryDepth;
+	type MaxMaintenanceDuration = MaxMaintenanceDuration;
+	type MaxMetadataLength = MaxMetadataLength;
+	type JailDuration = JailDuration;
+	type MaxOfflineRemovals = MaxOfflineRemovals;
+	type OfflineRemovalWindow = OfflineRemovalWindow;
+	type MaxRejoinAttempts = MaxRejoinAttempts;
+	type RejoinWindow = RejoinWindow;
+	type FinalityVoters = ();
+	type MaxMissedFinalityEpochs = MaxMissedFinalityEpochs;
+	type MinFinalityJustifications = MinFinalityJustifications;
+	type MaxFinalityVotes = MaxFinalityVotes;
+	type EquivocationReportLongevity = EquivocationReportLongevity;
 }
 
 impl pallet_session::Config for Runtime {
@@ -354,14 +401,19 @@ impl ExtBuilder {
 			_marker: Default::default(),
 			accounts: {
 				let mut map = BTreeMap::new();
-				let revert_bytecode = vec![0x60, 0x00, 0x60, 0x00, 0xFD];
+				// Returns (1, 1) for `getConversionRate(address,address,address)`.
+				let controller_bytecode = vec![
+					0x60, 0x00, 0x35, 0x60, 0xE0, 0x1C, 0x63, 0xFF, 0x47, 0xB5, 0xA6, 0x14, 0x60,
+					0x14, 0x57, 0x60, 0x00, 0x60, 0x00, 0xFD, 0x5B, 0x60, 0x01, 0x60, 0x00, 0x52,
+					0x60, 0x01, 0x60, 0x20, 0x52, 0x60, 0x40, 0x60, 0x00, 0xF3,
+				];
 				map.insert(
 					custom_controller,
 					fp_evm::GenesisAccount {
 						nonce: U256::zero(),
 						balance: U256::from(1000000000000000000u128),
 						storage: BTreeMap::new(),
-						code: revert_bytecode,
+						code: controller_bytecode,
 					},
 				);
 				map
@@ -22,7 +22,7 @@ use precompile_utils::{
 	prelude::{log1, log2, log3, Address},
 	testing::{CryptoAlith, Precompile1, PrecompileTesterExt},
 };
-use sp_core::{H160, H256};
+use sp_core::{H160, H256, U256};
 
 use crate::{
 	mock::{
@@ -30,7 +30,10 @@ use crate::{
 		NonCryptoAlith, PCall, Precompiles, PrecompilesValue, Runtime, UnpermissionedAccount,
 		UnpermissionedAccount2,
 	},
-	DefaultAcceptance, SELECTOR_LOG_NEW_OWNER, SELECTOR_LOG_VALIDATOR_CONTROLLER_CHANGED,
+	DefaultAcceptance, FALLBACK_MODE_NONE, FALLBACK_MODE_RATE, FALLBACK_MODE_REJECT,
+	SELECTOR_LOG_NEW_OWNER, SELECTOR_LOG_VALIDATOR_CONTROLLER_CHANGED,
+	SELECTOR_LOG_VALIDATOR_CONVERSION_RATE_FALLBACK_CHANGED,
+	SELECTOR_LOG_VALIDATOR_CONVERSION_RATE_GAS_LIMIT_CHANGED,
 	SELECTOR_LOG_VALIDATOR_TOKEN_ACCEPTANCE_CHANGED,
 };
 
@@ -460,3 +463,162 @@ fn reverts_if_validator_dont_accepts_token() {
 			.execute_some();
 	})
 }
+
+#[test]
+fn set_conversion_rate_gas_limit() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				CryptoAlith,
+				Precompile1,
+				PCall::set_conversion_rate_gas_limit { gas_limit: 100_000 },
+			)
+			.expect_log(log2(
+				Precompile1,
+				SELECTOR_LOG_VALIDATOR_CONVERSION_RATE_GAS_LIMIT_CHANGED,
+				H160::from(CryptoAlith),
+				solidity::encode_event_data(100_000u64),
+			))
+			.execute_some();
+
+		precompiles()
+			.prepare_test(
+				CryptoAlith,
+				Precompile1,
+				PCall::conversion_rate_gas_limit {
+					validator: Address(CryptoAlith.into()),
+				},
+			)
+			.execute_returns(100_000u64);
+	})
+}
+
+#[test]
+fn fail_set_conversion_rate_gas_limit_above_max() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				CryptoAlith,
+				Precompile1,
+				PCall::set_conversion_rate_gas_limit {
+					gas_limit: 10_000_001,
+				},
+			)
+			.execute_reverts(|x| {
+				x.eq_ignore_ascii_case(b"ValidatorFeeTokenController: gas limit too high")
+			});
+	})
+}
+
+#[test]
+fn set_conversion_rate_fallback() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				CryptoAlith,
+				Precompile1,
+				PCall::conversion_rate_fallback {
+					validator: Address(CryptoAlith.into()),
+				},
+			)
+			.execute_returns((FALLBACK_MODE_NONE, U256::zero(), U256::zero()));
+
+		precompiles()
+			.prepare_test(
+				CryptoAlith,
+				Precompile1,
+				PCall::set_conversion_rate_fallback {
+					numerator: 2.into(),
+					denominator: 3.into(),
+				},
+			)
+			.expect_log(log2(
+				Precompile1,
+				SELECTOR_LOG_VALIDATOR_CONVERSION_RATE_FALLBACK_CHANGED,
+				H160::from(CryptoAlith),
+				solidity::encode_event_data((FALLBACK_MODE_RATE, U256::from(2), U256::from(3))),
+			))
+			.execute_some();
+
+		precompiles()
+			.prepare_test(
+				CryptoAlith,
+				Precompile1,
+				PCall::conversion_rate_fallback {
+					validator: Address(CryptoAlith.into()),
+				},
+			)
+			.execute_returns((FALLBACK_MODE_RATE, U256::from(2), U256::from(3)));
+
+		precompiles()
+			.prepare_test(
+				CryptoAlith,
+				Precompile1,
+				PCall::set_conversion_rate_fail_closed {},
+			)
+			.execute_some();
+
+		precompiles()
+			.prepare_test(
+				CryptoAlith,
+				Precompile1,
+				PCall::conversion_rate_fallback {
+					validator: Address(CryptoAlith.into()),
+				},
+			)
+			.execute_returns((FALLBACK_MODE_REJECT, U256::zero(), U256::zero()));
+
+		precompiles()
+			.prepare_test(
+				CryptoAlith,
+				Precompile1,
+				PCall::clear_conversion_rate_fallback {},
+			)
+			.execute_some();
+
+		precompiles()
+			.prepare_test(
+				CryptoAlith,
+				Precompile1,
+				PCall::conversion_rate_fallback {
+					validator: Address(CryptoAlith.into()),
+				},
+			)
+			.execute_returns((FALLBACK_MODE_NONE, U256::zero(), U256::zero()));
+	})
+}
+
+#[test]
+fn fail_set_conversion_rate_fallback_with_zero_denominator() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				CryptoAlith,
+				Precompile1,
+				PCall::set_conversion_rate_fallback {
+					numerator: 2.into(),
+					denominator: 0.into(),
+				},
+			)
+			.execute_reverts(|x| {
+				x.eq_ignore_ascii_case(b"ValidatorFeeTokenController: invalid fallback rate")
+			});
+	})
+}
+
+#[test]
+fn fail_set_conversion_rate_fallback_non_validator() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				NonCryptoAlith::get(),
+				Precompile1,
+				PCall::set_conversion_rate_fail_closed {},
+			)
+			.execute_reverts(|x| {
+				x.eq_ignore_ascii_case(
+					b"ValidatorFeeTokenController: sender is not an approved validator",
+				)
+			});
+	})
+}
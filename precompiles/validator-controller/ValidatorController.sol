@@ -2,6 +2,12 @@
 pragma solidity >=0.8.3;
 
 interface ValidatorController {
+    struct SessionStats {
+        uint32 sessionIndex;
+        uint32 blocksAuthored;
+        uint32 slotsAssigned;
+    }
+
     function addValidator(address validator) external; // onlyOwner
 
     function removeValidator(address validator) external; // onlyOwner
@@ -13,4 +19,42 @@ interface ValidatorController {
     function getValidatorMissingBlocks(
         address validator
     ) external view returns (uint256);
+
+    function getValidatorStats(
+        address validator
+    ) external view returns (SessionStats[] memory);
+
+    event MaintenanceAnnounced(address indexed validator, uint32 until);
+    event MaintenanceEnded(address indexed validator);
+
+    function getMaintenanceWindow(
+        address validator
+    ) external view returns (uint32);
+
+    function announceMaintenance(uint32 until) external; // onlyValidator
+
+    function endMaintenance() external; // onlyValidator
+
+    event ValidatorMetadataSet(address indexed validator);
+
+    function getValidatorMetadata(
+        address validator
+    )
+        external
+        view
+        returns (
+            string memory displayName,
+            string memory website,
+            string memory contact,
+            string memory peerId,
+            address payoutAddress
+        );
+
+    function setValidatorMetadata(
+        string memory displayName,
+        string memory website,
+        string memory contact,
+        string memory peerId,
+        address payoutAddress
+    ) external; // onlyValidator
 }
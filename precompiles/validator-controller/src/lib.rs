@@ -40,6 +40,7 @@ use precompile_utils::prelude::*;
 
 use sp_core::Get;
 use sp_core::{H160, H256, U256};
+use sp_runtime::traits::UniqueSaturatedInto;
 
 use sp_std::marker::PhantomData;
 use sp_std::vec::Vec;
@@ -55,6 +56,10 @@ pub const SELECTOR_LOG_TRANSFER_OWNER: [u8; 32] =
 	keccak256!("OwnershipTransferStarted(address,address)");
 pub const SELECTOR_VALIDATOR_ADDED: [u8; 32] = keccak256!("ValidatorAdded(bytes32)");
 pub const SELECTOR_VALIDATOR_REMOVED: [u8; 32] = keccak256!("ValidatorRemoved(bytes32)");
+pub const SELECTOR_MAINTENANCE_ANNOUNCED: [u8; 32] =
+	keccak256!("MaintenanceAnnounced(address,uint32)");
+pub const SELECTOR_MAINTENANCE_ENDED: [u8; 32] = keccak256!("MaintenanceEnded(address)");
+pub const SELECTOR_VALIDATOR_METADATA_SET: [u8; 32] = keccak256!("ValidatorMetadataSet(address)");
 
 /// Storage prefix for owner.
 pub struct OwnerPrefix;
@@ -222,6 +227,23 @@ where
 		Ok(epochs_missed)
 	}
 
+	#[precompile::public("getValidatorStats(address)")]
+	#[precompile::view]
+	fn get_validator_stats(
+		handle: &mut impl PrecompileHandle,
+		validator: Address,
+	) -> EvmResult<Vec<(u32, u32, u32)>> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let account_id =
+			<Runtime as pallet_evm::Config>::AddressMapping::into_account_id(validator.into());
+		let stats = pallet_validator_set::ValidatorStats::<Runtime>::get(account_id);
+
+		Ok(stats
+			.iter()
+			.map(|s| (s.session_index, s.blocks_authored, s.slots_assigned))
+			.collect())
+	}
+
 	#[precompile::public("addValidator(address)")]
 	fn add_validator(handle: &mut impl PrecompileHandle, new_validator: Address) -> EvmResult {
 		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
@@ -290,4 +312,151 @@ where
 
 		Ok(())
 	}
+
+	#[precompile::public("getMaintenanceWindow(address)")]
+	#[precompile::view]
+	fn get_maintenance_window(
+		handle: &mut impl PrecompileHandle,
+		validator: Address,
+	) -> EvmResult<u32> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let account_id =
+			<Runtime as pallet_evm::Config>::AddressMapping::into_account_id(validator.into());
+
+		Ok(
+			pallet_validator_set::MaintenanceWindows::<Runtime>::get(account_id)
+				.map(|until| until.unique_saturated_into())
+				.unwrap_or_default(),
+		)
+	}
+
+	#[precompile::public("announceMaintenance(uint32)")]
+	fn announce_maintenance(handle: &mut impl PrecompileHandle, until: u32) -> EvmResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let msg_sender = handle.context().caller;
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(Runtime::AccountId::from(msg_sender)).into(),
+			pallet_validator_set::Call::<Runtime>::announce_maintenance {
+				until: until.into(),
+			},
+		)?;
+
+		handle.record_log_costs_manual(2, 32)?;
+		log2(
+			handle.context().address,
+			SELECTOR_MAINTENANCE_ANNOUNCED,
+			Into::<H256>::into(msg_sender),
+			solidity::encode_event_data(until),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("endMaintenance()")]
+	fn end_maintenance(handle: &mut impl PrecompileHandle) -> EvmResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let msg_sender = handle.context().caller;
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(Runtime::AccountId::from(msg_sender)).into(),
+			pallet_validator_set::Call::<Runtime>::end_maintenance {},
+		)?;
+
+		handle.record_log_costs_manual(2, 0)?;
+		log2(
+			handle.context().address,
+			SELECTOR_MAINTENANCE_ENDED,
+			Into::<H256>::into(msg_sender),
+			solidity::encode_event_data(()),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("getValidatorMetadata(address)")]
+	#[precompile::view]
+	fn get_validator_metadata(
+		handle: &mut impl PrecompileHandle,
+		validator: Address,
+	) -> EvmResult<(
+		UnboundedString,
+		UnboundedString,
+		UnboundedString,
+		UnboundedString,
+		Address,
+	)> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let account_id =
+			<Runtime as pallet_evm::Config>::AddressMapping::into_account_id(validator.into());
+
+		// Validators without metadata get empty fields and the zero address.
+		Ok(
+			match pallet_validator_set::ValidatorMetadataOf::<Runtime>::get(account_id) {
+				Some(metadata) => (
+					metadata.display_name.into(),
+					metadata.website.into(),
+					metadata.contact.into(),
+					metadata.peer_id.into(),
+					Runtime::AccountIdMapping::into_evm_address(&metadata.payout_address).into(),
+				),
+				None => (
+					Vec::new().into(),
+					Vec::new().into(),
+					Vec::new().into(),
+					Vec::new().into(),
+					Address(H160::zero()),
+				),
+			},
+		)
+	}
+
+	#[precompile::public("setValidatorMetadata(string,string,string,string,address)")]
+	fn set_validator_metadata(
+		handle: &mut impl PrecompileHandle,
+		display_name: UnboundedString,
+		website: UnboundedString,
+		contact: UnboundedString,
+		peer_id: UnboundedString,
+		payout_address: Address,
+	) -> EvmResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let msg_sender = handle.context().caller;
+		let metadata = pallet_validator_set::ValidatorMetadata {
+			display_name: display_name.into(),
+			website: website.into(),
+			contact: contact.into(),
+			peer_id: peer_id.into(),
+			payout_address: <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(
+				payout_address.into(),
+			),
+		};
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(Runtime::AccountId::from(msg_sender)).into(),
+			pallet_validator_set::Call::<Runtime>::set_validator_metadata { metadata },
+		)?;
+
+		handle.record_log_costs_manual(2, 0)?;
+		log2(
+			handle.context().address,
+			SELECTOR_VALIDATOR_METADATA_SET,
+			Into::<H256>::into(msg_sender),
+			solidity::encode_event_data(()),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
 }
@@ -251,6 +251,18 @@ impl Convert<UintAuthorityId, AccountId> for AccountIdOfValidator {
 parameter_types! {
 	pub const MinAuthorities: u32 = 0u32;
 	pub const MaxKeys: u32 = 1000u32;
+	pub const MaxMissedFinalityEpochs: u32 = 3;
+	pub const MinFinalityJustifications: u32 = 1;
+	pub const MaxFinalityVotes: u32 = 3;
+	pub const EquivocationReportLongevity: u64 = 100;
+	pub const MaxRejoinAttempts: u32 = 3;
+	pub const RejoinWindow: u64 = 100;
+	pub const JailDuration: u64 = 100;
+	pub const MaxOfflineRemovals: u32 = 3;
+	pub const OfflineRemovalWindow: u64 = 100;
+	pub const MaxMetadataLength: u32 = 128;
+	pub const MaxMaintenanceDuration: u64 = 100;
+	pub const StatsHistoryDepth: u32 = 10;
 }
 impl pallet_validator_set::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
@@ -268,6 +280,32 @@ impl pallet_validator_set::Config for Runtime {
 	type MaxKeys = MaxKeys;
 
 	type AccountIdOfValidator = AccountIdOfValidator;
+
+	type SlotAuthorship = ();
+
+	type StatsHistoryDepth = StatsHistoryDepth;
+
+	type MaxMaintenanceDuration = MaxMaintenanceDuration;
+
+	type MaxMetadataLength = MaxMetadataLength;
+
+	type JailDuration = JailDuration;
+
+	type MaxOfflineRemovals = MaxOfflineRemovals;
+	type OfflineRemovalWindow = OfflineRemovalWindow;
+
+	type MaxRejoinAttempts = MaxRejoinAttempts;
+
+	type RejoinWindow = RejoinWindow;
+
+	type FinalityVoters = ();
+
+	type MaxMissedFinalityEpochs = MaxMissedFinalityEpochs;
+
+	type MinFinalityJustifications = MinFinalityJustifications;
+	type MaxFinalityVotes = MaxFinalityVotes;
+
+	type EquivocationReportLongevity = EquivocationReportLongevity;
 }
 
 thread_local! {
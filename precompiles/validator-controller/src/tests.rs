@@ -40,6 +40,12 @@ fn selectors() {
 	assert!(PCall::remove_validator_selectors().contains(&0x40a141ff));
 	assert!(PCall::get_validator_list_selectors().contains(&0xe35c0f7d));
 	assert!(PCall::get_validator_missing_blocks_selectors().contains(&0x41ee9a53));
+	assert!(PCall::get_validator_stats_selectors().contains(&0x9258effc));
+	assert!(PCall::get_maintenance_window_selectors().contains(&0x28a2e95f));
+	assert!(PCall::announce_maintenance_selectors().contains(&0x3fb67fb1));
+	assert!(PCall::end_maintenance_selectors().contains(&0xba9acbac));
+	assert!(PCall::get_validator_metadata_selectors().contains(&0xed717184));
+	assert!(PCall::set_validator_metadata_selectors().contains(&0x8f8df441));
 	assert_eq!(
 		crate::SELECTOR_LOG_NEW_OWNER,
 		&Keccak256::digest(b"NewOwner(address)")[..]
@@ -54,10 +60,16 @@ fn modifiers() {
 		tester.test_view_modifier(PCall::owner_selectors());
 		tester.test_view_modifier(PCall::pending_owner_selectors());
 		tester.test_view_modifier(PCall::get_validator_missing_blocks_selectors());
+		tester.test_view_modifier(PCall::get_validator_stats_selectors());
+		tester.test_view_modifier(PCall::get_maintenance_window_selectors());
+		tester.test_view_modifier(PCall::get_validator_metadata_selectors());
 		tester.test_default_modifier(PCall::transfer_ownership_selectors());
 		tester.test_default_modifier(PCall::claim_ownership_selectors());
 		tester.test_default_modifier(PCall::add_validator_selectors());
 		tester.test_default_modifier(PCall::remove_validator_selectors());
+		tester.test_default_modifier(PCall::announce_maintenance_selectors());
+		tester.test_default_modifier(PCall::end_maintenance_selectors());
+		tester.test_default_modifier(PCall::set_validator_metadata_selectors());
 	});
 }
 
@@ -433,3 +445,180 @@ fn checks_a_validator_missing_blocks() {
 				.execute_returns(U256::from(2));
 		});
 }
+
+#[test]
+fn get_validator_stats() {
+	let sender = UnpermissionedAccount::get();
+	let validator = ValidatorInitial::get();
+	let validators = vec![validator.clone()];
+	ExtBuilder::default()
+		.with_validators(validators.clone())
+		.build()
+		.execute_with(|| {
+			pallet_validator_set::ValidatorStats::<Runtime>::insert(
+				validator.clone(),
+				vec![pallet_validator_set::SessionStats {
+					session_index: 4,
+					blocks_authored: 10,
+					slots_assigned: 12,
+				}],
+			);
+			precompiles()
+				.prepare_test(
+					sender,
+					Precompile1,
+					PCall::get_validator_stats {
+						validator: account_id_to_evm_address(validator.clone()),
+					},
+				)
+				.execute_returns(vec![(4u32, 10u32, 12u32)]);
+		});
+}
+
+#[test]
+fn announce_maintenance() {
+	let validator = ValidatorInitial::get();
+	ExtBuilder::default()
+		.with_validators(vec![validator.clone()])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					H160(validator.0),
+					Precompile1,
+					PCall::announce_maintenance { until: 10 },
+				)
+				.expect_log(log2(
+					Precompile1,
+					SELECTOR_MAINTENANCE_ANNOUNCED,
+					H256::from(H160(validator.0)),
+					solidity::encode_event_data(10u32),
+				))
+				.execute_some();
+
+			precompiles()
+				.prepare_test(
+					UnpermissionedAccount::get(),
+					Precompile1,
+					PCall::get_maintenance_window {
+						validator: account_id_to_evm_address(validator.clone()),
+					},
+				)
+				.execute_returns(10u32);
+		});
+}
+
+#[test]
+fn announce_maintenance_fails_if_sender_not_validator() {
+	let validator = ValidatorInitial::get();
+	ExtBuilder::default()
+		.with_validators(vec![validator.clone()])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					UnpermissionedAccount::get(),
+					Precompile1,
+					PCall::announce_maintenance { until: 10 },
+				)
+				.execute_reverts(|_| true);
+
+			assert_eq!(ValidatorSet::maintenance_windows(validator), None);
+		});
+}
+
+#[test]
+fn end_maintenance() {
+	let validator = ValidatorInitial::get();
+	ExtBuilder::default()
+		.with_validators(vec![validator.clone()])
+		.build()
+		.execute_with(|| {
+			pallet_validator_set::MaintenanceWindows::<Runtime>::insert(validator.clone(), 10);
+
+			precompiles()
+				.prepare_test(H160(validator.0), Precompile1, PCall::end_maintenance {})
+				.expect_log(log2(
+					Precompile1,
+					SELECTOR_MAINTENANCE_ENDED,
+					H256::from(H160(validator.0)),
+					solidity::encode_event_data(()),
+				))
+				.execute_some();
+
+			assert_eq!(ValidatorSet::maintenance_windows(validator), Some(1));
+		});
+}
+
+#[test]
+fn set_validator_metadata() {
+	let validator = ValidatorInitial::get();
+	let payout_address = UnpermissionedAccount2::get();
+	ExtBuilder::default()
+		.with_validators(vec![validator.clone()])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					H160(validator.0),
+					Precompile1,
+					PCall::set_validator_metadata {
+						display_name: "Stability".into(),
+						website: "https://stabilityprotocol.com".into(),
+						contact: "validators@stabilityprotocol.com".into(),
+						peer_id: "12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp".into(),
+						payout_address: payout_address.into(),
+					},
+				)
+				.expect_log(log2(
+					Precompile1,
+					SELECTOR_VALIDATOR_METADATA_SET,
+					H256::from(H160(validator.0)),
+					solidity::encode_event_data(()),
+				))
+				.execute_some();
+
+			precompiles()
+				.prepare_test(
+					UnpermissionedAccount::get(),
+					Precompile1,
+					PCall::get_validator_metadata {
+						validator: account_id_to_evm_address(validator.clone()),
+					},
+				)
+				.execute_returns((
+					UnboundedString::from("Stability"),
+					UnboundedString::from("https://stabilityprotocol.com"),
+					UnboundedString::from("validators@stabilityprotocol.com"),
+					UnboundedString::from("12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp"),
+					Address(payout_address),
+				));
+		});
+}
+
+#[test]
+fn set_validator_metadata_fails_if_sender_not_validator() {
+	ExtBuilder::default()
+		.with_validators(vec![ValidatorInitial::get()])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					UnpermissionedAccount::get(),
+					Precompile1,
+					PCall::set_validator_metadata {
+						display_name: "Stability".into(),
+						website: "".into(),
+						contact: "".into(),
+						peer_id: "".into(),
+						payout_address: UnpermissionedAccount::get().into(),
+					},
+				)
+				.execute_reverts(|_| true);
+
+			assert_eq!(
+				ValidatorSet::validator_metadata(AccountId::from(UnpermissionedAccount::get())),
+				None
+			);
+		});
+}
@@ -0,0 +1,38 @@
+// Copyright © 2022 STABILITY SOLUTIONS, INC. (“STABILITY”)
+// This file is part of the Stability Global Trust Network client
+// software and accompanying documentation (the “Software”).
+
+// You can download and use the Software for free under the terms of
+// the Stability Open License Agreement as published by Stability on
+// Github at https://github.com/stabilityprotocol/stability/blob/master/LICENSE.
+
+// THE SOFTWARE IS PROVIDED “AS IS” WITHOUT WARRANTY OF ANY KIND.
+// STABILITY EXPRESSLY DISCLAIMS ALL WARRANTIES, EXPRESS OR IMPLIED,
+// INCLUDING MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE, AND
+// NON-INFRINGEMENT. IN NO EVENT SHALL OWNER BE LIABLE FOR ANY
+// INDIRECT, INCIDENTAL, SPECIAL OR CONSEQUENTIAL DAMAGES ARISING
+// OUT OF USE OF THE SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF
+// SUCH DAMAGES.
+
+// Please see the Stability Open License Agreement for more
+// information.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_runtime::traits::Block as BlockT;
+
+/// Proof that an authority sealed two different headers for the same Aura slot.
+pub type EquivocationProof<Header, AuthorityId> =
+	sp_consensus_slots::EquivocationProof<Header, AuthorityId>;
+
+sp_api::decl_runtime_apis! {
+	#[api_version(1)]
+	pub trait AuraEquivocationApi<AuthorityId: Codec> {
+		/// Submits an unsigned extrinsic reporting an Aura equivocation. Returns
+		/// `None` if the report could not be submitted to the pool.
+		fn submit_report_equivocation_unsigned_extrinsic(
+			equivocation_proof: EquivocationProof<<Block as BlockT>::Header, AuthorityId>,
+		) -> Option<()>;
+	}
+}
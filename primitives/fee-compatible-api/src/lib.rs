@@ -22,9 +22,14 @@
 use sp_runtime::traits::Block as BlockT;
 
 sp_api::decl_runtime_apis! {
-	#[api_version(1)]
+	#[api_version(2)]
 	pub trait CompatibleFeeApi<AccountId> where
 		AccountId: codec::Codec,  {
 		fn is_compatible_fee(tx: <Block as BlockT>::Extrinsic, validator: AccountId) -> bool;
+
+		/// Returns the account of the validator that authors blocks with the given aura key,
+		/// which for rotated keys is the account of the validator they were rotated from.
+		#[api_version(2)]
+		fn validator_account(authority: sp_core::ecdsa::Public) -> AccountId;
 	}
 }
@@ -0,0 +1,77 @@
+// Copyright © 2022 STABILITY SOLUTIONS, INC. (“STABILITY”)
+// This file is part of the Stability Global Trust Network client
+// software and accompanying documentation (the “Software”).
+
+// You can download and use the Software for free under the terms of
+// the Stability Open License Agreement as published by Stability on
+// Github at https://github.com/stabilityprotocol/stability/blob/master/LICENSE.
+
+// THE SOFTWARE IS PROVIDED “AS IS” WITHOUT WARRANTY OF ANY KIND.
+// STABILITY EXPRESSLY DISCLAIMS ALL WARRANTIES, EXPRESS OR IMPLIED,
+// INCLUDING MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE, AND
+// NON-INFRINGEMENT. IN NO EVENT SHALL OWNER BE LIABLE FOR ANY
+// INDIRECT, INCIDENTAL, SPECIAL OR CONSEQUENTIAL DAMAGES ARISING
+// OUT OF USE OF THE SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF
+// SUCH DAMAGES.
+
+// Please see the Stability Open License Agreement for more
+// information.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_consensus_grandpa::{AuthorityId, AuthoritySignature, RoundNumber};
+use sp_inherents::InherentIdentifier;
+use sp_runtime::RuntimeDebug;
+
+/// Identifier of the inherent reporting the GRANDPA precommits seen by the
+/// block author.
+pub const INHERENT_IDENTIFIER: InherentIdentifier = *b"finvotes";
+
+/// A GRANDPA precommit along with its signature and signer.
+pub type SignedPrecommit<Hash, Number> =
+	finality_grandpa::SignedPrecommit<Hash, Number, AuthoritySignature, AuthorityId>;
+
+/// Precommits of the latest GRANDPA justification seen by the block author.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct FinalityVotes<Hash, Number> {
+	/// Round the precommits were cast in.
+	pub round: RoundNumber,
+	/// Signed precommits of the justification.
+	pub precommits: Vec<SignedPrecommit<Hash, Number>>,
+}
+
+/// Provides the precommits of the latest justification, if any, to the
+/// inherent data.
+#[cfg(feature = "std")]
+pub struct InherentDataProvider<Hash, Number>(pub Option<FinalityVotes<Hash, Number>>);
+
+#[cfg(feature = "std")]
+#[async_trait::async_trait]
+impl<Hash, Number> sp_inherents::InherentDataProvider for InherentDataProvider<Hash, Number>
+where
+	Hash: Encode + Send + Sync,
+	Number: Encode + Send + Sync,
+{
+	async fn provide_inherent_data(
+		&self,
+		inherent_data: &mut sp_inherents::InherentData,
+	) -> Result<(), sp_inherents::Error> {
+		match &self.0 {
+			Some(votes) => inherent_data.put_data(INHERENT_IDENTIFIER, votes),
+			None => Ok(()),
+		}
+	}
+
+	async fn try_handle_error(
+		&self,
+		_identifier: &InherentIdentifier,
+		_error: &[u8],
+	) -> Option<Result<(), sp_inherents::Error>> {
+		None
+	}
+}
@@ -0,0 +1,135 @@
+// Copyright © 2022 STABILITY SOLUTIONS, INC. (“STABILITY”)
+// This file is part of the Stability Global Trust Network client
+// software and accompanying documentation (the “Software”).
+
+// You can download and use the Software for free under the terms of
+// the Stability Open License Agreement as published by Stability on
+// Github at https://github.com/stabilityprotocol/stability/blob/master/LICENSE.
+
+// THE SOFTWARE IS PROVIDED “AS IS” WITHOUT WARRANTY OF ANY KIND.
+// STABILITY EXPRESSLY DISCLAIMS ALL WARRANTIES, EXPRESS OR IMPLIED,
+// INCLUDING MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE, AND
+// NON-INFRINGEMENT. IN NO EVENT SHALL OWNER BE LIABLE FOR ANY
+// INDIRECT, INCIDENTAL, SPECIAL OR CONSEQUENTIAL DAMAGES ARISING
+// OUT OF USE OF THE SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF
+// SUCH DAMAGES.
+
+// Please see the Stability Open License Agreement for more
+// information.
+
+//! Discovery and verification of where a fee token keeps its balances, by running its
+//! `balanceOf` against the chain state.
+
+use super::*;
+
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use pallet_supported_tokens_manager::{
+	BalanceLayout, BalanceLayoutVerifier, BALANCE_PROBE_GAS_LIMIT,
+};
+use sp_runtime::DispatchError;
+
+/// Holder whose balance is read and overwritten to probe a token.
+pub const BALANCE_PROBE_HOLDER: H160 = H160([0xba; 20]);
+
+/// Highest slot of the balances mapping a discovery looks for.
+pub const MAX_DISCOVERED_BALANCE_SLOT: u64 = 256;
+
+// "STABILITY" in ASCII, unlikely to be the balance of the probe holder already.
+const PROBE_BALANCE: u128 = 0x53_5441_4249_4c49_5459;
+
+/// `BalanceLayoutVerifier` calling `balanceOf` of the token in the EVM. Every change the
+/// probe makes to the state is rolled back.
+pub struct BalanceLayoutProbe<T>(PhantomData<T>);
+
+impl<T: pallet_erc20_manager::Config> BalanceLayoutProbe<T>
+where
+	BalanceOf<T>: TryFrom<U256> + Into<U256>,
+{
+	// `balanceOf(BALANCE_PROBE_HOLDER)` of `token`, with the slots of `token` the call read.
+	fn balance_of(token: H160) -> Option<(U256, BTreeSet<H256>)> {
+		let (output, reads) = token_view::call_recording_reads::<T>(
+			BALANCE_PROBE_HOLDER,
+			token,
+			stbl_tools::eth::generate_calldata(
+				"balanceOf(address)",
+				&vec![BALANCE_PROBE_HOLDER.into()],
+			),
+			BALANCE_PROBE_GAS_LIMIT,
+		)
+		.0?;
+
+		match output.len() {
+			32 => Some((U256::from_big_endian(&output), reads)),
+			_ => None,
+		}
+	}
+
+	// Writes a new balance for the probe holder through `layout` and checks that `balanceOf`
+	// returns it. Must run in a storage transaction that is rolled back.
+	fn balance_written_through(token: H160, layout: &BalanceLayout) -> bool {
+		let Some((current_balance, _)) = Self::balance_of(token) else {
+			return false;
+		};
+
+		let (slot, mask) = pallet_erc20_manager::Pallet::<T>::balance_storage_location(
+			layout,
+			BALANCE_PROBE_HOLDER,
+		);
+		let word = AccountStorages::<T>::get(token, slot);
+		let stored_balance = pallet_erc20_manager::Pallet::<T>::decode_balance(word, mask);
+
+		// A balance that fits the mask and differs from both the stored and the returned one
+		let max_balance = mask >> mask.trailing_zeros();
+		let Some(balance) = [
+			U256::from(PROBE_BALANCE) & max_balance,
+			max_balance,
+			max_balance >> 1,
+		]
+		.into_iter()
+		.find(|balance| *balance != current_balance && *balance != stored_balance) else {
+			return false;
+		};
+
+		let Some(word) = pallet_erc20_manager::Pallet::<T>::encode_balance(word, mask, balance)
+		else {
+			return false;
+		};
+		AccountStorages::<T>::insert(token, slot, word);
+
+		matches!(Self::balance_of(token), Some((new_balance, _)) if new_balance == balance)
+	}
+}
+
+impl<T: pallet_erc20_manager::Config> BalanceLayoutVerifier for BalanceLayoutProbe<T>
+where
+	BalanceOf<T>: TryFrom<U256> + Into<U256>,
+{
+	fn discover_balance_slot(token: H160) -> Option<H256> {
+		let (_, reads) = with_transaction(|| {
+			TransactionOutcome::Rollback(Ok::<_, DispatchError>(Self::balance_of(token)))
+		})
+		.ok()
+		.flatten()?;
+
+		// Only the slots whose entry for the probe holder was read can be the balances mapping.
+		// The first one is verified, so that a discovery costs a bounded number of calls.
+		(0..=MAX_DISCOVERED_BALANCE_SLOT)
+			.map(H256::from_low_u64_be)
+			.find(|slot| {
+				reads.contains(&stbl_tools::eth::get_storage_address_for_mapping(
+					BALANCE_PROBE_HOLDER,
+					*slot,
+				))
+			})
+			.filter(|slot| Self::verify_balance_layout(token, &BalanceLayout::mapping(*slot)))
+	}
+
+	fn verify_balance_layout(token: H160, layout: &BalanceLayout) -> bool {
+		with_transaction(|| {
+			TransactionOutcome::Rollback(Ok::<_, DispatchError>(Self::balance_written_through(
+				token, layout,
+			)))
+		})
+		.unwrap_or(false)
+	}
+}
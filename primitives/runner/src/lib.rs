@@ -19,7 +19,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use core::marker::PhantomData;
+use core::{cell::RefCell, marker::PhantomData};
 
 use evm::{
 	backend::Backend as BackendT,
@@ -38,6 +38,8 @@ use frame_support::{
 	weights::Weight,
 };
 use pallet_evm::Pallet;
+use pallet_erc20_manager::TransferLogs;
+use pallet_supported_tokens_manager::ComplianceCache;
 use pallet_evm::{
 	AccountCodes, AccountCodesMetadata, AccountStorages, AddressMapping, BalanceOf,
 	BlockHashMapping, Config, Error, Event, FeeCalculator, OnCreate, Runner as RunnerT,
@@ -54,10 +56,15 @@ use sp_std::{
 };
 use stbl_tools;
 
+pub use balance_probe::*;
+pub use token_view::EvmTokenViewCaller;
+
+mod balance_probe;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
+mod token_view;
 
 pub const LOG_TARGET: &'static str = "runner-evm";
 
@@ -69,6 +76,9 @@ environmental::thread_local_impl!(static IN_EVM: environmental::RefCell<bool> =
 
 pub const TRANSFER_GAS_LIMIT: u64 = 350_000_u64;
 
+/// Maximum number of contracts the dApp share of a fee is split across.
+pub const MAX_FEE_CONSUMERS: usize = 8;
+
 #[derive(Default)]
 pub struct Runner<T: Config, FC: OnChargeDecentralizedNativeTokenFee, U: UserFeeTokenController> {
 	_marker: PhantomData<(T, FC, U)>,
@@ -221,8 +231,29 @@ where
 		let vault = FC::get_fee_vault();
 		let token = FC::get_transaction_fee_token(source);
 
+		// The lookup of the validator's conversion rate is paid by the transaction, as the
+		// controller of the validator is called for the senders it has not seen in the block.
+		let conversion_rate_gas = FC::get_transaction_conversion_rate_gas(source, validator, token);
+
+		// Transactions paying fees in a token the validator rejects are not executed.
+		// Calls without fees, like `eth_call`, fall back to the user's conversion rate.
 		let validator_conversion_rate =
-			FC::get_transaction_conversion_rate(source, validator, token);
+			match FC::get_transaction_conversion_rate(source, validator, token) {
+				Ok(conversion_rate) => Some(conversion_rate),
+				Err(_) if is_transactional && !custom_fee_info.actual_fee.is_zero() => {
+					log::error!(
+						target: LOG_TARGET,
+						"Validator has no conversion rate for the token [validator: {:?}, token: {:?}]",
+						validator,
+						token
+					);
+					return Err(RunnerError {
+						error: Error::<T>::Undefined,
+						weight,
+					});
+				},
+				Err(_) => None,
+			};
 
 		// We compare the user's conversion rate against the validator's conversion rate.
 		// If the user's conversion rate is greater than or equal to the validator's rate,
@@ -231,10 +262,9 @@ where
 		// 
 		// This ensures users don't overpay for transactions while still allowing validators
 		// to enforce their minimum acceptable conversion rate for transactions they process.
-		let actual_conversion_rate = if custom_fee_info.match_validator_conversion_rate_limit(validator_conversion_rate) {
-			validator_conversion_rate
-		} else {
-			custom_fee_info.user_conversion_rate_cap
+		let actual_conversion_rate = match validator_conversion_rate {
+			Some(validator_conversion_rate) if custom_fee_info.match_validator_conversion_rate_limit(validator_conversion_rate) => validator_conversion_rate,
+			_ => custom_fee_info.user_conversion_rate_cap,
 		};
 
 		// Calculate the maximum gas cost with the base fee.
@@ -249,12 +279,18 @@ where
 		// Or a Non-Transactional OP - Read/Call
 		let is_zero_gas_transaction: bool = custom_fee_info.actual_fee == U256::zero();
 
+		// The compliance hook of the fee token is consulted once for all the fee movements, and
+		// the transaction pays for the gas of its views.
+		let mut compliance_cache = ComplianceCache::default();
+		// The `Transfer` logs of the fee movements are added to the transaction receipt.
+		let mut transfer_logs = TransferLogs::default();
+
 		// Ensure the account has enough balance to pay for the transaction.
 		if !is_zero_gas_transaction {
 			// Withdraw all the gas limit from the user's account.
 			// We will refund later if the transaction is inserted into the block.
 			// maximum_gas_cost_with_base_fee * actual_conversion_rate = total_fee
-			FC::withdraw_fee(source, token, actual_conversion_rate, maximum_gas_cost_with_base_fee).map_err(|_| {
+			compliance_cache.using(|| transfer_logs.using(|| FC::withdraw_fee(source, token, actual_conversion_rate, maximum_gas_cost_with_base_fee))).map_err(|_| {
 				log::error!(
 					target: LOG_TARGET, 
 					"Error while withdrawing fee [source: {:?}, token: {:?}, conversion_rate: ({},{}), total_fee: {}]",
@@ -265,7 +301,7 @@ where
 					maximum_gas_cost_with_base_fee
 				);
 				RunnerError {
-					error: Error::<T>::FeeOverflow,
+					error: Error::<T>::WithdrawFailed,
 					weight,
 				}
 			})?;
@@ -281,8 +317,18 @@ where
 		let state = SubstrateStackState::new(&vicinity, metadata, maybe_weight_info);
 		let mut executor = StackExecutor::new_with_precompiles(state, config, precompiles);
 
+		// The fee withdrawal shows up as a `Transfer` in the transaction receipt.
+		Self::record_transfer_logs(&mut executor, transfer_logs.take(), weight)?;
+
 		let (reason, retv) = f(&mut executor);
 
+		// Contracts that consumed gas themselves, so the dApp share reaches the contracts doing
+		// the work behind routers, proxies or wallets.
+		let consumers = match dapp {
+			Some(_) => Self::fee_consumers(executor.state(), precompiles),
+			None => Vec::new(),
+		};
+
 		// Post execution.
 		let used_gas = executor.used_gas();
 		// EFFECTIVE GAS UNITS - The gas units used by the transaction.
@@ -296,6 +342,12 @@ where
 			)),
 			_ => used_gas.into(),
 		};
+		let compliance_gas = U256::from(compliance_cache.used_gas())
+			.min(U256::from(gas_limit).saturating_sub(effective_gas));
+		let effective_gas = effective_gas.saturating_add(compliance_gas);
+		let conversion_rate_gas = U256::from(conversion_rate_gas)
+			.min(U256::from(gas_limit).saturating_sub(effective_gas));
+		let effective_gas = effective_gas.saturating_add(conversion_rate_gas);
 		let effective_gas_w_base_fee = effective_gas.saturating_mul(base_fee);
 
 		log::debug!(
@@ -317,7 +369,7 @@ where
 		if !is_zero_gas_transaction {
 			// Refund the user for the gas used in the transaction.
 			// (maximum_gas_cost_with_base_fee - effective_gas_w_base_fee) * conversion_rate = gas refunded
-			FC::correct_fee(source, token, actual_conversion_rate, maximum_gas_cost_with_base_fee, effective_gas_w_base_fee).map_err(
+			compliance_cache.using(|| transfer_logs.using(|| FC::correct_fee(source, token, actual_conversion_rate, maximum_gas_cost_with_base_fee, effective_gas_w_base_fee))).map_err(
 				|_| {
 					log::error!(target: LOG_TARGET, "Error while correcting fee");
 					RunnerError {
@@ -327,8 +379,8 @@ where
 				},
 			)?;
 
-			let (validator_fee, dapp_fee) =
-				FC::pay_fees(token, actual_conversion_rate, effective_gas_w_base_fee, validator, dapp).map_err(
+			let (validator_fee, dapp_fee, treasury_fee) =
+				transfer_logs.using(|| FC::pay_fees(token, actual_conversion_rate, effective_gas_w_base_fee, validator, dapp, &consumers)).map_err(
 					|_| {
 						log::error!(target: LOG_TARGET, "Error while paying fees",);
 						RunnerError {
@@ -338,14 +390,17 @@ where
 					},
 				)?;
 
+			// The refund and the fee payouts show up as `Transfer`s as well.
+			Self::record_transfer_logs(&mut executor, transfer_logs.take(), weight)?;
+
 			executor
 				.log(
 					vault,
 					sp_std::vec![TRANSACTION_FEE_TOPIC.into()],
 					stbl_tools::eth::args_to_bytes(sp_std::vec![
 						token.into(),
-						stbl_tools::misc::u256_to_h256(validator_fee.checked_add(dapp_fee).unwrap_or_else(|| {
-							log::warn!(target: LOG_TARGET, "Fee addition overflow: validator_fee={}, dapp_fee={}", validator_fee, dapp_fee);
+						stbl_tools::misc::u256_to_h256(validator_fee.checked_add(dapp_fee).and_then(|v| v.checked_add(treasury_fee)).unwrap_or_else(|| {
+							log::warn!(target: LOG_TARGET, "Fee addition overflow: validator_fee={}, dapp_fee={}, treasury_fee={}", validator_fee, dapp_fee, treasury_fee);
 							U256::max_value()
 						})),
 						validator.into(),
@@ -383,20 +438,7 @@ where
 				log.data.len(),
 				log.data
 			);
-			{
-				let event = Event::<T>::Log {
-					log: Log {
-						address: log.address,
-						topics: log.topics.clone(),
-						data: log.data.clone(),
-					},
-				};
-				let event = <<T as Config>::RuntimeEvent as From<Event<T>>>::from(event);
-				let event = <<T as Config>::RuntimeEvent as Into<
-					<T as frame_system::Config>::RuntimeEvent,
-				>>::into(event);
-				<frame_system::Pallet<T>>::deposit_event(event)
-			};
+			Self::deposit_log_event(log.clone());
 		}
 
 		Ok(ExecutionInfoV2 {
@@ -410,6 +452,66 @@ where
 			logs: state.substate.logs,
 		})
 	}
+
+	// Adds the `Transfer` logs of the fee token movements to the executor, so they end up in
+	// the transaction receipt.
+	fn record_transfer_logs<'config, 'precompiles>(
+		executor: &mut StackExecutor<
+			'config,
+			'precompiles,
+			SubstrateStackState<'_, 'config, T>,
+			T::PrecompilesType,
+		>,
+		logs: Vec<Log>,
+		weight: Weight,
+	) -> Result<(), RunnerError<Error<T>>> {
+		for log in logs {
+			executor.log(log.address, log.topics, log.data).map_err(|_| {
+				log::error!(target: LOG_TARGET, "Error while logging token transfer");
+				RunnerError {
+					error: Error::<T>::Undefined,
+					weight,
+				}
+			})?;
+		}
+
+		Ok(())
+	}
+
+	fn deposit_log_event(log: Log) {
+		let event = Event::<T>::Log { log };
+		let event = <<T as Config>::RuntimeEvent as From<Event<T>>>::from(event);
+		let event = <<T as Config>::RuntimeEvent as Into<
+			<T as frame_system::Config>::RuntimeEvent,
+		>>::into(event);
+		<frame_system::Pallet<T>>::deposit_event(event)
+	}
+
+	// The contracts that consumed the most gas in the execution, precompiles excluded.
+	fn fee_consumers(
+		state: &SubstrateStackState<'_, '_, T>,
+		precompiles: &T::PrecompilesType,
+	) -> Vec<(H160, u64)> {
+		let mut consumers: Vec<(H160, u64)> = state
+			.consumed_gas()
+			.iter()
+			.filter(|(address, gas)| {
+				**gas > 0
+					&& !matches!(
+						precompiles.is_precompile(**address, u64::MAX),
+						IsPrecompileResult::Answer {
+							is_precompile: true,
+							..
+						}
+					)
+			})
+			.map(|(address, gas)| (*address, *gas))
+			.collect();
+
+		consumers.sort_by(|a, b| b.1.cmp(&a.1));
+		consumers.truncate(MAX_FEE_CONSUMERS);
+		consumers
+	}
 }
 
 impl<T: Config, FC: OnChargeDecentralizedNativeTokenFee, U: UserFeeTokenController> RunnerT<T>
@@ -688,6 +790,12 @@ struct SubstrateStackSubstate<'config> {
 	metadata: StackSubstateMetadata<'config>,
 	deletes: BTreeSet<H160>,
 	logs: Vec<Log>,
+	// Contract whose code runs in this substate, if it is a call.
+	callee: Option<H160>,
+	// Gas used by the subcalls already attributed to their callees.
+	attributed_gas: u64,
+	// Gas used by each contract itself in the exited subcalls.
+	consumed_gas: BTreeMap<H160, u64>,
 	parent: Option<Box<SubstrateStackSubstate<'config>>>,
 }
 
@@ -706,6 +814,9 @@ impl<'config> SubstrateStackSubstate<'config> {
 			parent: None,
 			deletes: BTreeSet::new(),
 			logs: Vec::new(),
+			callee: None,
+			attributed_gas: 0,
+			consumed_gas: BTreeMap::new(),
 		};
 		mem::swap(&mut entering, self);
 
@@ -718,9 +829,16 @@ impl<'config> SubstrateStackSubstate<'config> {
 		let mut exited = *self.parent.take().expect("Cannot commit on root substate");
 		mem::swap(&mut exited, self);
 
+		let used_gas = exited.metadata.gasometer().total_used_gas();
 		self.metadata.swallow_commit(exited.metadata)?;
 		self.logs.append(&mut exited.logs);
 		self.deletes.append(&mut exited.deletes);
+		self.record_consumed_gas(
+			exited.callee,
+			used_gas,
+			exited.attributed_gas,
+			exited.consumed_gas,
+		);
 
 		sp_io::storage::commit_transaction();
 		Ok(())
@@ -729,7 +847,14 @@ impl<'config> SubstrateStackSubstate<'config> {
 	pub fn exit_revert(&mut self) -> Result<(), ExitError> {
 		let mut exited = *self.parent.take().expect("Cannot discard on root substate");
 		mem::swap(&mut exited, self);
+		let used_gas = exited.metadata.gasometer().total_used_gas();
 		self.metadata.swallow_revert(exited.metadata)?;
+		self.record_consumed_gas(
+			exited.callee,
+			used_gas,
+			exited.attributed_gas,
+			exited.consumed_gas,
+		);
 
 		sp_io::storage::rollback_transaction();
 		Ok(())
@@ -738,12 +863,43 @@ impl<'config> SubstrateStackSubstate<'config> {
 	pub fn exit_discard(&mut self) -> Result<(), ExitError> {
 		let mut exited = *self.parent.take().expect("Cannot discard on root substate");
 		mem::swap(&mut exited, self);
+		let used_gas = exited.metadata.gasometer().total_used_gas();
 		self.metadata.swallow_discard(exited.metadata)?;
+		self.record_consumed_gas(
+			exited.callee,
+			used_gas,
+			exited.attributed_gas,
+			exited.consumed_gas,
+		);
 
 		sp_io::storage::rollback_transaction();
 		Ok(())
 	}
 
+	// Gas is consumed whether the subcall commits or not. The gas a subcall used itself goes to
+	// its callee; without one, e.g. for contract creations, it stays with the caller.
+	fn record_consumed_gas(
+		&mut self,
+		callee: Option<H160>,
+		used_gas: u64,
+		attributed_gas: u64,
+		consumed_gas: BTreeMap<H160, u64>,
+	) {
+		for (address, gas) in consumed_gas {
+			let entry = self.consumed_gas.entry(address).or_default();
+			*entry = entry.saturating_add(gas);
+		}
+
+		match callee {
+			Some(callee) => {
+				let entry = self.consumed_gas.entry(callee).or_default();
+				*entry = entry.saturating_add(used_gas.saturating_sub(attributed_gas));
+				self.attributed_gas = self.attributed_gas.saturating_add(used_gas);
+			}
+			None => self.attributed_gas = self.attributed_gas.saturating_add(attributed_gas),
+		}
+	}
+
 	pub fn deleted(&self, address: H160) -> bool {
 		if self.deletes.contains(&address) {
 			return true;
@@ -794,6 +950,7 @@ pub struct SubstrateStackState<'vicinity, 'config, T> {
 	original_storage: BTreeMap<(H160, H256), H256>,
 	recorded: Recorded,
 	weight_info: Option<WeightInfo>,
+	storage_reads: Option<RefCell<BTreeSet<(H160, H256)>>>,
 	_marker: PhantomData<T>,
 }
 
@@ -810,15 +967,40 @@ impl<'vicinity, 'config, T: Config> SubstrateStackState<'vicinity, 'config, T> {
 				metadata,
 				deletes: BTreeSet::new(),
 				logs: Vec::new(),
+				callee: None,
+				attributed_gas: 0,
+				consumed_gas: BTreeMap::new(),
 				parent: None,
 			},
 			_marker: PhantomData,
 			original_storage: BTreeMap::new(),
 			recorded: Default::default(),
 			weight_info,
+			storage_reads: None,
 		}
 	}
 
+	/// Records the storage slots read from now on, see `storage_reads`.
+	pub fn recording_storage_reads(mut self) -> Self {
+		self.storage_reads = Some(Default::default());
+		self
+	}
+
+	/// Storage slots of `address` read since the recording started.
+	pub fn storage_reads(&self, address: H160) -> BTreeSet<H256> {
+		self.storage_reads
+			.as_ref()
+			.map(|reads| {
+				reads
+					.borrow()
+					.iter()
+					.filter(|(read_address, _)| *read_address == address)
+					.map(|(_, index)| *index)
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
 	pub fn weight_info(&self) -> Option<WeightInfo> {
 		self.weight_info
 	}
@@ -827,6 +1009,11 @@ impl<'vicinity, 'config, T: Config> SubstrateStackState<'vicinity, 'config, T> {
 		&self.recorded
 	}
 
+	/// Gas used by each contract itself, excluding its subcalls, in the calls exited so far.
+	pub fn consumed_gas(&self) -> &BTreeMap<H160, u64> {
+		&self.substate.consumed_gas
+	}
+
 	pub fn info_mut(&mut self) -> (&mut Option<WeightInfo>, &mut Recorded) {
 		(&mut self.weight_info, &mut self.recorded)
 	}
@@ -905,6 +1092,9 @@ where
 	}
 
 	fn storage(&self, address: H160, index: H256) -> H256 {
+		if let Some(reads) = &self.storage_reads {
+			reads.borrow_mut().insert((address, index));
+		}
 		<AccountStorages<T>>::get(address, index)
 	}
 
@@ -1033,12 +1223,16 @@ where
 		// issuance to be reduced. We do not need to replicate this.
 	}
 
-	fn touch(&mut self, _address: H160) {
-		// Do nothing on touch in Substrate.
-		//
+	fn touch(&mut self, address: H160) {
 		// EVM pallet considers all accounts to exist, and distinguish
 		// only empty and non-empty accounts. This avoids many of the
 		// subtle issues in EIP-161.
+		//
+		// A call touches its context address right after entering its
+		// substate, which is all we record to attribute the gas it uses.
+		if self.substate.callee.is_none() {
+			self.substate.callee = Some(address);
+		}
 	}
 
 	fn is_cold(&self, address: H160) -> bool {
@@ -1319,7 +1513,14 @@ pub trait OnChargeDecentralizedNativeTokenFee {
 	fn get_transaction_fee_token(from: H160) -> H160;
 
 	// Get the fee token of the validator and its conversion rate.
-	fn get_transaction_conversion_rate(sender: H160, validator: H160, token: H160) -> (U256, U256);
+	fn get_transaction_conversion_rate(
+		sender: H160,
+		validator: H160,
+		token: H160,
+	) -> Result<(U256, U256), Self::Error>;
+
+	// Gas the transaction pays for looking up the conversion rate of the validator.
+	fn get_transaction_conversion_rate_gas(sender: H160, validator: H160, token: H160) -> u64;
 
 	// Get fee vault address
 	fn get_fee_vault() -> H160;
@@ -1341,12 +1542,14 @@ pub trait OnChargeDecentralizedNativeTokenFee {
 		actual_amount: U256,
 	) -> Result<(), Self::Error>;
 
-	// Distributes the fee to the validator and dApp.
+	// Distributes the fee to the validator, the dApp and the treasury. The dApp share is split
+	// across `consumers` by the gas each one consumed, or goes to `to` if there are none.
 	fn pay_fees(
 		token: H160,
 		conversion_rate: (U256, U256),
 		actual_amount: U256,
 		validator: H160,
 		to: Option<H160>,
-	) -> Result<(U256, U256), Self::Error>;
+		consumers: &[(H160, u64)],
+	) -> Result<(U256, U256, U256), Self::Error>;
 }
@@ -34,6 +34,7 @@ use sp_runtime::{
 	traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Verify},
 	MultiSignature,
 };
+use std::cell::RefCell;
 use std::collections::BTreeMap;
 
 pub type Signature = MultiSignature;
@@ -131,6 +132,18 @@ impl pallet_timestamp::Config for Runtime {
 	type WeightInfo = ();
 }
 
+thread_local! {
+	pub static FEE_CONSUMERS: RefCell<Vec<(H160, u64)>> = RefCell::new(Vec::new());
+	pub static FEE_TRANSFER_LOG: RefCell<Option<Log>> = RefCell::new(None);
+}
+
+// Records `FEE_TRANSFER_LOG`, if set, as the log of a fee token movement.
+fn record_fee_transfer_log() {
+	if let Some(log) = FEE_TRANSFER_LOG.with(|v| v.borrow().clone()) {
+		let _ = pallet_erc20_manager::TransferLogs::collect(log);
+	}
+}
+
 pub struct MockDNTFeeController;
 impl crate::OnChargeDecentralizedNativeTokenFee for MockDNTFeeController {
 	type Error = ();
@@ -143,8 +156,12 @@ impl crate::OnChargeDecentralizedNativeTokenFee for MockDNTFeeController {
 		_sender: H160,
 		_validator: H160,
 		_token: H160,
-	) -> (U256, U256) {
-		(Default::default(), Default::default())
+	) -> Result<(U256, U256), Self::Error> {
+		Ok((Default::default(), Default::default()))
+	}
+
+	fn get_transaction_conversion_rate_gas(_sender: H160, _validator: H160, _token: H160) -> u64 {
+		Default::default()
 	}
 
 	fn get_fee_vault() -> H160 {
@@ -157,6 +174,7 @@ impl crate::OnChargeDecentralizedNativeTokenFee for MockDNTFeeController {
 		_conversion_rate: (U256, U256),
 		_amount: U256,
 	) -> Result<(), Self::Error> {
+		record_fee_transfer_log();
 		Ok(())
 	}
 
@@ -176,8 +194,11 @@ impl crate::OnChargeDecentralizedNativeTokenFee for MockDNTFeeController {
 		_actual_amount: U256,
 		_validator: H160,
 		_to: Option<H160>,
-	) -> Result<(U256, U256), Self::Error> {
-		Ok((Default::default(), Default::default()))
+		consumers: &[(H160, u64)],
+	) -> Result<(U256, U256, U256), Self::Error> {
+		FEE_CONSUMERS.with(|v| *v.borrow_mut() = consumers.to_vec());
+		record_fee_transfer_log();
+		Ok((Default::default(), Default::default(), Default::default()))
 	}
 }
 
@@ -224,6 +245,15 @@ impl pallet_evm::Config for Runtime {
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
 }
 
+impl pallet_supported_tokens_manager::Config for Runtime {
+	type BalanceLayoutVerifier = StabilityRunner::BalanceLayoutProbe<Self>;
+	type TokenViewCaller = StabilityRunner::EvmTokenViewCaller<Self>;
+}
+
+impl pallet_erc20_manager::Config for Runtime {
+	type SupportedTokensManager = SupportedTokensManager;
+}
+
 // Configure a mock runtime to test the pallet.
 construct_runtime!(
 	pub enum Runtime {
@@ -231,6 +261,8 @@ construct_runtime!(
 		Balances: pallet_balances,
 		Evm: pallet_evm,
 		Timestamp: pallet_timestamp,
+		SupportedTokensManager: pallet_supported_tokens_manager,
+		ERC20Manager: pallet_erc20_manager,
 	}
 );
 
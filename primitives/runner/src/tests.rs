@@ -25,8 +25,13 @@ use crate::mock::MockUserFeeTokenController;
 
 use super::*;
 use evm::ExitSucceed;
-use mock::{new_test_ext, Balances, MockDNTFeeController, Runtime};
+use mock::{
+	new_test_ext, Balances, ERC20SlotZero, MockDNTFeeController, Runtime, SupportedTokensManager,
+};
 use pallet_evm::AddressMapping;
+use pallet_supported_tokens_manager::{
+	BalanceLayout, BalanceLayoutVerifier, SupportedTokensManager as _,
+};
 use sp_core::{bytes::from_hex, H160, U256};
 use sp_runtime::traits::BlakeTwo256;
 
@@ -217,3 +222,164 @@ fn transaction_fee_log_not_emitted() {
 		assert_eq!(response.logs.len(), 0);
 	})
 }
+
+#[test]
+fn fee_consumers_follow_consumed_gas() {
+	new_test_ext().execute_with(|| {
+		let config = pallet_evm::EvmConfig::london();
+
+		let acc = H160::from_low_u64_be(1);
+		let router = H160::from_low_u64_be(0x1000);
+		let worker = H160::from_low_u64_be(0x2000);
+
+		// SSTORE(0, 1)
+		pallet_evm::AccountCodes::<Runtime>::insert(worker, from_hex("0x6001600055").unwrap());
+		// CALL(gas(), worker, 0, 0, 0, 0, 0)
+		let mut router_code = from_hex("0x60006000600060006000").unwrap();
+		router_code.push(0x73);
+		router_code.extend_from_slice(worker.as_bytes());
+		router_code.extend_from_slice(&[0x5a, 0xf1, 0x00]);
+		pallet_evm::AccountCodes::<Runtime>::insert(router, router_code);
+
+		let result = Runner::<Runtime, MockDNTFeeController, MockUserFeeTokenController>::call(
+			acc,
+			router,
+			vec![],
+			U256::from(0),
+			1_000_000,
+			Some(U256::from(1)),
+			None,
+			None,
+			vec![],
+			true,
+			false,
+			None,
+			None,
+			&config,
+		);
+
+		assert!(result.is_ok());
+
+		let consumers = mock::FEE_CONSUMERS.with(|v| v.borrow().clone());
+		assert_eq!(consumers.len(), 2);
+		assert_eq!(consumers[0].0, worker);
+		assert_eq!(consumers[1].0, router);
+		assert!(consumers[0].1 > consumers[1].1);
+		assert!(consumers[1].1 > 0);
+	})
+}
+
+#[test]
+fn transfer_logs_are_added_to_the_receipt() {
+	new_test_ext().execute_with(|| {
+		frame_system::Pallet::<Runtime>::set_block_number(1);
+		let config = pallet_evm::EvmConfig::london();
+
+		let acc = H160::from_low_u64_be(1);
+		let token_addr = H160::from_str("0x22D598E0a9a1b474CdC7c6fBeA0B4F83E12046a9").unwrap();
+
+		let fee_log = Log {
+			address: token_addr,
+			topics: vec![H256::from_low_u64_be(1)],
+			data: vec![],
+		};
+		mock::FEE_TRANSFER_LOG.with(|v| *v.borrow_mut() = Some(fee_log.clone()));
+
+		let result = Runner::<Runtime, MockDNTFeeController, MockUserFeeTokenController>::call(
+			acc,
+			token_addr,
+			stbl_tools::eth::generate_calldata(
+				"transfer(recipient, amount)",
+				&vec![acc.into(), H256::from_low_u64_be(100)],
+			),
+			U256::from(0),
+			u64::MAX,
+			Some(U256::from(1)),
+			None,
+			None,
+			vec![],
+			true,
+			false,
+			None,
+			None,
+			&config,
+		);
+
+		let response = result.unwrap();
+		assert_eq!(response.logs.len(), 3);
+		assert_eq!(response.logs[0], fee_log);
+		assert_eq!(response.logs[1], fee_log);
+		assert_eq!(response.logs[2].topics[0], TRANSACTION_FEE_TOPIC.into());
+	})
+}
+
+#[test]
+fn balance_probe_discovers_the_balances_mapping() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			BalanceLayoutProbe::<Runtime>::discover_balance_slot(ERC20SlotZero::get()),
+			Some(H256::from_low_u64_be(0))
+		);
+		// Accounts without code have no balances to discover
+		assert_eq!(
+			BalanceLayoutProbe::<Runtime>::discover_balance_slot(H160::from_low_u64_be(1)),
+			None
+		);
+	});
+}
+
+#[test]
+fn balance_probe_verifies_layouts_without_changing_the_state() {
+	new_test_ext().execute_with(|| {
+		let token = ERC20SlotZero::get();
+		let holder_slot = stbl_tools::eth::get_storage_address_for_mapping(
+			BALANCE_PROBE_HOLDER,
+			H256::from_low_u64_be(0),
+		);
+
+		assert!(BalanceLayoutProbe::<Runtime>::verify_balance_layout(
+			token,
+			&BalanceLayout::mapping(H256::from_low_u64_be(0))
+		));
+		// The allowances of the token are a mapping too, but `balanceOf` does not read them
+		assert!(!BalanceLayoutProbe::<Runtime>::verify_balance_layout(
+			token,
+			&BalanceLayout::mapping(H256::from_low_u64_be(1))
+		));
+		assert!(!BalanceLayoutProbe::<Runtime>::verify_balance_layout(
+			token,
+			&BalanceLayout {
+				offset: 1,
+				..BalanceLayout::mapping(H256::from_low_u64_be(0))
+			}
+		));
+
+		assert_eq!(
+			pallet_evm::AccountStorages::<Runtime>::get(token, holder_slot),
+			H256::zero()
+		);
+		assert_eq!(
+			pallet_evm::Pallet::<Runtime>::account_basic(&BALANCE_PROBE_HOLDER)
+				.0
+				.nonce,
+			U256::zero()
+		);
+	});
+}
+
+#[test]
+fn tokens_with_an_unverified_slot_are_not_supported() {
+	new_test_ext().execute_with(|| {
+		let token = ERC20SlotZero::get();
+
+		assert!(
+			SupportedTokensManager::add_supported_token(token, H256::from_low_u64_be(1)).is_err()
+		);
+		assert!(!SupportedTokensManager::is_supported_token(token));
+
+		assert!(
+			SupportedTokensManager::add_supported_token(token, H256::from_low_u64_be(0)).is_ok()
+		);
+		assert!(SupportedTokensManager::is_supported_token(token));
+	});
+}
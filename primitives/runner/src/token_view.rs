@@ -0,0 +1,84 @@
+// Copyright © 2022 STABILITY SOLUTIONS, INC. (“STABILITY”)
+// This file is part of the Stability Global Trust Network client
+// software and accompanying documentation (the “Software”).
+
+// You can download and use the Software for free under the terms of
+// the Stability Open License Agreement as published by Stability on
+// Github at https://github.com/stabilityprotocol/stability/blob/master/LICENSE.
+
+// THE SOFTWARE IS PROVIDED “AS IS” WITHOUT WARRANTY OF ANY KIND.
+// STABILITY EXPRESSLY DISCLAIMS ALL WARRANTIES, EXPRESS OR IMPLIED,
+// INCLUDING MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE, AND
+// NON-INFRINGEMENT. IN NO EVENT SHALL OWNER BE LIABLE FOR ANY
+// INDIRECT, INCIDENTAL, SPECIAL OR CONSEQUENTIAL DAMAGES ARISING
+// OUT OF USE OF THE SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF
+// SUCH DAMAGES.
+
+// Please see the Stability Open License Agreement for more
+// information.
+
+//! Calls to token contracts made by the runtime itself, outside of any transaction.
+
+use super::*;
+
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use pallet_supported_tokens_manager::{TokenViewCaller, TOKEN_VIEW_GAS_LIMIT};
+use sp_runtime::DispatchError;
+
+/// Calls `target` with `input` from `caller` without charging any fee. Returns the output of a
+/// successful call and the storage slots of `target` it read, and the gas the call used. The
+/// changes made by the call are kept, so it must run in a storage transaction that is rolled
+/// back.
+pub(crate) fn call_recording_reads<T: Config>(
+	caller: H160,
+	target: H160,
+	input: Vec<u8>,
+	gas_limit: u64,
+) -> (Option<(Vec<u8>, BTreeSet<H256>)>, u64)
+where
+	BalanceOf<T>: TryFrom<U256> + Into<U256>,
+{
+	let vicinity = Vicinity {
+		gas_price: U256::zero(),
+		origin: caller,
+	};
+	let config = T::config();
+	let precompiles = T::PrecompilesValue::get();
+
+	let metadata = StackSubstateMetadata::new(gas_limit, config);
+	let state = SubstrateStackState::<T>::new(&vicinity, metadata, None).recording_storage_reads();
+	let mut executor = StackExecutor::new_with_precompiles(state, config, &precompiles);
+
+	let (reason, output) =
+		executor.transact_call(caller, target, U256::zero(), input, gas_limit, Vec::new());
+
+	let used_gas = executor.used_gas();
+
+	match reason.is_succeed() {
+		true => (
+			Some((output, executor.state().storage_reads(target))),
+			used_gas,
+		),
+		false => (None, used_gas),
+	}
+}
+
+/// `TokenViewCaller` running the views in the EVM.
+pub struct EvmTokenViewCaller<T>(PhantomData<T>);
+
+impl<T: Config> TokenViewCaller for EvmTokenViewCaller<T>
+where
+	BalanceOf<T>: TryFrom<U256> + Into<U256>,
+{
+	fn call_view(token: H160, input: Vec<u8>) -> (Option<Vec<u8>>, u64) {
+		with_transaction(|| {
+			let (result, used_gas) =
+				call_recording_reads::<T>(H160::zero(), token, input, TOKEN_VIEW_GAS_LIMIT);
+			TransactionOutcome::Rollback(Ok::<_, DispatchError>((
+				result.map(|(output, _)| output),
+				used_gas,
+			)))
+		})
+		.unwrap_or((None, TOKEN_VIEW_GAS_LIMIT))
+	}
+}
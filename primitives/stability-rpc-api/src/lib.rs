@@ -19,12 +19,61 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use sp_core::H160;
-use sp_runtime::traits::Block as BlockT;
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::{H160, U256};
+use sp_runtime::{traits::Block as BlockT, RuntimeDebug};
 use sp_std::vec::Vec;
 
+/// Performance counters of a validator during a past session.
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(
+	feature = "std",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "camelCase")
+)]
+pub struct ValidatorSessionStats {
+	pub session_index: u32,
+	pub blocks_authored: u32,
+	pub slots_assigned: u32,
+}
+
+/// Public information published by a validator about itself.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ValidatorMetadata {
+	pub display_name: Vec<u8>,
+	pub website: Vec<u8>,
+	pub contact: Vec<u8>,
+	pub peer_id: Vec<u8>,
+	pub payout_address: H160,
+}
+
+/// Reward a holder can claim from the fee rewards vault in a token.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(
+	feature = "std",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "camelCase")
+)]
+pub struct ClaimableReward {
+	pub token: H160,
+	pub amount: U256,
+}
+
+/// Current base fee per gas and elasticity, in parts per million, of the base fee.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(
+	feature = "std",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "camelCase")
+)]
+pub struct BaseFeeParameters {
+	pub base_fee_per_gas: U256,
+	pub elasticity: u32,
+}
+
 sp_api::decl_runtime_apis! {
-	#[api_version(1)]
+	#[api_version(2)]
 	pub trait StabilityRpcApi {
 		fn get_supported_tokens() -> Vec<H160>;
 
@@ -32,6 +81,18 @@ sp_api::decl_runtime_apis! {
 
 		fn get_active_validator_list() -> Vec<H160>;
 
+		#[api_version(2)]
+		fn get_validator_stats(validator: H160) -> Vec<ValidatorSessionStats>;
+
+		#[api_version(2)]
+		fn get_validator_metadata(validator: H160) -> Option<ValidatorMetadata>;
+
+		#[api_version(2)]
+		fn get_claimable_rewards(holder: H160) -> Vec<ClaimableReward>;
+
+		#[api_version(2)]
+		fn get_base_fee_parameters() -> BaseFeeParameters;
+
 		fn convert_sponsored_transaction(transaction: fp_ethereum::Transaction, meta_trx_sponsor: H160, meta_trx_sponsor_signature: Vec<u8>) -> <Block as BlockT>::Extrinsic;
 	}
 }
@@ -129,11 +129,14 @@ pub fn args_to_bytes(args: sp_std::vec::Vec<H256>) -> Vec<u8> {
 }
 
 pub fn get_storage_address_for_mapping(address: H160, var_slot: H256) -> H256 {
-	let u256_address = H256::from(address);
-	let address_bytes = u256_address.as_bytes();
+	get_storage_address_for_key(H256::from(address), var_slot)
+}
+
+pub fn get_storage_address_for_key(key: H256, var_slot: H256) -> H256 {
+	let key_bytes = key.as_bytes();
 	let balance_slot_bytes = var_slot.as_bytes();
 
-	let input = &[&address_bytes[..], &balance_slot_bytes[..]].concat();
+	let input = &[&key_bytes[..], &balance_slot_bytes[..]].concat();
 
 	sha3::Keccak256::new()
 		.chain_update(input)
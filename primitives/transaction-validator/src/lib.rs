@@ -23,6 +23,7 @@ use core::{marker::PhantomData, ops::Mul};
 use fp_ethereum::TransactionData;
 use fp_evm::{CheckEvmTransaction, CheckEvmTransactionConfig, FeeCalculator};
 use pallet_ethereum::InvalidTransactionWrapper;
+use pallet_supported_tokens_manager::SupportedTokensManager;
 use pallet_user_fee_selector::UserFeeTokenController;
 use sp_core::{Get, H160, U256};
 use sp_runtime::transaction_validity::{
@@ -77,6 +78,29 @@ where
 		}
 	}
 
+	/// Rejects the transactions whose fee token cannot be moved from the sender to `fee_vault`,
+	/// like when either is blacklisted by the token or while it is paused. Fees failing to move
+	/// once the transaction is included would revert it along with its nonce.
+	pub fn check_fee_token_compliance(
+		origin: &H160,
+		fee_vault: &H160,
+	) -> Result<(), TransactionValidityError> {
+		let token =
+			<pallet_user_fee_selector::Pallet<T> as UserFeeTokenController>::get_user_fee_token(
+				*origin,
+			);
+
+		match <T as pallet_user_fee_selector::Config>::SupportedTokensManager::is_compliant(
+			token,
+			&[*origin, *fee_vault],
+		) {
+			true => Ok(()),
+			false => Err(TransactionValidityError::Invalid(
+				InvalidTransaction::Payment,
+			)),
+		}
+	}
+
 	fn build_validity_success_transaction(
 		origin: &H160,
 		transaction: &ethereum::TransactionV2,
@@ -206,10 +206,28 @@ impl pallet_supported_tokens_manager::SupportedTokensManager for MockSupportedTo
 		Ok(())
 	}
 
+	fn add_supported_token_with_layout(
+		_token: H160,
+		_layout: pallet_supported_tokens_manager::BalanceLayout,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+
+	fn set_token_compliance_hook(
+		_token: H160,
+		_hook: Option<pallet_supported_tokens_manager::ComplianceHook>,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+
 	fn is_supported_token(_token: H160) -> bool {
 		false
 	}
 
+	fn is_compliant(_token: H160, accounts: &[H160]) -> bool {
+		!accounts.contains(&BlacklistedAccount::get())
+	}
+
 	fn remove_supported_token(_token: H160) -> Result<(), Self::Error> {
 		Ok(())
 	}
@@ -257,6 +275,7 @@ impl pallet_user_fee_selector::Config for Runtime {
 parameter_types! {
 	pub NoFundsAccount: H160 = H160::from_str("A38395b264f232ffF4bb294b5947092E359dDE88").expect("invalid address");
 	pub FundedAccount: H160 = H160::from_str("0x2dEA828C816cC4D7CF195E0D220CB75354f47F2F").expect("invalid address");
+	pub BlacklistedAccount: H160 = H160::from_str("0x00000000000000000000000000000000000000b1").expect("invalid address");
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -19,7 +19,7 @@
 
 #![cfg(test)]
 
-use crate::mock::{new_test_ext, FundedAccount, NoFundsAccount, Runtime};
+use crate::mock::{new_test_ext, BlacklistedAccount, FundedAccount, NoFundsAccount, Runtime};
 
 use ethereum::{EIP2930Transaction, TransactionAction, TransactionV2 as Transaction};
 use fp_evm::FeeCalculator;
@@ -28,6 +28,7 @@ use sp_core::{H160, H256, U256};
 use sp_runtime::transaction_validity::{InvalidTransaction, TransactionValidityError};
 
 parameter_types! {
+	pub FeeVault: H160 = H160::from_low_u64_be(0x807);
 	pub SampleTransaction: Transaction = Transaction::EIP2930(EIP2930Transaction {
 		chain_id: 0u64,
 		nonce: U256::from(0),
@@ -105,3 +106,40 @@ fn no_fee_transaction_rejected() {
 		))
 	})
 }
+
+#[test]
+fn non_compliant_account_rejected() {
+	new_test_ext().execute_with(|| {
+		assert!(
+			crate::FallbackTransactionValidator::<Runtime>::check_fee_token_compliance(
+				&FundedAccount::get(),
+				&FeeVault::get()
+			)
+			.is_ok()
+		);
+		assert!(matches!(
+			crate::FallbackTransactionValidator::<Runtime>::check_fee_token_compliance(
+				&BlacklistedAccount::get(),
+				&FeeVault::get()
+			),
+			Err(TransactionValidityError::Invalid(
+				InvalidTransaction::Payment
+			))
+		));
+	})
+}
+
+#[test]
+fn non_compliant_fee_vault_rejected() {
+	new_test_ext().execute_with(|| {
+		assert!(matches!(
+			crate::FallbackTransactionValidator::<Runtime>::check_fee_token_compliance(
+				&FundedAccount::get(),
+				&BlacklistedAccount::get()
+			),
+			Err(TransactionValidityError::Invalid(
+				InvalidTransaction::Payment
+			))
+		));
+	})
+}
@@ -81,6 +81,7 @@ use frame_support::weights::constants::RocksDbWeight as RuntimeDbWeight;
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
+use pallet_session::historical as pallet_session_historical;
 // Frontier
 use fp_rpc::TransactionStatus;
 use pallet_ethereum::{Call::transact, PostLogContent, Transaction as EthereumTransaction};
@@ -95,8 +96,8 @@ pub use frame_support::{
 	genesis_builder_helper::{build_state, get_preset},
 	parameter_types,
 	traits::{
-		ConstBool, ConstU32, ConstU8, EitherOfDiverse, FindAuthor, KeyOwnerProofSystem, OnFinalize,
-		OnTimestampSet, Randomness,
+		ConstBool, ConstU32, ConstU8, Contains, EitherOfDiverse, FindAuthor, KeyOwnerProofSystem,
+		OnFinalize, OnTimestampSet, Randomness,
 	},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight},
@@ -111,9 +112,17 @@ use pallet_user_fee_selector;
 
 mod stability_config;
 use stability_config::{
-	COUNCIL_MAX_MEMBERS, COUNCIL_MAX_PROPOSALS, COUNCIL_MOTION_MINUTES_DURATION,
-	DEFAULT_ELASTICITY, DEFAULT_FEE_TOKEN, EXISTENTIAL_DEPOSIT, GAS_BASE_FEE, MAXIMUM_BLOCK_LENGTH,
-	MILLISECS_PER_BLOCK, SESSION_MINUTES_DURATION, VALIDATOR_SET_MIN_VALIDATORS,
+	CONVERSION_RATE_DEFAULT_GAS_LIMIT, CONVERSION_RATE_MAX_GAS_LIMIT, COUNCIL_MAX_MEMBERS,
+	COUNCIL_MAX_PROPOSALS, COUNCIL_MOTION_MINUTES_DURATION, DEFAULT_ELASTICITY, DEFAULT_FEE_TOKEN,
+	EQUIVOCATION_REPORT_SESSIONS, EXISTENTIAL_DEPOSIT, FEE_REWARDS_MAX_PAYOUTS_PER_BLOCK,
+	GAS_BASE_FEE, MAXIMUM_BLOCK_LENGTH, MAX_ELASTICITY, MAX_ELASTICITY_CHANGE, MAX_GAS_BASE_FEE,
+	MAX_GAS_BASE_FEE_CHANGE, MILLISECS_PER_BLOCK, MIN_GAS_BASE_FEE, SESSION_MINUTES_DURATION,
+	VALIDATOR_SET_JAIL_HOURS, VALIDATOR_SET_MAX_MAINTENANCE_HOURS,
+	VALIDATOR_SET_MAX_METADATA_LENGTH, VALIDATOR_SET_MAX_MISSED_FINALITY_EPOCHS,
+	VALIDATOR_SET_MAX_OFFLINE_REMOVALS, VALIDATOR_SET_MAX_REJOIN_ATTEMPTS,
+	VALIDATOR_SET_MIN_FINALITY_JUSTIFICATIONS, VALIDATOR_SET_MIN_VALIDATORS,
+	VALIDATOR_SET_OFFLINE_REMOVAL_WINDOW_HOURS, VALIDATOR_SET_REJOIN_WINDOW_HOURS,
+	VALIDATOR_SET_STATS_HISTORY_DEPTH,
 };
 
 mod precompiles;
@@ -277,7 +286,7 @@ impl frame_system::Config for Runtime {
 	/// The set code logic, just the default since we're not a parachain.
 	type OnSetCode = ();
 	type MaxConsumers = ConstU32<16>;
-	type SingleBlockMigrations = ();
+	type SingleBlockMigrations = Migrations;
 	type MultiBlockMigrator = ();
 	type PreInherents = ();
 	type PostInherents = ();
@@ -291,18 +300,24 @@ parameter_types! {
 impl pallet_aura::Config for Runtime {
 	type AuthorityId = AuraId;
 	type MaxAuthorities = MaxAuthorities;
-	type DisabledValidators = ();
+	type DisabledValidators = Session;
 	type AllowMultipleBlocksPerSlot = ConstBool<false>;
 	type SlotDuration = frame_support::traits::ConstU64<SLOT_DURATION>;
 }
 
+parameter_types! {
+	pub ReportLongevity: u64 = EQUIVOCATION_REPORT_SESSIONS as u64 * Period::get() as u64;
+	pub const MaxSetIdSessionEntries: u32 = EQUIVOCATION_REPORT_SESSIONS;
+}
+
 impl pallet_grandpa::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
 	type MaxAuthorities = ConstU32<32>;
-	type MaxSetIdSessionEntries = ();
-	type KeyOwnerProof = sp_core::Void;
-	type EquivocationReportSystem = ();
+	type MaxSetIdSessionEntries = MaxSetIdSessionEntries;
+	type KeyOwnerProof = sp_session::MembershipProof;
+	type EquivocationReportSystem =
+		pallet_grandpa::EquivocationReportSystem<Self, Offences, Historical, ReportLongevity>;
 	type MaxNominators = ();
 }
 
@@ -359,7 +374,13 @@ where
 		let token = DNTFeeController::get_transaction_fee_token(from);
 		let validator = EVM::find_author();
 		let conversion_rate =
-			DNTFeeController::get_transaction_conversion_rate(from, validator, token);
+			DNTFeeController::get_transaction_conversion_rate(from, validator, token).map_err(
+				|_| {
+					TransactionValidityError::Invalid(
+						frame_support::pallet_prelude::InvalidTransaction::Payment,
+					)
+				},
+			)?;
 		let fee = U256::from(_fee.saturated_into::<u128>());
 
 		DNTFeeController::withdraw_fee(from, token, conversion_rate, fee).map_err(|_x| {
@@ -386,7 +407,13 @@ where
 
 		let token = DNTFeeController::get_transaction_fee_token(from);
 		let conversion_rate =
-			DNTFeeController::get_transaction_conversion_rate(from, validator, token);
+			DNTFeeController::get_transaction_conversion_rate(from, validator, token).map_err(
+				|_| {
+					TransactionValidityError::Invalid(
+						frame_support::pallet_prelude::InvalidTransaction::Payment,
+					)
+				},
+			)?;
 
 		DNTFeeController::correct_fee(
 			from,
@@ -401,7 +428,7 @@ where
 			)
 		})?;
 
-		DNTFeeController::pay_fees(token, conversion_rate, corrected_fee, validator, None)
+		DNTFeeController::pay_fees(token, conversion_rate, corrected_fee, validator, None, &[])
 			.map_err(|_x| {
 				TransactionValidityError::Invalid(
 					frame_support::pallet_prelude::InvalidTransaction::Payment,
@@ -433,9 +460,7 @@ impl<F: FindAuthor<u32>> FindAuthor<H160> for FindAuthorLinkedOrTruncated<F> {
 			let authority_id =
 				pallet_aura::Authorities::<Runtime>::get()[author_index as usize].clone();
 
-			let bytes: [u8; 33] = authority_id.as_slice().try_into().unwrap();
-			let signer: EthereumSigner = sp_core::ecdsa::Public::from(bytes).into();
-			return Some(signer.into_account().into());
+			return Some(AccountIdOfValidator::convert(authority_id).into());
 		}
 		None
 	}
@@ -451,9 +476,7 @@ impl<F: FindAuthor<u32>> FindAuthor<AccountId> for FindBlockAuthorityId<F> {
 			let authority_id =
 				pallet_aura::Authorities::<Runtime>::get()[author_index as usize].clone();
 
-			let bytes: [u8; 33] = authority_id.as_slice().try_into().unwrap();
-			let signer: EthereumSigner = sp_core::ecdsa::Public::from(bytes).into();
-			return Some(signer.into_account().into());
+			return Some(AccountIdOfValidator::convert(authority_id));
 		}
 		None
 	}
@@ -554,12 +577,23 @@ impl pallet_user_fee_selector::Config for Runtime {
 	type SupportedTokensManager = pallet_supported_tokens_manager::Pallet<Self>;
 	type ERC20Manager = pallet_erc20_manager::Pallet<Self>;
 }
+parameter_types! {
+	pub const DefaultConversionRateGasLimit: u64 = CONVERSION_RATE_DEFAULT_GAS_LIMIT;
+	pub const MaxConversionRateGasLimit: u64 = CONVERSION_RATE_MAX_GAS_LIMIT;
+}
+
 impl pallet_validator_fee_selector::Config for Runtime {
 	type SupportedTokensManager = pallet_supported_tokens_manager::Pallet<Self>;
 	type SimulatorRunner = pallet_evm::runner::stack::Runner<Self>;
+	type RuntimeEvent = RuntimeEvent;
+	type DefaultConversionRateGasLimit = DefaultConversionRateGasLimit;
+	type MaxConversionRateGasLimit = MaxConversionRateGasLimit;
 }
 
-impl pallet_supported_tokens_manager::Config for Runtime {}
+impl pallet_supported_tokens_manager::Config for Runtime {
+	type BalanceLayoutVerifier = runner::BalanceLayoutProbe<Self>;
+	type TokenViewCaller = runner::EvmTokenViewCaller<Self>;
+}
 
 parameter_types! {
 	pub DefaultBaseFeePerGas: U256 = U256::from(GAS_BASE_FEE);
@@ -586,6 +620,24 @@ impl pallet_base_fee::Config for Runtime {
 	type DefaultElasticity = DefaultElasticity;
 }
 
+parameter_types! {
+	pub MinBaseFeePerGas: U256 = U256::from(MIN_GAS_BASE_FEE);
+	pub MaxBaseFeePerGas: U256 = U256::from(MAX_GAS_BASE_FEE);
+	pub const MaxBaseFeePerGasChange: Permill = MAX_GAS_BASE_FEE_CHANGE;
+	pub const MaxElasticity: Permill = MAX_ELASTICITY;
+	pub const MaxElasticityChange: Permill = MAX_ELASTICITY_CHANGE;
+}
+
+impl pallet_base_fee_controller::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ControlOrigin = EnsureRootOrHalfTechCommittee;
+	type MinBaseFeePerGas = MinBaseFeePerGas;
+	type MaxBaseFeePerGas = MaxBaseFeePerGas;
+	type MaxBaseFeePerGasChange = MaxBaseFeePerGasChange;
+	type MaxElasticity = MaxElasticity;
+	type MaxElasticityChange = MaxElasticityChange;
+}
+
 impl pallet_hotfix_sufficients::Config for Runtime {
 	type AddressMapping = IdentityAddressMapping;
 	type WeightInfo = pallet_hotfix_sufficients::weights::SubstrateWeight<Self>;
@@ -622,6 +674,16 @@ impl pallet_root_controller::Config for Runtime {
 
 parameter_types! {
 	pub const MinAuthorities: u32 = VALIDATOR_SET_MIN_VALIDATORS;
+	pub const StatsHistoryDepth: u32 = VALIDATOR_SET_STATS_HISTORY_DEPTH;
+	pub const MaxMaintenanceDuration: BlockNumber = VALIDATOR_SET_MAX_MAINTENANCE_HOURS * HOURS;
+	pub const MaxMetadataLength: u32 = VALIDATOR_SET_MAX_METADATA_LENGTH;
+	pub const JailDuration: BlockNumber = VALIDATOR_SET_JAIL_HOURS * HOURS;
+	pub const MaxOfflineRemovals: u32 = VALIDATOR_SET_MAX_OFFLINE_REMOVALS;
+	pub const OfflineRemovalWindow: BlockNumber = VALIDATOR_SET_OFFLINE_REMOVAL_WINDOW_HOURS * HOURS;
+	pub const MaxRejoinAttempts: u32 = VALIDATOR_SET_MAX_REJOIN_ATTEMPTS;
+	pub const RejoinWindow: BlockNumber = VALIDATOR_SET_REJOIN_WINDOW_HOURS * HOURS;
+	pub const MaxMissedFinalityEpochs: u32 = VALIDATOR_SET_MAX_MISSED_FINALITY_EPOCHS;
+	pub const MinFinalityJustifications: u32 = VALIDATOR_SET_MIN_FINALITY_JUSTIFICATIONS;
 }
 
 type EnsureRootOrHalfTechCommittee = EitherOfDiverse<
@@ -639,12 +701,79 @@ impl SessionBlockManager<BlockNumber> for PeriodicSessionBlockManager {
 pub struct AccountIdOfValidator;
 impl Convert<AuraId, AccountId> for AccountIdOfValidator {
 	fn convert(authority_id: AuraId) -> AccountId {
+		// Rotated keys keep the account of the validator they were rotated from.
+		if let Some(owner) =
+			pallet_validator_keys_controller::KeyOwners::<Runtime>::get(&authority_id)
+		{
+			return owner;
+		}
+
 		let bytes: [u8; 33] = authority_id.as_slice().try_into().unwrap();
 		let signer: EthereumSigner = sp_core::ecdsa::Public::from(bytes).into();
 		return signer.into_account().into();
 	}
 }
 
+pub struct AuraSlotAuthorship;
+impl pallet_validator_set::SlotAuthorship<AccountId> for AuraSlotAuthorship {
+	fn current_slot() -> u64 {
+		pallet_aura::CurrentSlot::<Runtime>::get().into()
+	}
+
+	fn authorities_len() -> u32 {
+		pallet_aura::Authorities::<Runtime>::decode_len().unwrap_or(0) as u32
+	}
+
+	fn authorities() -> Vec<AccountId> {
+		pallet_aura::Authorities::<Runtime>::get()
+			.into_iter()
+			.map(AccountIdOfValidator::convert)
+			.collect()
+	}
+}
+
+pub struct GrandpaFinalityVoters;
+impl pallet_validator_set::FinalityVoters<AccountId> for GrandpaFinalityVoters {
+	type Votes = stbl_primitives_finality_votes::FinalityVotes<Hash, BlockNumber>;
+
+	// Votes of a previous authority set don't verify against the current one.
+	fn justification(votes: &Self::Votes) -> (u64, u64) {
+		(Grandpa::current_set_id(), votes.round)
+	}
+
+	fn votes_len(votes: &Self::Votes) -> u32 {
+		votes.precommits.len() as u32
+	}
+
+	// Precommits are checked against the current authority set, and credited
+	// to the owner of the key that signed them, as the order of the authority
+	// set doesn't follow the session validators. Rotated keys keep their owner.
+	fn voters(votes: &Self::Votes) -> Vec<AccountId> {
+		let set_id = Grandpa::current_set_id();
+		let authorities = Grandpa::grandpa_authorities();
+
+		votes
+			.precommits
+			.iter()
+			.filter(|x| authorities.iter().any(|(id, _)| *id == x.id))
+			.filter(|x| {
+				let message = finality_grandpa::Message::Precommit(x.precommit.clone());
+				let payload = fg_primitives::localized_payload(votes.round, set_id, &message);
+				sp_runtime::RuntimeAppPublic::verify(&x.id, &payload, &x.signature)
+			})
+			.filter_map(|x| {
+				pallet_validator_keys_controller::FinalizationKeyOwners::<Runtime>::get(&x.id)
+					.or_else(|| {
+						pallet_session::KeyOwner::<Runtime>::get((
+							<GrandpaId as sp_runtime::RuntimeAppPublic>::ID,
+							sp_runtime::RuntimeAppPublic::to_raw_vec(&x.id),
+						))
+					})
+			})
+			.collect()
+	}
+}
+
 impl pallet_validator_set::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type AddRemoveOrigin = EnsureRootOrHalfTechCommittee;
@@ -654,6 +783,22 @@ impl pallet_validator_set::Config for Runtime {
 	type AuthorityId = AuraId;
 	type MaxKeys = MaxKeys;
 	type AccountIdOfValidator = AccountIdOfValidator;
+	type SlotAuthorship = AuraSlotAuthorship;
+	type StatsHistoryDepth = StatsHistoryDepth;
+	type MaxMaintenanceDuration = MaxMaintenanceDuration;
+	type MaxMetadataLength = MaxMetadataLength;
+	type JailDuration = JailDuration;
+	type MaxOfflineRemovals = MaxOfflineRemovals;
+	type OfflineRemovalWindow = OfflineRemovalWindow;
+	type MaxRejoinAttempts = MaxRejoinAttempts;
+	type RejoinWindow = RejoinWindow;
+	type FinalityVoters = GrandpaFinalityVoters;
+	type MaxMissedFinalityEpochs = MaxMissedFinalityEpochs;
+	type MinFinalityJustifications = MinFinalityJustifications;
+	// Justifications hold a precommit of each GRANDPA authority at most,
+	// unless the authority equivocated.
+	type MaxFinalityVotes = <Runtime as pallet_grandpa::Config>::MaxAuthorities;
+	type EquivocationReportLongevity = ReportLongevity;
 }
 
 pub struct SessionKeysBuilder;
@@ -688,12 +833,23 @@ impl pallet_session::Config for Runtime {
 	type ValidatorIdOf = pallet_validator_set::ValidatorOf<Self>;
 	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
 	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
-	type SessionManager = ValidatorSet;
+	type SessionManager = pallet_session_historical::NoteHistoricalRoot<Self, ValidatorSet>;
 	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
 	type Keys = opaque::SessionKeys;
 	type WeightInfo = ();
 }
 
+impl pallet_session_historical::Config for Runtime {
+	type FullIdentification = AccountId;
+	type FullIdentificationOf = pallet_validator_set::ValidatorOf<Self>;
+}
+
+impl pallet_offences::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type IdentificationTuple = pallet_session_historical::IdentificationTuple<Self>;
+	type OnOffenceHandler = ValidatorSet;
+}
+
 parameter_types! {
 	pub const MaxKeys: u32 = 10_000;
 	pub const MaxPeerInHeartbeats: u32 = 10_000;
@@ -795,7 +951,43 @@ impl pallet_upgrade_runtime_proposal::Config for Runtime {
 	type MaxSizeOfCode = MaxSizeOfCode;
 }
 
-impl pallet_fee_rewards_vault::Config for Runtime {}
+pub struct FeeRewardsVaultAddress;
+impl Get<H160> for FeeRewardsVaultAddress {
+	fn get() -> H160 {
+		DNTFeeController::get_fee_vault()
+	}
+}
+
+/// Only the holders that could claim their rewards through the fee rewards vault precompile
+/// are paid out automatically.
+pub struct FeeRewardsPayoutFilter;
+impl Contains<H160> for FeeRewardsPayoutFilter {
+	fn contains(holder: &H160) -> bool {
+		precompile_fee_rewards_vault_controller::holder_can_claim::<Runtime>(*holder)
+	}
+}
+
+/// Holders of fee rewards that are validators, whose rewards held before the vault recorded
+/// them were earned as validators.
+pub struct FeeRewardsValidators;
+impl Contains<H160> for FeeRewardsValidators {
+	fn contains(holder: &H160) -> bool {
+		precompile_fee_rewards_vault_controller::holder_is_validator::<Runtime>(*holder)
+	}
+}
+
+parameter_types! {
+	pub const FeeRewardsMaxPayoutsPerBlock: u32 = FEE_REWARDS_MAX_PAYOUTS_PER_BLOCK;
+}
+
+impl pallet_fee_rewards_vault::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ERC20Manager = pallet_erc20_manager::Pallet<Runtime>;
+	type VaultAddress = FeeRewardsVaultAddress;
+	type PayoutFilter = FeeRewardsPayoutFilter;
+	type PayoutPeriod = Period;
+	type MaxPayoutsPerBlock = FeeRewardsMaxPayoutsPerBlock;
+}
 
 #[frame_support::pallet]
 pub mod pallet_manual_seal {
@@ -856,6 +1048,9 @@ construct_runtime!(
 		MetaTransactions: pallet_sponsored_transactions,
 		ZeroGasTransactions: pallet_zero_gas_transactions,
 		ManualSeal: pallet_manual_seal,
+		Historical: pallet_session_historical,
+		Offences: pallet_offences,
+		BaseFeeController: pallet_base_fee_controller,
 	}
 );
 
@@ -912,6 +1107,10 @@ pub type CheckedExtrinsic =
 	fp_self_contained::CheckedExtrinsic<AccountId, RuntimeCall, SignedExtra, H160>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Migrations to run on the next runtime upgrade.
+pub type Migrations =
+	(pallet_fee_rewards_vault::migrations::v2::MigrateToV2<Runtime, FeeRewardsValidators>,);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -952,6 +1151,13 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
 						FallbackTransactionValidator::check_actual_balance(info, call)
 					}
 					_ => result,
+				})
+				.map(|result| {
+					FallbackTransactionValidator::<Runtime>::check_fee_token_compliance(
+						info,
+						&DNTFeeController::get_fee_vault(),
+					)?;
+					result
 				}),
 			_ => None,
 		}
@@ -971,6 +1177,13 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
 						FallbackTransactionValidator::check_actual_balance(info, call).map(|_| ())
 					}
 					_ => result,
+				})
+				.map(|result| {
+					FallbackTransactionValidator::<Runtime>::check_fee_token_compliance(
+						info,
+						&DNTFeeController::get_fee_vault(),
+					)?;
+					result
 				}),
 			_ => None,
 		}
@@ -1634,26 +1847,42 @@ impl_runtime_apis! {
 		}
 
 		fn submit_report_equivocation_unsigned_extrinsic(
-			_equivocation_proof: fg_primitives::EquivocationProof<
+			equivocation_proof: fg_primitives::EquivocationProof<
 				<Block as BlockT>::Hash,
 				NumberFor<Block>,
 			>,
-			_key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
+			key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
 		) -> Option<()> {
-			None
+			let key_owner_proof = key_owner_proof.decode()?;
+
+			Grandpa::submit_unsigned_equivocation_report(
+				equivocation_proof,
+				key_owner_proof,
+			)
 		}
 
 		fn generate_key_ownership_proof(
 			_set_id: fg_primitives::SetId,
-			_authority_id: GrandpaId,
+			authority_id: GrandpaId,
 		) -> Option<fg_primitives::OpaqueKeyOwnershipProof> {
-			// NOTE: this is the only implementation possible since we've
-			// defined our key owner proof type as a bottom type (i.e. a type
-			// with no values).
-			None
+			Historical::prove((fg_primitives::KEY_TYPE, authority_id))
+				.map(|proof| proof.encode())
+				.map(fg_primitives::OpaqueKeyOwnershipProof::new)
+		}
+	}
+
+	impl stbl_primitives_aura_equivocation_api::AuraEquivocationApi<Block, AuraId> for Runtime {
+		fn submit_report_equivocation_unsigned_extrinsic(
+			equivocation_proof: stbl_primitives_aura_equivocation_api::EquivocationProof<
+				<Block as BlockT>::Header,
+				AuraId,
+			>,
+		) -> Option<()> {
+			ValidatorSet::submit_unsigned_aura_equivocation_report(equivocation_proof)
 		}
 	}
 
+	#[api_version(2)]
 	impl stbl_primitives_fee_compatible_api::CompatibleFeeApi<Block, AccountId> for Runtime {
 		fn is_compatible_fee(tx: <Block as BlockT>::Extrinsic, validator: AccountId) -> bool {
 			match tx.0.function {
@@ -1666,7 +1895,10 @@ impl_runtime_apis! {
 
 					let source_address = source_address_option.unwrap();
 					let source_fee_token = <pallet_user_fee_selector::Pallet<Runtime>>::get_user_fee_token(source_address);
-					let validator_conversion_rate = <pallet_validator_fee_selector::Pallet<Runtime>>::conversion_rate(source_address, validator.into(), source_fee_token);
+					let validator_conversion_rate = match <pallet_validator_fee_selector::Pallet<Runtime>>::conversion_rate(source_address, validator.into(), source_fee_token) {
+						Ok(validator_conversion_rate) => validator_conversion_rate,
+						Err(_) => return false,
+					};
 					let fee = pallet_base_fee::BaseFeePerGas::<Runtime>::get();
 					let custom_fee_info = CustomFeeInfo::new(fee, &transaction);
 
@@ -1679,8 +1911,13 @@ impl_runtime_apis! {
 				_ => true, // always return true for non-ethereum transactions
 			}
 		}
+
+		fn validator_account(authority: sp_core::ecdsa::Public) -> AccountId {
+			AccountIdOfValidator::convert(AuraId::from(authority))
+		}
 	}
 
+	#[api_version(2)]
 	impl stability_rpc_api::StabilityRpcApi<Block> for Runtime {
 		fn get_supported_tokens() -> Vec<H160> {
 			<pallet_supported_tokens_manager::Pallet<Runtime> as OtherSupportedTokensManager>::get_supported_tokens()
@@ -1705,6 +1942,42 @@ impl_runtime_apis! {
 
 		}
 
+		fn get_validator_stats(validator: H160) -> Vec<stability_rpc_api::ValidatorSessionStats> {
+			<pallet_validator_set::Pallet<Runtime>>::validator_stats(AccountId::from(validator))
+			.into_iter()
+			.map(|stats| stability_rpc_api::ValidatorSessionStats {
+				session_index: stats.session_index,
+				blocks_authored: stats.blocks_authored,
+				slots_assigned: stats.slots_assigned,
+			})
+			.collect()
+		}
+
+		fn get_validator_metadata(validator: H160) -> Option<stability_rpc_api::ValidatorMetadata> {
+			<pallet_validator_set::Pallet<Runtime>>::validator_metadata(AccountId::from(validator))
+			.map(|metadata| stability_rpc_api::ValidatorMetadata {
+				display_name: metadata.display_name,
+				website: metadata.website,
+				contact: metadata.contact,
+				peer_id: metadata.peer_id,
+				payout_address: <Runtime as pallet_custom_balances::Config>::AccountIdMapping::into_evm_address(&metadata.payout_address),
+			})
+		}
+
+		fn get_claimable_rewards(holder: H160) -> Vec<stability_rpc_api::ClaimableReward> {
+			<pallet_fee_rewards_vault::Pallet<Runtime>>::get_claimable_rewards(holder)
+			.into_iter()
+			.map(|(token, amount)| stability_rpc_api::ClaimableReward { token, amount })
+			.collect()
+		}
+
+		fn get_base_fee_parameters() -> stability_rpc_api::BaseFeeParameters {
+			stability_rpc_api::BaseFeeParameters {
+				base_fee_per_gas: pallet_base_fee::BaseFeePerGas::<Runtime>::get(),
+				elasticity: pallet_base_fee::Elasticity::<Runtime>::get().deconstruct(),
+			}
+		}
+
 		fn convert_sponsored_transaction(transaction: EthereumTransaction, meta_trx_sponsor: H160, meta_trx_sponsor_signature: Vec<u8>) -> <Block as BlockT>::Extrinsic {
 			UncheckedExtrinsic::new_unsigned(
 				pallet_sponsored_transactions::Call::<Runtime>::send_sponsored_transaction { transaction,  meta_trx_sponsor, meta_trx_sponsor_signature }.into(),
@@ -1754,7 +2027,12 @@ impl_runtime_apis! {
 
 #[cfg(test)]
 mod tests {
-	use super::{Runtime, WeightPerGas};
+	use super::*;
+	use frame_support::{assert_ok, traits::OnFinalize, BoundedVec};
+	use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
+	use sp_core::{ecdsa, ed25519, Pair};
+	use sp_runtime::{BuildStorage, Digest};
+
 	#[test]
 	fn configured_base_extrinsic_weight_is_evm_compatible() {
 		let min_ethereum_transaction_weight = WeightPerGas::get() * 21_000;
@@ -1763,4 +2041,79 @@ mod tests {
 			.base_extrinsic;
 		assert!(base_extrinsic.ref_time() <= min_ethereum_transaction_weight.ref_time());
 	}
+
+	#[test]
+	fn blocks_authored_with_rotated_keys_are_credited_to_the_validator() {
+		let current = ecdsa::Pair::from_seed(&[1u8; 32]);
+		let rotated = ecdsa::Pair::from_seed(&[2u8; 32]);
+		let validator: AccountId = EthereumSigner::from(current.public()).into_account();
+
+		let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::<Runtime>::default()
+			.build_storage()
+			.unwrap()
+			.into();
+
+		ext.execute_with(|| {
+			pallet_validator_set::ApprovedValidators::<Runtime>::put(vec![validator]);
+			pallet_session::NextKeys::<Runtime>::insert(
+				validator,
+				SessionKeys {
+					aura: current.public().into(),
+					grandpa: ed25519::Pair::from_seed(&[1u8; 32]).public().into(),
+				},
+			);
+
+			let rotation = pallet_validator_keys_controller::KeyRotation {
+				current: AuraId::from(current.public()),
+				aura: AuraId::from(rotated.public()),
+				grandpa: ed25519::Pair::from_seed(&[2u8; 32]).public().into(),
+				block_number: 0,
+			};
+			let signature = current.sign(&rotation.encode()).into();
+			assert_ok!(ValidatorKeysController::rotate_keys(
+				RuntimeOrigin::none(),
+				rotation,
+				signature
+			));
+
+			// The rotated key authors the first block of the next session.
+			pallet_aura::Authorities::<Runtime>::put(
+				BoundedVec::try_from(vec![AuraId::from(rotated.public())]).unwrap(),
+			);
+			System::initialize(
+				&1,
+				&Default::default(),
+				&Digest {
+					logs: vec![DigestItem::PreRuntime(
+						AURA_ENGINE_ID,
+						Slot::from(1).encode(),
+					)],
+				},
+			);
+
+			ValidatorSet::on_finalize(1);
+			assert_eq!(
+				pallet_validator_set::LastAuthoredBlock::<Runtime>::get(validator),
+				1
+			);
+
+			// Transaction fees are credited to the author the EVM finds for the block.
+			let author = pallet_evm::Pallet::<Runtime>::find_author();
+			assert_eq!(author, H160::from(validator));
+
+			let token = H160::from_low_u64_be(1);
+			assert_ok!(DNTFeeController::pay_fees(
+				token,
+				(1.into(), 1.into()),
+				1_000.into(),
+				author,
+				None,
+				&[]
+			));
+			assert_eq!(
+				FeeRewardsVault::get_claimable_reward(validator.into(), token),
+				1_000.into()
+			);
+		});
+	}
 }
@@ -67,6 +67,67 @@ pub const SESSION_MINUTES_DURATION: u32 = 2;
 
 pub const VALIDATOR_SET_MIN_VALIDATORS: u32 = 1;
 
+/// Number of past sessions for which validator performance statistics are kept.
+pub const VALIDATOR_SET_STATS_HISTORY_DEPTH: u32 = 30;
+
+/// Maximum length of a validator maintenance window.
+pub const VALIDATOR_SET_MAX_MAINTENANCE_HOURS: u32 = 24;
+
+/// Maximum length, in bytes, of each text field of the validator metadata.
+pub const VALIDATOR_SET_MAX_METADATA_LENGTH: u32 = 128;
+
+/// Time a jailed validator has to wait before it can rejoin the validator set.
+pub const VALIDATOR_SET_JAIL_HOURS: u32 = 24;
+
+/// Number of offline removals within `VALIDATOR_SET_OFFLINE_REMOVAL_WINDOW_HOURS`
+/// after which a validator is jailed.
+pub const VALIDATOR_SET_MAX_OFFLINE_REMOVALS: u32 = 3;
+
+/// Window offline removals are counted in.
+pub const VALIDATOR_SET_OFFLINE_REMOVAL_WINDOW_HOURS: u32 = 24;
+
+/// Maximum number of times a validator can rejoin the validator set within
+/// `VALIDATOR_SET_REJOIN_WINDOW_HOURS`.
+pub const VALIDATOR_SET_MAX_REJOIN_ATTEMPTS: u32 = 3;
+
+/// Window rejoin attempts are counted in.
+pub const VALIDATOR_SET_REJOIN_WINDOW_HOURS: u32 = 24;
+
+/// Number of sessions in a row a validator can miss finality votes before
+/// being removed as offline.
+pub const VALIDATOR_SET_MAX_MISSED_FINALITY_EPOCHS: u32 = 3;
+
+/// Number of GRANDPA justifications that must be reported in a session for
+/// the validators missing from all of them to miss the session.
+pub const VALIDATOR_SET_MIN_FINALITY_JUSTIFICATIONS: u32 = 10;
+
+// Equivocations
+
+/// Number of sessions during which an equivocation can still be reported.
+pub const EQUIVOCATION_REPORT_SESSIONS: u32 = 720;
+
+// Conversion Rate Controllers
+
+/// Gas available to a conversion rate controller unless its validator sets another limit.
+pub const CONVERSION_RATE_DEFAULT_GAS_LIMIT: u64 = 3_000_000;
+/// Highest gas limit a validator can set for its conversion rate controller.
+pub const CONVERSION_RATE_MAX_GAS_LIMIT: u64 = 10_000_000;
+
+// Fee Rewards Vault
+
+/// Holders paid out per block while an automatic fee rewards payout round is in progress.
+pub const FEE_REWARDS_MAX_PAYOUTS_PER_BLOCK: u32 = 20;
+
 // Gas Base Fee
 pub const GAS_BASE_FEE: u128 = 1_000_000_000;
 pub const DEFAULT_ELASTICITY: Permill = Permill::from_parts(0);
+/// Lowest base fee per gas governance can set.
+pub const MIN_GAS_BASE_FEE: u128 = 100_000_000;
+/// Highest base fee per gas governance can set.
+pub const MAX_GAS_BASE_FEE: u128 = 100_000_000_000;
+/// Largest change of the base fee per gas in a single governance update.
+pub const MAX_GAS_BASE_FEE_CHANGE: Permill = Permill::from_percent(50);
+/// Highest elasticity governance can set.
+pub const MAX_ELASTICITY: Permill = Permill::from_percent(50);
+/// Largest change of the elasticity in a single governance update.
+pub const MAX_ELASTICITY_CHANGE: Permill = Permill::from_percent(25);
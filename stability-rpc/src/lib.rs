@@ -18,7 +18,7 @@
 // information.
 
 use futures_util::TryFutureExt;
-use jsonrpsee::types::ErrorObject;
+use jsonrpsee::types::{ErrorObject, ErrorObjectOwned};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use sc_transaction_pool_api::TransactionSource;
 use sp_api::ProvideRuntimeApi;
@@ -26,6 +26,9 @@ use sp_blockchain::HeaderBackend;
 use sp_core::{Bytes, H160, H256};
 use sp_runtime::traits::Block as BlockT;
 pub use stability_rpc_api::StabilityRpcApi as StabilityRpcRuntimeApi;
+use stability_rpc_api::{
+	BaseFeeParameters, ClaimableReward, ValidatorMetadata, ValidatorSessionStats,
+};
 use std::{
 	str::{self},
 	sync::Arc,
@@ -42,6 +45,28 @@ pub struct StabilityOutput<T> {
 	value: T,
 }
 
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorMetadataOutput {
+	pub display_name: String,
+	pub website: String,
+	pub contact: String,
+	pub peer_id: String,
+	pub payout_address: H160,
+}
+
+impl From<ValidatorMetadata> for ValidatorMetadataOutput {
+	fn from(metadata: ValidatorMetadata) -> Self {
+		Self {
+			display_name: String::from_utf8_lossy(&metadata.display_name).into_owned(),
+			website: String::from_utf8_lossy(&metadata.website).into_owned(),
+			contact: String::from_utf8_lossy(&metadata.contact).into_owned(),
+			peer_id: String::from_utf8_lossy(&metadata.peer_id).into_owned(),
+			payout_address: metadata.payout_address,
+		}
+	}
+}
+
 #[rpc(server)]
 pub trait StabilityRpcEndpoints<BlockHash> {
 	#[method(name = "stability_getSupportedTokens")]
@@ -56,6 +81,33 @@ pub trait StabilityRpcEndpoints<BlockHash> {
 		at: Option<BlockHash>,
 	) -> RpcResult<StabilityOutput<Vec<H160>>>;
 
+	#[method(name = "stability_getValidatorStats")]
+	fn get_validator_stats(
+		&self,
+		validator: H160,
+		at: Option<BlockHash>,
+	) -> RpcResult<StabilityOutput<Vec<ValidatorSessionStats>>>;
+
+	#[method(name = "stability_getValidatorMetadata")]
+	fn get_validator_metadata(
+		&self,
+		validator: H160,
+		at: Option<BlockHash>,
+	) -> RpcResult<StabilityOutput<Option<ValidatorMetadataOutput>>>;
+
+	#[method(name = "stability_getClaimableRewards")]
+	fn get_claimable_rewards(
+		&self,
+		holder: H160,
+		at: Option<BlockHash>,
+	) -> RpcResult<StabilityOutput<Vec<ClaimableReward>>>;
+
+	#[method(name = "stability_getBaseFeeParameters")]
+	fn get_base_fee_parameters(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<StabilityOutput<BaseFeeParameters>>;
+
 	#[method(name = "stability_sendSponsoredTransaction")]
 	async fn send_sponsored_transaction(
 		&self,
@@ -136,6 +188,60 @@ where
 		})
 	}
 
+	fn get_validator_stats(
+		&self,
+		validator: H160,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<StabilityOutput<Vec<ValidatorSessionStats>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let value = api
+			.get_validator_stats(at, validator)
+			.map_err(runtime_error_into_rpc_err)?;
+		Ok(StabilityOutput { code: 200, value })
+	}
+
+	fn get_validator_metadata(
+		&self,
+		validator: H160,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<StabilityOutput<Option<ValidatorMetadataOutput>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let value = api
+			.get_validator_metadata(at, validator)
+			.map_err(runtime_error_into_rpc_err)?;
+		Ok(StabilityOutput {
+			code: 200,
+			value: value.map(Into::into),
+		})
+	}
+
+	fn get_claimable_rewards(
+		&self,
+		holder: H160,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<StabilityOutput<Vec<ClaimableReward>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let value = api
+			.get_claimable_rewards(at, holder)
+			.map_err(runtime_error_into_rpc_err)?;
+		Ok(StabilityOutput { code: 200, value })
+	}
+
+	fn get_base_fee_parameters(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<StabilityOutput<BaseFeeParameters>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let value = api
+			.get_base_fee_parameters(at)
+			.map_err(runtime_error_into_rpc_err)?;
+		Ok(StabilityOutput { code: 200, value })
+	}
+
 	async fn send_sponsored_transaction(
 		&self,
 		transaction: Bytes,
@@ -195,10 +301,6 @@ where
 const RUNTIME_ERROR: i32 = 1;
 
 /// Converts a runtime trap into an RPC error.
-fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> RpcResult<String> {
-	Err(ErrorObject::owned(
-		RUNTIME_ERROR,
-		"Runtime error",
-		Some(format!("{:?}", err)),
-	))
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> ErrorObjectOwned {
+	ErrorObject::owned(RUNTIME_ERROR, "Runtime error", Some(format!("{:?}", err)))
 }
@@ -35,6 +35,28 @@ sp_api::mock_impl_runtime_apis! {
 			vec![H160::from_str("0xaf537bd156c7E548D0BF2CD43168dABF7aF2feb5").expect("Bad account id format"),
 			H160::from_str("0xf25F864329C44b2aA103De1dFf6fA020b85D8C07").expect("Bad account id format")]
 		}
+
+		fn get_validator_stats(_validator: H160) -> Vec<ValidatorSessionStats> {
+			vec![ValidatorSessionStats { session_index: 7, blocks_authored: 9, slots_assigned: 10 }]
+		}
+
+		fn get_validator_metadata(validator: H160) -> Option<ValidatorMetadata> {
+			Some(ValidatorMetadata {
+				display_name: b"Stability".to_vec(),
+				website: b"https://stabilityprotocol.com".to_vec(),
+				contact: b"validators@stabilityprotocol.com".to_vec(),
+				peer_id: b"12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp".to_vec(),
+				payout_address: validator,
+			})
+		}
+
+		fn get_claimable_rewards(_holder: H160) -> Vec<ClaimableReward> {
+			vec![ClaimableReward { token: H160::from_str("0xaf537bd156c7E548D0BF2CD43168dABF7aF2feb5").expect("Bad account id format"), amount: 100.into() }]
+		}
+
+		fn get_base_fee_parameters() -> BaseFeeParameters {
+			BaseFeeParameters { base_fee_per_gas: 1_000_000_000u64.into(), elasticity: 125_000 }
+		}
 	}
 
 	impl fp_rpc::EthereumRuntimeRPCApi<Block> for TestRuntimeApi {}
@@ -69,3 +91,77 @@ async fn get_validator_list_should_return_a_validator_list() {
 		.expect("Bad account id format")];
 	assert_eq!(expected, result_unwrap);
 }
+
+#[tokio::test]
+async fn get_validator_stats_should_return_session_stats() {
+	let client = Arc::new(TestApi {});
+	let pool = Arc::new(MockedMempool::default());
+	let api = StabilityRpc::<TestApi, MockedMempool, Block>::new(client, pool);
+	let validator = H160::from_str("0xaf537bd156c7E548D0BF2CD43168dABF7aF2feb5")
+		.expect("Bad account id format");
+	let result = api.get_validator_stats(validator, None);
+	assert_eq!(true, result.is_ok());
+	let expected = vec![ValidatorSessionStats {
+		session_index: 7,
+		blocks_authored: 9,
+		slots_assigned: 10,
+	}];
+	assert_eq!(expected, result.unwrap().value);
+}
+
+#[tokio::test]
+async fn get_validator_metadata_should_return_readable_metadata() {
+	let client = Arc::new(TestApi {});
+	let pool = Arc::new(MockedMempool::default());
+	let api = StabilityRpc::<TestApi, MockedMempool, Block>::new(client, pool);
+	let validator = H160::from_str("0xaf537bd156c7E548D0BF2CD43168dABF7aF2feb5")
+		.expect("Bad account id format");
+	let result = api.get_validator_metadata(validator, None);
+	assert_eq!(true, result.is_ok());
+	let expected = ValidatorMetadataOutput {
+		display_name: "Stability".to_string(),
+		website: "https://stabilityprotocol.com".to_string(),
+		contact: "validators@stabilityprotocol.com".to_string(),
+		peer_id: "12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp".to_string(),
+		payout_address: validator,
+	};
+	assert_eq!(Some(expected), result.unwrap().value);
+}
+
+#[tokio::test]
+async fn get_claimable_rewards_should_return_token_amounts() {
+	let client = Arc::new(TestApi {});
+	let pool = Arc::new(MockedMempool::default());
+	let api = StabilityRpc::<TestApi, MockedMempool, Block>::new(client, pool);
+	let holder = H160::from_str("0xf25F864329C44b2aA103De1dFf6fA020b85D8C07")
+		.expect("Bad account id format");
+	let result = api.get_claimable_rewards(holder, None);
+	assert_eq!(true, result.is_ok());
+	let expected = vec![ClaimableReward {
+		token: H160::from_str("0xaf537bd156c7E548D0BF2CD43168dABF7aF2feb5")
+			.expect("Bad account id format"),
+		amount: 100.into(),
+	}];
+	assert_eq!(expected, result.unwrap().value);
+}
+
+#[tokio::test]
+async fn get_base_fee_parameters_should_return_current_values() {
+	let client = Arc::new(TestApi {});
+	let pool = Arc::new(MockedMempool::default());
+	let api = StabilityRpc::<TestApi, MockedMempool, Block>::new(client, pool);
+	let result = api.get_base_fee_parameters(None);
+	assert_eq!(true, result.is_ok());
+	let expected = BaseFeeParameters {
+		base_fee_per_gas: 1_000_000_000u64.into(),
+		elasticity: 125_000,
+	};
+	assert_eq!(expected, result.unwrap().value);
+}
+
+#[test]
+fn runtime_errors_are_returned_as_rpc_errors() {
+	let error = runtime_error_into_rpc_err("Exported method not found");
+	assert_eq!(RUNTIME_ERROR, error.code());
+	assert_eq!("Runtime error", error.message());
+}